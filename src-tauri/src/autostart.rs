@@ -0,0 +1,189 @@
+// OSのログイン時自動起動にランチャーを登録・解除する
+//
+// - Windows: HKCU\Software\Microsoft\Windows\CurrentVersion\Run
+// - macOS: ~/Library/LaunchAgents の plist
+// - Linux: ~/.config/autostart の .desktop ファイル
+
+#[cfg(not(target_os = "windows"))]
+use std::path::PathBuf;
+
+#[cfg(target_os = "windows")]
+const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+#[cfg(target_os = "windows")]
+const ENTRY_NAME: &str = "AjiponzuUtilityLauncher";
+
+#[cfg(target_os = "macos")]
+const LAUNCH_AGENT_LABEL: &str = "com.cpjvm.ajiponzu-utility-launcher";
+
+// ランチャー自身の実行ファイルのパス
+fn launcher_executable() -> Result<String, String> {
+    std::env::current_exe()
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| format!("Failed to get launcher executable path: {}", e))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn home_dir() -> Result<PathBuf, String> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| "HOME is not set".to_string())
+}
+
+// 自動起動エントリのパス
+#[cfg(target_os = "macos")]
+pub fn entry_path() -> Result<PathBuf, String> {
+    Ok(home_dir()?
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", LAUNCH_AGENT_LABEL)))
+}
+
+// 自動起動エントリのパス
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+pub fn entry_path() -> Result<PathBuf, String> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home_dir()?.join(".config"),
+    };
+    Ok(config_dir
+        .join("autostart")
+        .join("ajiponzu-utility-launcher.desktop"))
+}
+
+// 自動起動エントリの説明（レジストリのキーまたはファイルのパス）
+pub fn entry_location() -> String {
+    #[cfg(target_os = "windows")]
+    {
+        format!(r"HKCU\{}\{}", RUN_KEY, ENTRY_NAME)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        entry_path()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+// ランチャーが自動起動に登録されているか確認
+#[cfg(target_os = "windows")]
+pub fn is_enabled() -> bool {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(RUN_KEY)
+        .and_then(|key| key.get_value::<String, _>(ENTRY_NAME))
+        .is_ok()
+}
+
+// ランチャーが自動起動に登録されているか確認
+#[cfg(not(target_os = "windows"))]
+pub fn is_enabled() -> bool {
+    entry_path().map(|path| path.exists()).unwrap_or(false)
+}
+
+// 登録されているエントリが今の実行ファイルを起動する内容か（ランチャーを移動・更新した後は古いパスのままになる）
+#[cfg(target_os = "windows")]
+pub fn is_current() -> bool {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let Ok(executable) = launcher_executable() else {
+        return false;
+    };
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(RUN_KEY)
+        .and_then(|key| key.get_value::<String, _>(ENTRY_NAME))
+        .is_ok_and(|value| value == format!("\"{}\"", executable))
+}
+
+// 登録されているエントリが今の実行ファイルを起動する内容か（ランチャーを移動・更新した後は古いパスのままになる）
+#[cfg(not(target_os = "windows"))]
+pub fn is_current() -> bool {
+    let (Ok(path), Ok(executable)) = (entry_path(), launcher_executable()) else {
+        return false;
+    };
+    std::fs::read_to_string(path).is_ok_and(|contents| contents == entry_contents(&executable))
+}
+
+// 自動起動への登録・解除
+#[cfg(target_os = "windows")]
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_SET_VALUE};
+    use winreg::RegKey;
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    if enabled {
+        let (key, _) = hkcu
+            .create_subkey(RUN_KEY)
+            .map_err(|e| format!("Failed to open Run key: {}", e))?;
+        key.set_value(ENTRY_NAME, &format!("\"{}\"", launcher_executable()?))
+            .map_err(|e| format!("Failed to register autostart: {}", e))
+    } else {
+        match hkcu.open_subkey_with_flags(RUN_KEY, KEY_SET_VALUE) {
+            Ok(key) => match key.delete_value(ENTRY_NAME) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(format!("Failed to unregister autostart: {}", e)),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to open Run key: {}", e)),
+        }
+    }
+}
+
+// 自動起動への登録・解除
+#[cfg(not(target_os = "windows"))]
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    let path = entry_path()?;
+    if enabled {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, entry_contents(&launcher_executable()?))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    } else {
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to remove {}: {}", path.display(), e)),
+        }
+    }
+}
+
+// LaunchAgent の plist の内容
+#[cfg(target_os = "macos")]
+fn entry_contents(executable: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        LAUNCH_AGENT_LABEL,
+        executable
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    )
+}
+
+// XDG autostart の .desktop ファイルの内容
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+fn entry_contents(executable: &str) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName=Ajiponzu Utility Launcher\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+        executable.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
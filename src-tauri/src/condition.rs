@@ -0,0 +1,132 @@
+// 自動起動の条件
+//
+// 条件を満たさない場合は自動起動をスキップする（時間帯・他のプロセスの実行・VPNなどのネットワークの状態）
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+use super::{count_processes_by_name, process_name_from_path};
+
+// 条件の種類
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LaunchCondition {
+    // 時刻が start から end の間（"HH:MM"。end が start より前の場合は日付をまたぐ）
+    TimeWindow {
+        start: String,
+        end: String,
+    },
+    // 指定した名前のプロセスが実行中（"chrome.exe" のように拡張子を付けてもよい）
+    ProcessRunning {
+        name: String,
+    },
+    // TCPのポートに接続できる（VPNの内側のサーバーなど）
+    TcpReachable {
+        host: String,
+        port: u16,
+        #[serde(default = "default_timeout_ms", alias = "timeoutMs")]
+        timeout_ms: u64,
+    },
+}
+
+fn default_timeout_ms() -> u64 {
+    1000
+}
+
+// "HH:MM" を時刻にする
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time '{}' (expected HH:MM)", value))
+}
+
+// 設定が正しいか確認（登録・更新時）
+pub fn validate(condition: &LaunchCondition) -> Result<(), String> {
+    match condition {
+        LaunchCondition::TimeWindow { start, end } => {
+            if parse_time(start)? == parse_time(end)? {
+                return Err("Launch condition start and end times must differ".to_string());
+            }
+        }
+        LaunchCondition::ProcessRunning { name } => {
+            if process_name_from_path(name).is_none() {
+                return Err("Launch condition process name must not be empty".to_string());
+            }
+        }
+        LaunchCondition::TcpReachable {
+            host,
+            port,
+            timeout_ms,
+        } => {
+            if host.trim().is_empty() {
+                return Err("Launch condition host must not be empty".to_string());
+            }
+            if *port == 0 {
+                return Err("Launch condition port must not be 0".to_string());
+            }
+            if *timeout_ms == 0 {
+                return Err("Launch condition timeout must be greater than 0".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+// 時刻が時間帯に含まれるか（開始は含み、終了は含まない）
+fn in_time_window(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+// 条件を満たすか確認し、満たさない場合は理由をエラーで返す
+pub async fn evaluate(condition: &LaunchCondition) -> Result<(), String> {
+    match condition {
+        LaunchCondition::TimeWindow { start, end } => {
+            let now = chrono::Local::now().time();
+            if in_time_window(now, parse_time(start)?, parse_time(end)?) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "current time {} is outside {}-{}",
+                    now.format("%H:%M"),
+                    start,
+                    end
+                ))
+            }
+        }
+        LaunchCondition::ProcessRunning { name } => {
+            let process_name = process_name_from_path(name)
+                .ok_or_else(|| "Launch condition process name is empty".to_string())?;
+            // プロセスの一覧の取得には時間がかかるため別のスレッドで行う
+            let count = tauri::async_runtime::spawn_blocking(move || {
+                count_processes_by_name(&process_name)
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+            if count > 0 {
+                Ok(())
+            } else {
+                Err(format!("process {} is not running", name))
+            }
+        }
+        LaunchCondition::TcpReachable {
+            host,
+            port,
+            timeout_ms,
+        } => {
+            let connect = TcpStream::connect((host.as_str(), *port));
+            match tokio::time::timeout(Duration::from_millis(*timeout_ms), connect).await {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err(e)) => Err(format!("{}:{} is not reachable: {}", host, port, e)),
+                Err(_) => Err(format!(
+                    "{}:{} did not respond within {} ms",
+                    host, port, timeout_ms
+                )),
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// コマンドからフロントエンドへ返すエラー
+//
+// フロントエンドでメッセージの文字列を比較せずに済むよう、安定したコード（code）で分類する
+// message は画面に表示する内容、detail は補足（分類の元になったエラーなど）
+
+use serde::Serialize;
+
+// エラーの分類（フロントエンドでは "APP_NOT_FOUND" のような文字列で受け取る。値は変更しない）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // 登録されたアプリが見つからない
+    AppNotFound,
+    // 起動グループが見つからない
+    GroupNotFound,
+    // 設定ファイル・プロファイル・バックアップが見つからない
+    ConfigNotFound,
+    // ファイル・ディレクトリなどが見つからない
+    NotFound,
+    // アプリが実行中でない（一時停止していない・出力を取得していない場合を含む）
+    ProcessNotRunning,
+    // 既に存在する・既にその状態になっている
+    AlreadyExists,
+    // 同じ名前のアプリが既に登録されている
+    DuplicateName,
+    // 入力・設定の値が正しくない
+    InvalidInput,
+    // この環境・この設定では使えない
+    Unsupported,
+    // 権限がない・書き込めない
+    PermissionDenied,
+    // 制限時間内に終わらなかった
+    Timeout,
+    // 起動・停止に失敗した
+    LaunchFailed,
+    // 直前に同じアプリを起動しようとしたため無視した
+    Debounced,
+    // ファイルの読み書きに失敗した
+    Io,
+    // 上記以外
+    Internal,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub detail: Option<String>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    // バックグラウンドのタスクが完了しなかった（パニックした・取り消された）
+    pub fn background_task(error: impl std::fmt::Display) -> Self {
+        Self::new(ErrorCode::Internal, "Background task failed").with_detail(error.to_string())
+    }
+}
+
+// 内部の関数は String のエラーを返すため、? でそのまま変換できるようにする
+// （メッセージからは分類しない。分類が必要なエラーはエラーの発生元で CommandError::new に code を指定する）
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+// コマンドを内部から呼び出す場合（String のエラーを返す関数の中など）はメッセージだけを使う
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.message
+    }
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
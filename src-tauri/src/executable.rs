@@ -0,0 +1,339 @@
+// 実行ファイルのヘッダー（PE・ELF・Mach-O）の読み取り
+//
+// 登録しようとしている実行ファイルがこのマシンで動作するか（アーキテクチャ・OS）を確認するために使う
+// （起動前に実行ファイルが差し替えられていないかを確認するためのハッシュの計算もここで行う）
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+// 実行ファイルの情報
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ExeInfo {
+    // "x86"・"x86_64"・"arm"・"aarch64" など（std::env::consts::ARCH と同じ名前。
+    // ユニバーサルバイナリは "x86_64+aarch64" のように連結する）
+    pub arch: String,
+    // GUIアプリか（PEのサブシステムから判定。判定できない形式は None）
+    pub is_gui: Option<bool>,
+    // "windows"・"linux"・"macos" など
+    pub target_os: String,
+    // このマシンで動作しない可能性がある場合の警告
+    pub warning: Option<String>,
+}
+
+// ヘッダーから読み取った (アーキテクチャ, GUIアプリか, OS)
+type HeaderInfo = (String, Option<bool>, String);
+
+// 指定した位置からバイト列を読み取る
+fn read_at(file: &mut File, offset: u64, len: usize) -> Result<Vec<u8>, String> {
+    let mut buffer = vec![0u8; len];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut buffer))
+        .map_err(|e| format!("Failed to read executable header: {}", e))?;
+    Ok(buffer)
+}
+
+fn u16_le(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+fn u32_be(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap_or_default())
+}
+
+// 実行ファイルのヘッダーを読み取る（対応していない形式はエラー）
+pub fn inspect(path: &str) -> Result<ExeInfo, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let magic =
+        read_at(&mut file, 0, 4).map_err(|_| format!("Not an executable binary: {}", path))?;
+
+    let (arch, is_gui, target_os) = if magic.starts_with(b"MZ") {
+        inspect_pe(&mut file)?
+    } else if magic == b"\x7FELF" {
+        inspect_elf(&mut file)?
+    } else {
+        inspect_mach_o(&mut file, &magic)
+            .ok_or_else(|| format!("Not an executable binary: {}", path))??
+    };
+
+    let warning = compatibility_warning(&arch, &target_os);
+    Ok(ExeInfo {
+        arch,
+        is_gui,
+        target_os,
+        warning,
+    })
+}
+
+// Windowsの実行ファイル（PE）
+fn inspect_pe(file: &mut File) -> Result<HeaderInfo, String> {
+    const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+
+    let dos_header = read_at(file, 0, 64)?;
+    let pe_offset = u32_le(&dos_header, 0x3C) as u64;
+    // シグネチャ(4) + COFFヘッダー(20) + オプションヘッダーのサブシステムまで(70)
+    let header = read_at(file, pe_offset, 4 + 20 + 70)?;
+    if &header[0..4] != b"PE\0\0" {
+        return Err("Invalid PE signature".to_string());
+    }
+    let arch = match u16_le(&header, 4) {
+        0x014C => "x86",
+        0x8664 => "x86_64",
+        0x01C0 | 0x01C4 => "arm",
+        0xAA64 => "aarch64",
+        0x0200 => "ia64",
+        _ => "unknown",
+    };
+    let subsystem = u16_le(&header, 24 + 68);
+    Ok((
+        arch.to_string(),
+        Some(subsystem == IMAGE_SUBSYSTEM_WINDOWS_GUI),
+        "windows".to_string(),
+    ))
+}
+
+// PEに埋め込まれたマニフェストの requestedExecutionLevel（"asInvoker"・"requireAdministrator" など）
+// マニフェストがない・実行レベルの指定がない場合は None
+pub fn requested_execution_level(path: &str) -> Result<Option<String>, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let Some(manifest) = read_pe_manifest(&mut file)? else {
+        return Ok(None);
+    };
+    let manifest = String::from_utf8_lossy(&manifest);
+    let Some(start) = manifest.find("requestedExecutionLevel") else {
+        return Ok(None);
+    };
+    let element = &manifest[start..];
+    let element = &element[..element.find('>').unwrap_or(element.len())];
+    let Some(level) = element.find("level=") else {
+        return Ok(None);
+    };
+    let value = &element[level + "level=".len()..];
+    let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+        return Ok(None);
+    };
+    let value = &value[1..];
+    Ok(value.find(quote).map(|end| value[..end].to_string()))
+}
+
+// マニフェストが管理者権限を要求しているか（読み取れない場合は要求していないとみなす）
+pub fn requires_administrator(path: &str) -> bool {
+    requested_execution_level(path)
+        .ok()
+        .flatten()
+        .is_some_and(|level| level == "requireAdministrator")
+}
+
+// ファイルのSHA-256（小文字の16進数。大きな実行ファイルでもメモリに読み込まないよう少しずつ読む）
+pub fn sha256(path: &str) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+// PEのリソースからマニフェスト（RT_MANIFEST）を読み取る（PEでない・マニフェストがない場合は None）
+fn read_pe_manifest(file: &mut File) -> Result<Option<Vec<u8>>, String> {
+    const RT_MANIFEST: u32 = 24;
+    const RESOURCE_DIRECTORY_INDEX: usize = 2;
+    const MAX_MANIFEST_SIZE: u32 = 1024 * 1024;
+
+    let dos_header = read_at(file, 0, 64)?;
+    if !dos_header.starts_with(b"MZ") {
+        return Ok(None);
+    }
+    let pe_offset = u32_le(&dos_header, 0x3C) as u64;
+    let coff = read_at(file, pe_offset, 4 + 20)?;
+    if &coff[0..4] != b"PE\0\0" {
+        return Err("Invalid PE signature".to_string());
+    }
+    let section_count = u16_le(&coff, 4 + 2) as usize;
+    let optional_header_size = u16_le(&coff, 4 + 16) as usize;
+    let optional_header = read_at(file, pe_offset + 24, optional_header_size)?;
+    // データディレクトリの位置は PE32（0x10B）と PE32+（0x20B）で異なる
+    let (count_offset, directories_offset) = match u16_le(&optional_header, 0) {
+        0x10B => (92, 96),
+        0x20B => (108, 112),
+        _ => return Err("Invalid PE optional header".to_string()),
+    };
+    if optional_header_size < directories_offset + (RESOURCE_DIRECTORY_INDEX + 1) * 8
+        || (u32_le(&optional_header, count_offset) as usize) <= RESOURCE_DIRECTORY_INDEX
+    {
+        return Ok(None);
+    }
+    let resource_rva = u32_le(
+        &optional_header,
+        directories_offset + RESOURCE_DIRECTORY_INDEX * 8,
+    );
+    if resource_rva == 0 {
+        return Ok(None);
+    }
+
+    // RVA をファイル上の位置に変換する（セクションの一覧から探す）
+    let sections = read_at(
+        file,
+        pe_offset + 24 + optional_header_size as u64,
+        section_count * 40,
+    )?;
+    let file_offset = |rva: u32| {
+        sections.chunks_exact(40).find_map(|section| {
+            let virtual_address = u32_le(section, 12);
+            let size = u32_le(section, 8).max(u32_le(section, 16));
+            (rva >= virtual_address && rva - virtual_address < size)
+                .then(|| (u32_le(section, 20) + (rva - virtual_address)) as u64)
+        })
+    };
+    let Some(resource_offset) = file_offset(resource_rva) else {
+        return Ok(None);
+    };
+
+    // リソースディレクトリは 種類 → 名前 → 言語 の3階層（種類だけ RT_MANIFEST を探し、他は最初のエントリを使う）
+    let mut entry_offset = 0u32;
+    for (level, type_id) in [Some(RT_MANIFEST), None, None].into_iter().enumerate() {
+        let directory = read_at(file, resource_offset + entry_offset as u64, 16)?;
+        let named = u16_le(&directory, 12) as usize;
+        let ids = u16_le(&directory, 14) as usize;
+        let entries = read_at(
+            file,
+            resource_offset + entry_offset as u64 + 16,
+            (named + ids) * 8,
+        )?;
+        let entry = entries.chunks_exact(8).find(|entry| match type_id {
+            Some(id) => u32_le(entry, 0) == id,
+            None => true,
+        });
+        let Some(entry) = entry else {
+            return Ok(None);
+        };
+        // 最後の階層はデータを、それ以外は下の階層のディレクトリ（最上位ビットが立つ）を指す
+        let next = u32_le(entry, 4);
+        if (next & 0x8000_0000 != 0) != (level < 2) {
+            return Ok(None);
+        }
+        entry_offset = next & 0x7FFF_FFFF;
+    }
+
+    let data_entry = read_at(file, resource_offset + entry_offset as u64, 16)?;
+    let data_size = u32_le(&data_entry, 4);
+    if data_size == 0 || data_size > MAX_MANIFEST_SIZE {
+        return Ok(None);
+    }
+    let Some(data_offset) = file_offset(u32_le(&data_entry, 0)) else {
+        return Ok(None);
+    };
+    read_at(file, data_offset, data_size as usize).map(Some)
+}
+
+// Linuxなどの実行ファイル（ELF）
+fn inspect_elf(file: &mut File) -> Result<HeaderInfo, String> {
+    let header = read_at(file, 0, 20)?;
+    let machine = if header[5] == 2 {
+        u16::from_be_bytes([header[18], header[19]])
+    } else {
+        u16_le(&header, 18)
+    };
+    let arch = match machine {
+        3 => "x86",
+        62 => "x86_64",
+        40 => "arm",
+        183 => "aarch64",
+        243 => "riscv",
+        _ => "unknown",
+    };
+    let target_os = match header[7] {
+        0 | 3 => "linux",
+        9 => "freebsd",
+        _ => "unknown",
+    };
+    Ok((arch.to_string(), None, target_os.to_string()))
+}
+
+// macOSの実行ファイル（Mach-O、ユニバーサルバイナリを含む。Mach-Oでない場合は None）
+fn inspect_mach_o(file: &mut File, magic: &[u8]) -> Option<Result<HeaderInfo, String>> {
+    let cpu_arch = |cpu_type: u32| match cpu_type {
+        7 => "x86",
+        0x0100_0007 => "x86_64",
+        12 => "arm",
+        0x0100_000C => "aarch64",
+        _ => "unknown",
+    };
+    let magic = u32_be(magic, 0);
+    let arch = match magic {
+        // 32bit・64bit（ビッグエンディアン）
+        0xFEED_FACE | 0xFEED_FACF => {
+            read_at(file, 4, 4).map(|b| cpu_arch(u32_be(&b, 0)).to_string())
+        }
+        // 32bit・64bit（リトルエンディアン）
+        0xCEFA_EDFE | 0xCFFA_EDFE => {
+            read_at(file, 4, 4).map(|b| cpu_arch(u32_le(&b, 0)).to_string())
+        }
+        // ユニバーサルバイナリ（Javaのクラスファイルも同じマジックのため、アーキテクチャ数で区別する）
+        0xCAFE_BABE => {
+            let count = match read_at(file, 4, 4) {
+                Ok(b) => u32_be(&b, 0),
+                Err(e) => return Some(Err(e)),
+            };
+            if count == 0 || count > 16 {
+                return None;
+            }
+            read_at(file, 8, count as usize * 20).map(|entries| {
+                entries
+                    .chunks_exact(20)
+                    .map(|entry| cpu_arch(u32_be(entry, 0)))
+                    .collect::<Vec<_>>()
+                    .join("+")
+            })
+        }
+        _ => return None,
+    };
+    Some(arch.map(|arch| (arch, None, "macos".to_string())))
+}
+
+// このマシンで動作しない可能性があれば警告を返す
+fn compatibility_warning(arch: &str, target_os: &str) -> Option<String> {
+    let host_os = std::env::consts::OS;
+    let host_arch = std::env::consts::ARCH;
+    if target_os != "unknown" && target_os != host_os {
+        return Some(format!(
+            "This executable is built for {} but this machine runs {}",
+            target_os, host_os
+        ));
+    }
+    if arch == "unknown" {
+        return None;
+    }
+    // 64bitのOSは32bitを、ARMのWindows・macOSはエミュレーションでx86系を実行できる
+    let runnable = |exe_arch: &str| {
+        exe_arch == host_arch
+            || (host_arch == "x86_64" && exe_arch == "x86")
+            || (host_arch == "aarch64" && host_os == "windows" && exe_arch.starts_with("x86"))
+            || (host_arch == "aarch64" && host_os == "macos" && exe_arch == "x86_64")
+    };
+    if arch.split('+').any(runnable) {
+        None
+    } else {
+        Some(format!(
+            "This executable is built for {} but this machine is {}",
+            arch, host_arch
+        ))
+    }
+}
@@ -0,0 +1,166 @@
+// 他のランチャーの設定の読み込み
+//
+// 他のランチャーからエクスポートしたJSONを、名前・パス・引数などの共通の形に変換する
+// （対応する形式を増やす場合は FORMATS に変換の関数を追加する）
+
+use serde::Serialize;
+use serde_json::Value;
+
+// 他のランチャーの1つのアプリ（RegisteredApp に変換する前の共通の形）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignApp {
+    pub name: String,
+    pub path: String,
+    pub arguments: String,
+    pub working_directory: String,
+    pub description: String,
+}
+
+// 変換できなかったエントリ
+#[derive(Debug, Serialize, Clone)]
+pub struct SkippedEntry {
+    // 元のJSONでの位置（1から数える）
+    pub index: usize,
+    // 分かる場合はエントリの名前
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+// 変換の結果（変換できない場合は (分かればエントリの名前, 理由)）
+type ConvertedEntry = Result<ForeignApp, (Option<String>, String)>;
+
+// 対応している形式
+#[derive(Debug, Serialize, Clone)]
+pub struct ForeignFormat {
+    pub id: &'static str,
+    pub description: &'static str,
+    // エントリの一覧を取り出し、1つずつ変換する（変換できないエントリは理由を返す）
+    #[serde(skip)]
+    convert: fn(&Value) -> Result<Vec<ConvertedEntry>, String>,
+}
+
+pub const FORMATS: &[ForeignFormat] = &[ForeignFormat {
+    id: "title-exe",
+    description: "title / exe / args (/ cwd) fields, as an array or under \"apps\"",
+    convert: convert_title_exe,
+}];
+
+// 指定した形式で変換し、変換できたアプリと変換できなかったエントリを返す
+pub fn convert(format: &str, json: &str) -> Result<(Vec<ForeignApp>, Vec<SkippedEntry>), String> {
+    let foreign_format = FORMATS
+        .iter()
+        .find(|f| f.id.eq_ignore_ascii_case(format.trim()))
+        .ok_or_else(|| {
+            let ids: Vec<&str> = FORMATS.iter().map(|f| f.id).collect();
+            format!(
+                "Unknown import format '{}' (expected one of: {})",
+                format,
+                ids.join(", ")
+            )
+        })?;
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid import JSON: {}", e))?;
+
+    let mut apps = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, entry) in (foreign_format.convert)(&value)?.into_iter().enumerate() {
+        match entry {
+            Ok(app) => apps.push(app),
+            Err((name, reason)) => skipped.push(SkippedEntry {
+                index: index + 1,
+                name,
+                reason,
+            }),
+        }
+    }
+    Ok((apps, skipped))
+}
+
+// エントリの配列を取り出す（配列そのもの、または指定したキーのいずれかの配列）
+fn entry_array<'a>(value: &'a Value, keys: &[&str]) -> Result<&'a Vec<Value>, String> {
+    if let Some(entries) = value.as_array() {
+        return Ok(entries);
+    }
+    keys.iter()
+        .find_map(|key| value.get(key).and_then(Value::as_array))
+        .ok_or_else(|| {
+            format!(
+                "Expected an array of entries or an object with one of: {}",
+                keys.join(", ")
+            )
+        })
+}
+
+// 文字列のフィールド（空白だけの場合はないものとする）
+fn string_field(entry: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| entry.get(key).and_then(Value::as_str))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// 引数の配列を1つの文字列にまとめる（空白や「"」を含む引数は「"」で囲む）
+fn join_arguments(arguments: &[Value]) -> Result<String, String> {
+    arguments
+        .iter()
+        .map(|argument| {
+            let argument = match argument {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                _ => return Err(format!("Unsupported argument value: {}", argument)),
+            };
+            Ok(
+                if argument.is_empty()
+                    || argument.contains(char::is_whitespace)
+                    || argument.contains('"')
+                {
+                    format!("\"{}\"", argument.replace('"', "\\\""))
+                } else {
+                    argument
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|arguments| arguments.join(" "))
+}
+
+// title・exe・args の形式（名前がない場合は実行ファイル名を使う）
+fn convert_title_exe(value: &Value) -> Result<Vec<ConvertedEntry>, String> {
+    let entries = entry_array(value, &["apps", "items", "entries"])?;
+    Ok(entries
+        .iter()
+        .map(|entry| {
+            if !entry.is_object() {
+                return Err((None, "Entry is not an object".to_string()));
+            }
+            let title = string_field(entry, &["title", "name"]);
+            let Some(path) = string_field(entry, &["exe", "path"]) else {
+                return Err((title, "Missing 'exe'".to_string()));
+            };
+            let arguments = match entry.get("args") {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(s)) => s.trim().to_string(),
+                Some(Value::Array(arguments)) => {
+                    join_arguments(arguments).map_err(|e| (title.clone(), e))?
+                }
+                Some(other) => {
+                    return Err((title, format!("Unsupported 'args' value: {}", other)));
+                }
+            };
+            let name = title.unwrap_or_else(|| {
+                std::path::Path::new(&path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.clone())
+            });
+            Ok(ForeignApp {
+                name,
+                path,
+                arguments,
+                working_directory: string_field(entry, &["cwd", "workdir"]).unwrap_or_default(),
+                description: string_field(entry, &["description", "comment"]).unwrap_or_default(),
+            })
+        })
+        .collect())
+}
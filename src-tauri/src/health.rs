@@ -0,0 +1,202 @@
+// アプリのヘルスチェック
+//
+// プロセスが起動しているかではなく、ポートが接続を受け付けるか・HTTPで期待した応答を返すかで
+// アプリが使える状態になったかを確認する（ローカルのサーバーなど向け）
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+// 確認の方法
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HealthProbe {
+    // TCPのポートに接続できるか
+    Tcp {
+        #[serde(default = "default_host")]
+        host: String,
+        port: u16,
+    },
+    // HTTPのGETで期待したステータスコードが返るか（http:// のみ対応）
+    Http {
+        url: String,
+        #[serde(default = "default_expected_status", alias = "expectedStatus")]
+        expected_status: u16,
+    },
+}
+
+// ヘルスチェックの設定
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HealthCheck {
+    #[serde(flatten)]
+    pub probe: HealthProbe,
+    // 1回の確認のタイムアウト（ミリ秒）
+    #[serde(default = "default_timeout_ms", alias = "timeoutMs")]
+    pub timeout_ms: u64,
+    // 失敗した場合に確認し直す回数
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+    // 確認し直すまでの待ち時間（ミリ秒）
+    #[serde(default = "default_retry_interval_ms", alias = "retryIntervalMs")]
+    pub retry_interval_ms: u64,
+}
+
+fn default_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_expected_status() -> u16 {
+    200
+}
+
+fn default_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_retries() -> u32 {
+    10
+}
+
+fn default_retry_interval_ms() -> u64 {
+    1000
+}
+
+// ヘルスチェックの結果
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    // ヘルスチェックが設定されていない
+    Unknown,
+    Healthy,
+    Unhealthy,
+}
+
+// http://host[:port]/path を (ホスト, ポート, パス) に分解
+fn parse_http_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .trim()
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("Health check URL must start with http://: {}", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        // IPv6アドレス（[::1]）の中のコロンはポートの区切りではない
+        Some((host, port)) if !port.ends_with(']') => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| format!("Invalid port in health check URL: {}", url))?,
+        ),
+        _ => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("Health check URL has no host: {}", url));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+// 設定が正しいか確認（登録・更新時）
+pub fn validate(check: &HealthCheck) -> Result<(), String> {
+    match &check.probe {
+        HealthProbe::Tcp { host, port } => {
+            if host.trim().is_empty() {
+                return Err("Health check host must not be empty".to_string());
+            }
+            if *port == 0 {
+                return Err("Health check port must not be 0".to_string());
+            }
+        }
+        HealthProbe::Http { url, .. } => {
+            parse_http_url(url)?;
+        }
+    }
+    if check.timeout_ms == 0 {
+        return Err("Health check timeout must be greater than 0".to_string());
+    }
+    Ok(())
+}
+
+// 1回だけ確認する（タイムアウトを含めて失敗した理由をエラーで返す）
+pub async fn probe_once(check: &HealthCheck) -> Result<(), String> {
+    let timeout = Duration::from_millis(check.timeout_ms);
+    let probe = async {
+        match &check.probe {
+            HealthProbe::Tcp { host, port } => TcpStream::connect((host.as_str(), *port))
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Failed to connect to {}:{}: {}", host, port, e)),
+            HealthProbe::Http {
+                url,
+                expected_status,
+            } => {
+                let status = http_get_status(url).await?;
+                if status == *expected_status {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} returned status {} (expected {})",
+                        url, status, expected_status
+                    ))
+                }
+            }
+        }
+    };
+    tokio::time::timeout(timeout, probe)
+        .await
+        .map_err(|_| format!("Health check timed out after {} ms", check.timeout_ms))?
+}
+
+// 成功するまで設定された回数だけ確認し直す
+pub async fn check(check: &HealthCheck) -> Result<(), String> {
+    let mut attempt = 0;
+    loop {
+        match probe_once(check).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= check.retries => return Err(e),
+            Err(_) => {
+                attempt += 1;
+                tokio::time::sleep(Duration::from_millis(check.retry_interval_ms)).await;
+            }
+        }
+    }
+}
+
+// HTTPのGETを送り、レスポンスのステータスコードを返す
+async fn http_get_status(url: &str) -> Result<u16, String> {
+    let (host, port, path) = parse_http_url(url)?;
+    let mut stream = TcpStream::connect((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", url, e))?;
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}:{}\r\nConnection: close\r\nUser-Agent: ajiponzu-utility-launcher\r\n\r\n",
+        path, host, port
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to send request to {}: {}", url, e))?;
+
+    // ステータス行だけ読めればよい
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 512];
+    while !buffer.contains(&b'\n') && buffer.len() < 4096 {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|e| format!("Failed to read response from {}: {}", url, e))?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let status_line = String::from_utf8_lossy(&buffer);
+    status_line
+        .lines()
+        .next()
+        .filter(|line| line.starts_with("HTTP/"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| format!("Invalid HTTP response from {}", url))
+}
@@ -0,0 +1,64 @@
+// 起動の履歴
+//
+// いつ何を起動したかを時系列で確認できるよう、起動の成功・失敗を history.json に記録する
+// （ファイルが大きくならないよう、上限を超えた分は古いものから捨てる）
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::{write_file_atomically, MutexExt};
+
+// 記録する履歴の上限
+const MAX_HISTORY_ENTRIES: usize = 1000;
+
+// 起動の記録
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LaunchEvent {
+    #[serde(alias = "appId")]
+    pub app_id: String,
+    pub name: String,
+    // 起動した日時（RFC 3339）
+    pub timestamp: String,
+    pub success: bool,
+    // 失敗した場合のエラーメッセージ
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// 読み込みから書き込みまでを1つずつ行い、同時に起動した記録が消えないようにする
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+// 履歴を古い順に読み込む（ない・読み込めない場合は空）
+pub fn read(path: &Path) -> Vec<LaunchEvent> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        warn!("Ignoring invalid launch history {}: {}", path.display(), e);
+        Vec::new()
+    })
+}
+
+// 履歴に追加し、上限を超えた分は古いものから捨てる
+pub fn append(path: &Path, event: LaunchEvent) -> Result<(), String> {
+    let _write = WRITE_LOCK.lock_or_recover();
+    let mut events = read(path);
+    events.push(event);
+    if events.len() > MAX_HISTORY_ENTRIES {
+        events.drain(..events.len() - MAX_HISTORY_ENTRIES);
+    }
+    write(path, &events)
+}
+
+// 履歴を空にする
+pub fn clear(path: &Path) -> Result<(), String> {
+    let _write = WRITE_LOCK.lock_or_recover();
+    write(path, &[])
+}
+
+fn write(path: &Path, events: &[LaunchEvent]) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(events).map_err(|e| e.to_string())?;
+    write_file_atomically(path, &contents)
+}
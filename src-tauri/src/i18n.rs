@@ -0,0 +1,118 @@
+// トレイメニュー・通知の表示言語
+//
+// 言語ごとに「キー → 表示文字列」の表を持ち、未対応の言語・未翻訳のキーは英語で表示する
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// 既定の言語（フォールバック先）
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+static TRANSLATIONS: LazyLock<HashMap<&'static str, HashMap<&'static str, &'static str>>> =
+    LazyLock::new(|| HashMap::from([("en", english()), ("ja", japanese())]));
+
+fn english() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("tray.show", "Show"),
+        ("tray.hide", "Hide"),
+        ("tray.quit", "Quit"),
+        ("tray.tooltip", "Ajiponzu Utility Launcher"),
+        ("tray.tooltip_running_one", "Ajiponzu — 1 app running"),
+        ("tray.tooltip_running", "Ajiponzu — {count} apps running"),
+        ("notify.started", "Started {name}"),
+        ("notify.launch_failed", "Failed to launch {name}"),
+        (
+            "notify.post_launch_failed",
+            "The post-launch command of {name} failed",
+        ),
+        ("notify.startup_failed", "Failed to launch startup apps"),
+        ("notify.startup_skipped", "Skipped launching {name}"),
+        (
+            "notify.startup_suppressed",
+            "Skipped launching startup apps",
+        ),
+        ("notify.restarted", "Restarted {name}"),
+        (
+            "notify.restarted_many",
+            "Restarted {name} {count} times in the last {seconds}s",
+        ),
+        ("notify.restart_gave_up", "Gave up restarting {name}"),
+        (
+            "notify.config_load_failed",
+            "Failed to load the configuration",
+        ),
+        (
+            "notify.config_save_failed",
+            "Failed to save the configuration",
+        ),
+        ("notify.app_not_found", "Application not found"),
+        ("notify.group_not_found", "Launch group not found"),
+        ("notify.deep_link_failed", "Deep link failed"),
+        (
+            "notify.deep_link_unknown_app",
+            "Application not found: {id}",
+        ),
+        ("notify.deep_link_unsupported", "Unsupported link: {url}"),
+    ])
+}
+
+fn japanese() -> HashMap<&'static str, &'static str> {
+    HashMap::from([
+        ("tray.show", "表示"),
+        ("tray.hide", "隠す"),
+        ("tray.quit", "終了"),
+        ("tray.tooltip_running_one", "Ajiponzu — 1個のアプリを実行中"),
+        (
+            "tray.tooltip_running",
+            "Ajiponzu — {count}個のアプリを実行中",
+        ),
+        ("notify.started", "{name} を起動しました"),
+        ("notify.launch_failed", "{name} の起動に失敗しました"),
+        (
+            "notify.post_launch_failed",
+            "{name} の起動後のコマンドが失敗しました",
+        ),
+        ("notify.startup_failed", "自動起動に失敗しました"),
+        ("notify.startup_skipped", "{name} の起動をスキップしました"),
+        ("notify.startup_suppressed", "自動起動をスキップしました"),
+        ("notify.restarted", "{name} を再起動しました"),
+        (
+            "notify.restarted_many",
+            "{name} を直近{seconds}秒で{count}回再起動しました",
+        ),
+        ("notify.restart_gave_up", "{name} の再起動を諦めました"),
+        ("notify.config_load_failed", "設定の読み込みに失敗しました"),
+        ("notify.config_save_failed", "設定の保存に失敗しました"),
+        ("notify.app_not_found", "アプリが見つかりません"),
+        ("notify.group_not_found", "グループが見つかりません"),
+        (
+            "notify.deep_link_failed",
+            "ディープリンクを開けませんでした",
+        ),
+        (
+            "notify.deep_link_unknown_app",
+            "アプリが見つかりません: {id}",
+        ),
+        (
+            "notify.deep_link_unsupported",
+            "対応していないリンクです: {url}",
+        ),
+    ])
+}
+
+// 対応している言語か
+pub fn is_supported(language: &str) -> bool {
+    TRANSLATIONS.contains_key(language)
+}
+
+// キーの表示文字列を取得し、{名前} を引数で置き換える（見つからない場合は英語、英語にもなければキーをそのまま返す）
+pub fn translate(language: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let text = [language, DEFAULT_LANGUAGE]
+        .iter()
+        .find_map(|l| TRANSLATIONS.get(l).and_then(|table| table.get(key)))
+        .copied()
+        .unwrap_or(key);
+    args.iter().fold(text.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
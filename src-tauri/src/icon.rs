@@ -0,0 +1,208 @@
+// アプリのアイコンの取得
+//
+// 実行ファイルのアイコンをPNGとして取り出し、フロントエンドで表示できるdata URLに変換する
+
+use base64::Engine;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::SystemTime;
+
+// アイコンを取り出せない場合のアイコン（アプリの画面）
+const GENERIC_APP_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 32 32"><rect x="3" y="5" width="26" height="22" rx="3" fill="#90a4ae"/><rect x="3" y="5" width="26" height="6" rx="3" fill="#546e7a"/><rect x="7" y="15" width="18" height="8" rx="1" fill="#eceff1"/></svg>"##;
+
+// URL・プロトコルのアイコン（地球儀）
+const GENERIC_LINK_ICON_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 32 32"><circle cx="16" cy="16" r="13" fill="#42a5f5"/><path d="M3 16h26M16 3c-4 4-6 8-6 13s2 9 6 13M16 3c4 4 6 8 6 13s-2 9-6 13" stroke="#e3f2fd" stroke-width="2" fill="none"/></svg>"##;
+
+pub fn generic_app_icon() -> String {
+    svg_data_url(GENERIC_APP_ICON_SVG)
+}
+
+pub fn generic_link_icon() -> String {
+    svg_data_url(GENERIC_LINK_ICON_SVG)
+}
+
+fn svg_data_url(svg: &str) -> String {
+    format!(
+        "data:image/svg+xml;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(svg)
+    )
+}
+
+pub fn png_data_url(png: &[u8]) -> String {
+    format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    )
+}
+
+// 画像ファイルを data URL にする（PNG・ICO・SVG・JPEG）
+pub fn image_data_url(path: &str) -> Result<String, String> {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mime = match extension.as_str() {
+        "png" => "image/png",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        "jpg" | "jpeg" => "image/jpeg",
+        _ => return Err(format!("Unsupported icon format: {}", path)),
+    };
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+// キャッシュのファイル名（パスと更新日時が同じ間は同じ名前になる）
+pub fn cache_file_name(path: &str, modified: SystemTime) -> String {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    modified.hash(&mut hasher);
+    format!("{:016x}.png", hasher.finish())
+}
+
+// ファイルのアイコンをPNGとして取り出す
+#[cfg(target_os = "windows")]
+pub fn extract_icon_png(path: &str) -> Result<Vec<u8>, String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::FILE_FLAGS_AND_ATTRIBUTES;
+    use windows::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
+    use windows::Win32::UI::Shell::{SHGetFileInfoW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON};
+    use windows::Win32::UI::WindowsAndMessaging::DestroyIcon;
+
+    let wide_path: Vec<u16> = std::ffi::OsStr::new(path)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut info = SHFILEINFOW::default();
+    // SHGetFileInfo はシェルの拡張を使う場合があるためCOMを初期化してから呼ぶ
+    let com_initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+    let result = unsafe {
+        SHGetFileInfoW(
+            PCWSTR(wide_path.as_ptr()),
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            Some(&mut info),
+            std::mem::size_of::<SHFILEINFOW>() as u32,
+            SHGFI_ICON | SHGFI_LARGEICON,
+        )
+    };
+    if com_initialized {
+        unsafe { CoUninitialize() };
+    }
+    if result == 0 || info.hIcon.is_invalid() {
+        return Err(format!("Failed to get icon of {}", path));
+    }
+
+    let png = icon_to_png(info.hIcon);
+    unsafe {
+        let _ = DestroyIcon(info.hIcon);
+    }
+    png
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn extract_icon_png(path: &str) -> Result<Vec<u8>, String> {
+    Err(format!(
+        "Extracting icons is only supported on Windows: {}",
+        path
+    ))
+}
+
+// アイコンのビットマップをRGBAのPNGに変換
+#[cfg(target_os = "windows")]
+fn icon_to_png(icon: windows::Win32::UI::WindowsAndMessaging::HICON) -> Result<Vec<u8>, String> {
+    use windows::Win32::Graphics::Gdi::{
+        CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits, GetObjectW, BITMAP, BITMAPINFO,
+        BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP, HDC,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{GetIconInfo, ICONINFO};
+
+    let mut icon_info = ICONINFO::default();
+    unsafe { GetIconInfo(icon, &mut icon_info) }.map_err(|e| e.to_string())?;
+
+    // 32bitのトップダウンのビットマップとして画素を読み取る（BGRAの順）
+    let read_pixels = |dc: HDC, bitmap: HBITMAP, width: i32, height: i32| -> Option<Vec<u8>> {
+        let mut bitmap_info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: width,
+                biHeight: -height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+        let lines = unsafe {
+            GetDIBits(
+                dc,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr().cast()),
+                &mut bitmap_info,
+                DIB_RGB_COLORS,
+            )
+        };
+        (lines == height).then_some(pixels)
+    };
+
+    let result = (|| {
+        let mut bitmap = BITMAP::default();
+        let size = unsafe {
+            GetObjectW(
+                icon_info.hbmColor.into(),
+                std::mem::size_of::<BITMAP>() as i32,
+                Some((&mut bitmap as *mut BITMAP).cast()),
+            )
+        };
+        if size == 0 || bitmap.bmWidth <= 0 || bitmap.bmHeight <= 0 {
+            return Err("Icon has no color bitmap".to_string());
+        }
+        let (width, height) = (bitmap.bmWidth, bitmap.bmHeight);
+
+        let dc = unsafe { CreateCompatibleDC(None) };
+        let color = read_pixels(dc, icon_info.hbmColor, width, height);
+        let mask = read_pixels(dc, icon_info.hbmMask, width, height);
+        unsafe {
+            let _ = DeleteDC(dc);
+        }
+        let mut pixels = color.ok_or("Failed to read icon bitmap")?;
+
+        // 透明度を持たない古い形式のアイコンはマスク（白が透明）から透明度を決める
+        if pixels.chunks_exact(4).all(|p| p[3] == 0) {
+            for (i, pixel) in pixels.chunks_exact_mut(4).enumerate() {
+                let transparent = mask.as_ref().is_some_and(|m| m[i * 4] != 0);
+                pixel[3] = if transparent { 0 } else { 0xff };
+            }
+        }
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        encode_png(&pixels, width as u32, height as u32)
+    })();
+
+    unsafe {
+        let _ = DeleteObject(icon_info.hbmColor.into());
+        let _ = DeleteObject(icon_info.hbmMask.into());
+    }
+    result
+}
+
+#[cfg(target_os = "windows")]
+fn encode_png(rgba: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(rgba).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(png)
+}
@@ -0,0 +1,512 @@
+// プロセスの起動・停止の抽象化
+//
+// 起動・停止の処理は ProcessLauncher を通して行い、実際のOSの操作は SystemLauncher が担う
+// （Command・PowerShell を直接呼ばない実装に差し替えられるように）
+
+use log::info;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use super::secret::redact;
+use super::{
+    apply_cpu_affinity, build_direct_launch_command, build_shell_launch_command, detach_process,
+    find_pids_by_executable_path, is_process_alive, kill_process_tree, open_app_log,
+    request_process_tree_exit, terminate_processes_by_path, use_new_process_group, LaunchError,
+    ProcessPriority,
+};
+
+#[cfg(target_os = "windows")]
+use super::{
+    build_powershell_launch_command, build_windows_command_line, is_native_launchable,
+    output_with_timeout, powershell_available, spawn_native, wait_for_pid_exit, CREATE_NO_WINDOW,
+    LAUNCH_TIMEOUT, RUN_AS_PASSWORD_ENV,
+};
+
+#[cfg(target_os = "windows")]
+use super::secret::account_password;
+
+#[cfg(not(target_os = "windows"))]
+use super::apply_priority;
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(test)]
+use std::sync::Mutex;
+
+// 起動するプロセスの内容
+#[derive(Debug, Clone, Default)]
+pub struct LaunchSpec {
+    pub app_id: String,
+    pub path: String,
+    pub arguments: String,
+    pub env: HashMap<String, String>,
+    pub working_directory: String,
+    pub log_paths: Option<(PathBuf, PathBuf)>,
+    pub priority: Option<ProcessPriority>,
+    // 管理者として実行する（Windowsのみ、PIDは取得できない）
+    pub run_as_admin: bool,
+    // 別のユーザーとして実行する（Windowsのみ、パスワードは起動時に保管場所から取得し、PIDは取得できない）
+    pub run_as_user: Option<String>,
+    // 標準出力・標準エラーをパイプで受け取る
+    pub capture_output: bool,
+    // パスと引数をコマンド文字列としてシェルで実行する（PIDはシェルのプロセス）
+    pub use_shell: bool,
+    // 実行するCPUコアの番号（起動後に設定する）
+    pub cpu_affinity: Option<Vec<usize>>,
+    // ランチャーから切り離して起動する（Windows: DETACHED_PROCESS、それ以外: 新しいセッション）
+    pub detached: bool,
+}
+
+// 終了するまで待ち、終了コードを返す（取得できない場合は None）
+pub type ExitWaiter = Box<dyn FnOnce() -> Option<i32> + Send>;
+
+// 起動したプロセス
+pub struct SpawnedProcess {
+    // プロセスID（管理者として実行した場合は分からない）
+    pub pid: Option<u32>,
+    // 終了の待機（待てない場合は None）
+    pub exit: Option<ExitWaiter>,
+    // capture_output の場合の標準出力・標準エラー
+    pub output: Option<(Box<dyn Read + Send>, Box<dyn Read + Send>)>,
+}
+
+// 停止する対象
+#[derive(Debug, Clone, PartialEq)]
+pub enum KillTarget {
+    // プロセスIDのプロセスを子プロセスも含めて停止
+    ProcessTree(u32),
+    // 実行ファイルのパスが一致するプロセスをすべて停止
+    ExecutablePath(String),
+}
+
+pub trait ProcessLauncher: Send + Sync {
+    // プロセスを起動
+    fn spawn(&self, spec: &LaunchSpec) -> Result<SpawnedProcess, LaunchError>;
+    // プロセスを停止し、停止した数を返す（既に終了している場合も成功として扱う）
+    fn kill(&self, target: KillTarget) -> Result<usize, LaunchError>;
+    // プロセスに子プロセスも含めて終了を要求（強制終了はせず、終了を待たない）
+    fn terminate(&self, pid: u32) -> Result<(), LaunchError>;
+    // プロセスIDのプロセスが生存しているか
+    fn is_alive(&self, pid: u32) -> bool;
+    // 実行ファイルのパスが一致するプロセスのIDを探す
+    fn find_by_path(&self, path: &str) -> Vec<u32>;
+}
+
+// OSのプロセスを実際に起動・停止する
+pub struct SystemLauncher;
+
+impl ProcessLauncher for SystemLauncher {
+    fn spawn(&self, spec: &LaunchSpec) -> Result<SpawnedProcess, LaunchError> {
+        let spawned = spawn_process(spec)?;
+        if let (Some(cores), Some(pid)) = (&spec.cpu_affinity, spawned.pid) {
+            apply_cpu_affinity(pid, cores);
+        }
+        Ok(spawned)
+    }
+
+    fn kill(&self, target: KillTarget) -> Result<usize, LaunchError> {
+        match target {
+            KillTarget::ProcessTree(pid) => kill_process_tree(pid).map(|()| 1),
+            KillTarget::ExecutablePath(path) => terminate_processes_by_path(&path),
+        }
+        .map_err(LaunchError::classify)
+    }
+
+    fn terminate(&self, pid: u32) -> Result<(), LaunchError> {
+        request_process_tree_exit(pid).map_err(LaunchError::classify)
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        is_process_alive(pid)
+    }
+
+    fn find_by_path(&self, path: &str) -> Vec<u32> {
+        find_pids_by_executable_path(path)
+    }
+}
+
+// テスト用のランチャーへの呼び出し
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LauncherCall {
+    Spawn(String),
+    Kill(KillTarget),
+    Terminate(u32),
+}
+
+// テスト用のランチャー（OSのプロセスは操作せず、呼び出しを記録して動作中のプロセスを表で管理する）
+#[cfg(test)]
+pub struct RecordingLauncher {
+    calls: Mutex<Vec<LauncherCall>>,
+    // 動作中のプロセス（PID、実行ファイルのパス）
+    processes: Mutex<Vec<(u32, String)>>,
+    // 次に起動するプロセスのPID（None ならPIDが分からない起動として扱う）
+    next_pid: Mutex<Option<u32>>,
+    // 終了の要求で終了する
+    exit_on_terminate: AtomicBool,
+    // 何回目の強制終了で終了するか（0 なら終了しない）
+    kills_to_exit: AtomicUsize,
+    kills: AtomicUsize,
+}
+
+#[cfg(test)]
+impl RecordingLauncher {
+    pub fn new() -> Self {
+        RecordingLauncher {
+            calls: Default::default(),
+            processes: Default::default(),
+            next_pid: Mutex::new(Some(1000)),
+            exit_on_terminate: Default::default(),
+            kills_to_exit: AtomicUsize::new(1),
+            kills: Default::default(),
+        }
+    }
+
+    pub fn calls(&self) -> Vec<LauncherCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    // ランチャーの外で起動したプロセスを追加
+    pub fn add_process(&self, pid: u32, path: &str) {
+        self.processes.lock().unwrap().push((pid, path.to_string()));
+    }
+
+    pub fn set_next_pid(&self, pid: Option<u32>) {
+        *self.next_pid.lock().unwrap() = pid;
+    }
+
+    pub fn set_exit_on_terminate(&self, exit: bool) {
+        self.exit_on_terminate.store(exit, Ordering::SeqCst);
+    }
+
+    pub fn set_kills_to_exit(&self, kills: usize) {
+        self.kills_to_exit.store(kills, Ordering::SeqCst);
+    }
+
+    fn record(&self, call: LauncherCall) {
+        self.calls.lock().unwrap().push(call);
+    }
+}
+
+#[cfg(test)]
+impl ProcessLauncher for RecordingLauncher {
+    fn spawn(&self, spec: &LaunchSpec) -> Result<SpawnedProcess, LaunchError> {
+        self.record(LauncherCall::Spawn(spec.path.clone()));
+        let mut next_pid = self.next_pid.lock().unwrap();
+        let pid = *next_pid;
+        if let Some(pid) = pid {
+            self.add_process(pid, &spec.path);
+            *next_pid = Some(pid + 1);
+        }
+        Ok(SpawnedProcess {
+            pid,
+            exit: None,
+            output: None,
+        })
+    }
+
+    fn kill(&self, target: KillTarget) -> Result<usize, LaunchError> {
+        self.record(LauncherCall::Kill(target.clone()));
+        let kills = self.kills.fetch_add(1, Ordering::SeqCst) + 1;
+        let kills_to_exit = self.kills_to_exit.load(Ordering::SeqCst);
+        let mut processes = self.processes.lock().unwrap();
+        let matches = |(pid, path): &(u32, String)| match &target {
+            KillTarget::ProcessTree(target_pid) => pid == target_pid,
+            KillTarget::ExecutablePath(target_path) => path == target_path,
+        };
+        let count = match target {
+            KillTarget::ProcessTree(_) => 1,
+            KillTarget::ExecutablePath(_) => processes.iter().filter(|p| matches(p)).count(),
+        };
+        if kills_to_exit != 0 && kills >= kills_to_exit {
+            processes.retain(|p| !matches(p));
+        }
+        Ok(count)
+    }
+
+    fn terminate(&self, pid: u32) -> Result<(), LaunchError> {
+        self.record(LauncherCall::Terminate(pid));
+        if self.exit_on_terminate.load(Ordering::SeqCst) {
+            self.processes.lock().unwrap().retain(|&(p, _)| p != pid);
+        }
+        Ok(())
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        self.processes
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|&(p, _)| p == pid)
+    }
+
+    fn find_by_path(&self, path: &str) -> Vec<u32> {
+        self.processes
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, p)| p == path)
+            .map(|&(pid, _)| pid)
+            .collect()
+    }
+}
+
+// 起動方法（パイプ・管理者・シェル）に応じてプロセスを起動する
+fn spawn_process(spec: &LaunchSpec) -> Result<SpawnedProcess, LaunchError> {
+    // 管理者・別のユーザーとして実行する場合は標準出力をパイプで受け取れない
+    if spec.capture_output
+        && !(cfg!(target_os = "windows") && (spec.run_as_admin || spec.run_as_user.is_some()))
+    {
+        return spawn_with_pipes(spec)
+            .map_err(|e| LaunchError::classify(format!("Failed to launch application: {}", e)));
+    }
+
+    #[cfg(target_os = "windows")]
+    if !spec.use_shell {
+        return spawn_windows(spec).map_err(LaunchError::classify);
+    }
+
+    spawn_direct(spec)
+        .map_err(|e| LaunchError::classify(format!("Failed to launch application: {}", e)))
+}
+
+// 直接起動するコマンド（use_shell の場合はシェル経由）
+fn direct_command(spec: &LaunchSpec) -> std::process::Command {
+    let build = if spec.use_shell {
+        build_shell_launch_command
+    } else {
+        build_direct_launch_command
+    };
+    let mut cmd = build(
+        &spec.path,
+        &spec.arguments,
+        &spec.env,
+        &spec.working_directory,
+    );
+    if spec.detached {
+        detach_process(&mut cmd);
+    } else {
+        use_new_process_group(&mut cmd);
+    }
+    cmd
+}
+
+// Windowsの作成フラグ（コンソールウィンドウを表示せず、優先度は起動時に指定する）
+#[cfg(target_os = "windows")]
+fn creation_flags(spec: &LaunchSpec) -> u32 {
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+
+    let detached = if spec.detached {
+        DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP
+    } else {
+        0
+    };
+    CREATE_NO_WINDOW | spec.priority.map_or(0, ProcessPriority::creation_flag) | detached
+}
+
+// 起動した子プロセスの終了を待つ（生存確認で先に回収済みの場合は終了コードが取れない）
+fn child_waiter(mut child: std::process::Child) -> ExitWaiter {
+    Box::new(move || child.wait().ok().and_then(|status| status.code()))
+}
+
+// 標準出力・標準エラーをパイプで受け取って直接起動
+fn spawn_with_pipes(spec: &LaunchSpec) -> Result<SpawnedProcess, String> {
+    let mut cmd = direct_command(spec);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(creation_flags(spec));
+    info!(
+        "{}",
+        redact(&format!(
+            "Launching app_id={} (capturing output) command={:?}",
+            spec.app_id, cmd
+        ))
+    );
+    let mut child = cmd.spawn().map_err(|e| e.to_string())?;
+    let pid = child.id();
+    #[cfg(not(target_os = "windows"))]
+    if let Some(priority) = spec.priority {
+        apply_priority(pid, priority);
+    }
+
+    let output = match (child.stdout.take(), child.stderr.take()) {
+        (Some(stdout), Some(stderr)) => Some((
+            Box::new(stdout) as Box<dyn Read + Send>,
+            Box::new(stderr) as Box<dyn Read + Send>,
+        )),
+        _ => None,
+    };
+    Ok(SpawnedProcess {
+        pid: Some(pid),
+        exit: Some(child_waiter(child)),
+        output,
+    })
+}
+
+// 直接起動（Windowsではシェル経由の場合のみ。ログ出力が有効な場合は標準出力・標準エラーをログファイルへ追記）
+fn spawn_direct(spec: &LaunchSpec) -> Result<SpawnedProcess, String> {
+    let mut cmd = direct_command(spec);
+    if let Some((log_path, err_log_path)) = &spec.log_paths {
+        cmd.stdout(open_app_log(log_path)?);
+        cmd.stderr(open_app_log(err_log_path)?);
+    }
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(creation_flags(spec));
+    info!(
+        "{}",
+        redact(&format!(
+            "Launching app_id={} command={:?}",
+            spec.app_id, cmd
+        ))
+    );
+    let child = cmd.spawn().map_err(|e| e.to_string())?;
+    #[cfg(not(target_os = "windows"))]
+    if let Some(priority) = spec.priority {
+        apply_priority(child.id(), priority);
+    }
+    Ok(SpawnedProcess {
+        pid: Some(child.id()),
+        exit: Some(child_waiter(child)),
+        output: None,
+    })
+}
+
+// Windowsでは実行ファイルを CreateProcessW で起動し、管理者実行・ShellExecuteが必要なファイルは
+// PowerShell の Start-Process で起動する（PowerShellが使えない場合はすべて CreateProcessW で起動する）
+#[cfg(target_os = "windows")]
+fn spawn_windows(spec: &LaunchSpec) -> Result<SpawnedProcess, String> {
+    use std::os::windows::io::AsRawHandle;
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Threading::{GetExitCodeProcess, WaitForSingleObject, INFINITE};
+
+    let fallback = !powershell_available();
+    if fallback && spec.run_as_admin {
+        return Err(
+            "Run as administrator requires PowerShell, which is unavailable on this system"
+                .to_string(),
+        );
+    }
+    if fallback && spec.run_as_user.is_some() {
+        return Err(
+            "Run as another user requires PowerShell, which is unavailable on this system"
+                .to_string(),
+        );
+    }
+    if fallback
+        || (!spec.run_as_admin && spec.run_as_user.is_none() && is_native_launchable(&spec.path))
+    {
+        info!(
+            "{}",
+            redact(&format!(
+                "Launching app_id={} (native) command={}",
+                spec.app_id,
+                build_windows_command_line(&spec.path, &spec.arguments)
+            ))
+        );
+        let (pid, process) = spawn_native(
+            &spec.path,
+            &spec.arguments,
+            &spec.env,
+            &spec.working_directory,
+            spec.log_paths.as_ref(),
+            spec.priority,
+            spec.detached,
+        )
+        .map_err(|e| {
+            if fallback && !is_native_launchable(&spec.path) {
+                format!(
+                    "Failed to launch application (PowerShell is unavailable, so only executables can be launched): {}",
+                    e
+                )
+            } else {
+                format!("Failed to launch application: {}", e)
+            }
+        })?;
+
+        // プロセスのハンドルで終了を待つ
+        let exit: ExitWaiter = Box::new(move || {
+            let handle = HANDLE(process.as_raw_handle());
+            let mut exit_code = 0u32;
+            unsafe {
+                WaitForSingleObject(handle, INFINITE);
+                GetExitCodeProcess(handle, &mut exit_code)
+            }
+            .ok()
+            .map(|_| exit_code as i32)
+        });
+        return Ok(SpawnedProcess {
+            pid: Some(pid),
+            exit: Some(exit),
+            output: None,
+        });
+    }
+
+    // 別のユーザーとして実行する場合は、保存したパスワードを環境変数で PowerShell に渡す
+    let password = match &spec.run_as_user {
+        Some(user) => Some(account_password(user)?.ok_or_else(|| {
+            format!(
+                "No password stored for user '{}' (save it with set_run_as_password)",
+                user
+            )
+        })?),
+        None => None,
+    };
+
+    // 昇格したプロセス・別のユーザーのプロセスは親からPIDで追跡できないため、PIDを取得しない
+    let track_pid = !spec.run_as_admin && spec.run_as_user.is_none();
+    let powershell_command = build_powershell_launch_command(spec, track_pid);
+    info!(
+        "{}",
+        redact(&format!(
+            "Launching app_id={} ({}) command={}",
+            spec.app_id,
+            if track_pid {
+                "PID tracking"
+            } else {
+                "name tracking"
+            },
+            powershell_command
+        ))
+    );
+
+    let mut command = std::process::Command::new("powershell");
+    command
+        .args(["-WindowStyle", "Hidden", "-Command", &powershell_command])
+        .creation_flags(CREATE_NO_WINDOW); // コンソールウィンドウを表示しない
+    if let Some(password) = password {
+        command.env(RUN_AS_PASSWORD_ENV, password);
+    }
+    let output = output_with_timeout(command, LAUNCH_TIMEOUT)
+        .map_err(|e| format!("Failed to launch application with Start-Process: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Start-Process failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    if !track_pid {
+        return Ok(SpawnedProcess {
+            pid: None,
+            exit: None,
+            output: None,
+        });
+    }
+
+    let pid_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let pid = pid_str
+        .parse::<u32>()
+        .map_err(|_| format!("Failed to parse process ID: {}", pid_str))?;
+    Ok(SpawnedProcess {
+        pid: Some(pid),
+        exit: Some(Box::new(move || wait_for_pid_exit(pid))),
+        output: None,
+    })
+}
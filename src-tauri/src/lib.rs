@@ -1,14 +1,45 @@
+mod autostart;
+mod condition;
+mod error;
+mod executable;
+mod foreign;
+mod health;
+mod history;
+mod i18n;
+mod icon;
+mod launcher;
+mod script;
+mod secret;
+
+use chrono::Timelike;
+use croner::Cron;
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::Mutex;
-use std::time::Duration;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{IconMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Manager, Runtime, Window,
+    AppHandle, Emitter, Manager, Runtime, Window,
 };
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_opener::OpenerExt;
+use tauri_plugin_window_state::{AppHandleExt, StateFlags};
+
+use condition::LaunchCondition;
+use error::{CommandError, ErrorCode};
+use health::{HealthCheck, HealthStatus};
+use history::LaunchEvent;
+use launcher::{ExitWaiter, KillTarget, LaunchSpec, ProcessLauncher, SystemLauncher};
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -16,543 +47,10107 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+// 自動再起動の監視間隔・再試行回数・バックオフの上限
+const AUTO_RESTART_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const AUTO_RESTART_MAX_RETRIES: u32 = 5;
+const AUTO_RESTART_MAX_BACKOFF: Duration = Duration::from_secs(30);
+// この時間以上動作し続けたら再試行回数をリセット
+const AUTO_RESTART_STABLE_PERIOD: Duration = Duration::from_secs(60);
+// min_healthy_runtime_ms より早く終了した（起動直後に失敗している）場合の再試行回数
+const AUTO_RESTART_FLAPPING_MAX_RETRIES: u32 = 2;
+
+// ランチャー終了時にアプリの終了を待つ最大時間
+const QUIT_STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+// 再起動時にプロセスの終了を待つ最大時間
+const RESTART_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+// 停止コマンドの実行とアプリの終了を待つ時間（過ぎたら強制終了する）
+const STOP_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+// OSの起動時刻の誤差として許す秒数（稼働時間から求めるため取得するたびにずれることがある）
+const BOOT_TIME_TOLERANCE_SECS: u64 = 5;
+// 強制終了したプロセスが本当に終了したか確認する時間（過ぎたらもう一度強制終了する）
+const KILL_VERIFY_TIMEOUT: Duration = Duration::from_secs(3);
+// 元に戻せる設定の変更の数
+const CONFIG_HISTORY_LIMIT: usize = 10;
+// 設定ファイルの形式のバージョン（形式を変える場合は上げて、migrate_config に変換を加える）
+const CONFIG_VERSION: u32 = 1;
+// 手動のバックアップを保存するディレクトリと、残すバックアップの数（超えた分は古いものから削除）
+const BACKUPS_DIR_NAME: &str = "backups";
+const CONFIG_BACKUP_LIMIT: usize = 20;
+// 起動前・起動後のコマンドの終了を待つ時間（過ぎたら強制終了して失敗とする）
+const LAUNCH_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+// 試験起動でプロセスの様子を見る時間
+const TEST_LAUNCH_WAIT: Duration = Duration::from_secs(2);
+// 試験起動で返す標準エラーの最大バイト数
+const TEST_LAUNCH_OUTPUT_LIMIT: usize = 8 * 1024;
+
+// ファイル変更監視のデバウンス間隔
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+// 設定ファイルの保存をまとめる間隔（並べ替えなどで続けて保存する場合に書き込みは1回にする）
+const CONFIG_SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+// 設定ディレクトリ（ネットワークドライブなど）の読み書きを待つ時間・試す回数・間隔
+const CONFIG_IO_TIMEOUT: Duration = Duration::from_secs(3);
+const CONFIG_IO_ATTEMPTS: u32 = 3;
+const CONFIG_IO_RETRY_DELAY: Duration = Duration::from_millis(500);
+// 設定ディレクトリに届かない間に、届くようになったかを確かめる間隔
+const CONFIG_RECOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+// アプリごとのログファイルの上限サイズ（超えたら起動時にローテーション）
+const APP_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
+// ランチャー自身のログファイル名（拡張子なし）と、1ファイルの上限・保持する世代数
+const LAUNCHER_LOG_FILE_NAME: &str = "launcher";
+const LAUNCHER_LOG_MAX_BYTES: u128 = 1024 * 1024;
+const LAUNCHER_LOG_KEEP_FILES: usize = 5;
+
+// 起動コマンド（PowerShell の Start-Process）の完了を待つ最大時間と確認間隔
+// （UAC の確認画面が開いたままの場合などに起動処理が止まらないように）
+#[cfg(target_os = "windows")]
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+// スタートメニューのショートカットを読み取る際の制限時間
+#[cfg(target_os = "windows")]
+const START_MENU_SCAN_TIMEOUT: Duration = Duration::from_secs(30);
+// スタートメニューから取り込まないショートカットの名前（アンインストーラー・ヘルプなど）
+#[cfg(target_os = "windows")]
+const START_MENU_SKIP_PATTERNS: &[&str] = &[
+    "uninstall",
+    "unins",
+    "アンインストール",
+    "help",
+    "ヘルプ",
+    "readme",
+    "manual",
+    "documentation",
+    "website",
+];
+#[cfg(target_os = "windows")]
+const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// 自分の子プロセスではないプロセスの終了を確認する間隔
+const PROCESS_EXIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+// PIDが分からないプロセス名のマーカーの終了を確認する間隔（すべてのプロセスを調べるため長めにする）
+const NAME_MARKER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+// 起動したプロセスが見つかるまでマーカーを取り除かずに待つ時間
+const NAME_MARKER_START_GRACE: Duration = Duration::from_secs(15);
+
+// ディープリンクのスキーム（ajiponzu://launch/<app_id>・ajiponzu://show）
+const DEEP_LINK_SCHEME: &str = "ajiponzu";
+
+// 出力を転送する際の1行の上限バイト数と、1秒あたりに転送する行数の上限（超えた分は破棄）
+const OUTPUT_MAX_LINE_BYTES: usize = 4096;
+const OUTPUT_MAX_LINES_PER_SEC: u32 = 200;
+
+// 重複起動禁止のプロセス名がこの数以上のプロセスに一致する場合は警告する
+const PROCESS_NAME_WARN_THRESHOLD: usize = 5;
+
+// 名前が重複している場合のエラーの先頭（フロントエンドで確認してから再送するために使う）
+const DUPLICATE_NAME_ERROR: &str = "Duplicate app name";
+
+// 設定ディレクトリを上書きする環境変数とコマンドライン引数（ポータブルモード）
+const CONFIG_DIR_ENV: &str = "AJIPONZU_CONFIG_DIR";
+const CONFIG_DIR_ARG: &str = "--config-dir";
+// 実行ファイルと同じ場所のポータブルモードのディレクトリ（config.json があればポータブルモードで起動する）
+const PORTABLE_DIR_NAME: &str = "portable";
+// 移行先に設定ファイルが既にある場合のエラーの先頭（フロントエンドで確認してから再送するために使う）
+const CONFIG_EXISTS_ERROR: &str = "Config already exists";
+
+// コマンドラインからアプリ・グループを起動する引数（名前またはIDで指定し、複数指定できる）
+const CLI_LAUNCH_ARG: &str = "--launch";
+const CLI_GROUP_ARG: &str = "--group";
+// 起動後にランチャーを終了する（常駐しない）
+const CLI_QUIT_AFTER_ARG: &str = "--quit-after";
+// 自動起動を行わずにウィンドウを表示するセーフモード（設定の誤りで起動のたびに問題が起きる場合の復旧用）
+const CLI_SAFE_MODE_ARG: &str = "--safe-mode";
+const SAFE_MODE_ENV: &str = "AJIPONZU_SAFE_MODE";
+
+// --quit-after で終了する際の終了コード（名前が見つからない場合を起動の失敗より優先する）
+const EXIT_CODE_NOT_FOUND: i32 = 2;
+const EXIT_CODE_LAUNCH_FAILED: i32 = 3;
+
+// 既定のプロファイル名と、プロファイル関連のファイル名
+const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR_NAME: &str = "profiles";
+const ACTIVE_PROFILE_FILE_NAME: &str = "active_profile";
+// 実行中のプロセスの記録（ランチャーが異常終了しても、再起動後に同じプロセスを管理し直すため）
+const RUNTIME_STATE_FILE_NAME: &str = "runtime_state.json";
+// 起動の履歴（手動・自動起動の成功と失敗）
+const HISTORY_FILE_NAME: &str = "history.json";
+
+// 保存・復元するウィンドウの状態（表示状態は復元せず、起動時はトレイに常駐させる）
+const WINDOW_STATE_FLAGS: StateFlags = StateFlags::SIZE
+    .union(StateFlags::POSITION)
+    .union(StateFlags::MAXIMIZED);
+
+// 定時起動のスケジュールを確認する間隔
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+// 依存アプリの起動完了を待つ最大時間と確認間隔
+const DEPENDENCY_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEPENDENCY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 // 登録されたアプリケーションの情報
+// （add_registered_app・update_registered_app の入力にも使うため、ID などランチャーが管理する項目も省略できる）
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RegisteredApp {
+    #[serde(default)]
     pub id: String,
     pub name: String,
     pub path: String,
+    #[serde(default)]
     pub arguments: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub delay: u64,
     #[serde(default, alias = "preventDuplicate")]
     pub prevent_duplicate: bool,
     #[serde(default, alias = "autoStart")]
     pub auto_start: bool,
+    #[serde(default, alias = "logOutput")]
+    pub log_output: bool,
+    #[serde(default, alias = "workingDirectory")]
+    pub working_directory: String,
+    // 起動時に追加する環境変数（継承した環境変数に上書き・追加される）
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default, alias = "watchPath")]
+    pub watch_path: Option<String>,
+    #[serde(default, alias = "watchRelaunchWhileRunning")]
+    pub watch_relaunch_while_running: bool,
+    #[serde(default, alias = "runAsAdmin")]
+    pub run_as_admin: bool,
+    #[serde(default, alias = "autoRestart")]
+    pub auto_restart: bool,
+    #[serde(default)]
+    pub category: String,
+    // 一覧の表示順（小さいほど先頭）
+    #[serde(default, alias = "sortOrder")]
+    pub sort_order: i64,
+    // 起動前に起動済みである必要があるアプリのID
+    #[serde(default, alias = "dependsOn")]
+    pub depends_on: Vec<String>,
+    // グローバルホットキー（例: "Ctrl+Alt+T"）
+    #[serde(default)]
+    pub hotkey: Option<String>,
+    // 起動回数と最終起動日時（RFC3339）
+    #[serde(default, alias = "launchCount")]
+    pub launch_count: u64,
+    #[serde(default, alias = "lastLaunched")]
+    pub last_launched: Option<String>,
+    // 定時起動のスケジュール（"HH:MM" または cron 式）
+    #[serde(default)]
+    pub schedule: Option<String>,
+    // 起動までの遅延（ミリ秒、指定時は delay より優先）
+    #[serde(default, alias = "delayMs")]
+    pub delay_ms: Option<u64>,
+    // 遅延に加えるランダムな時間の上限（ミリ秒）
+    #[serde(default, alias = "delayJitterMs")]
+    pub delay_jitter_ms: u64,
+    // 既に実行中なら自動起動しない（prevent_duplicate と違い既存のプロセスは停止しない）
+    #[serde(default, alias = "skipIfRunning")]
+    pub skip_if_running: bool,
+    // 無効にすると自動起動・定時起動・グループ起動の対象外になる（手動起動は可能）
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // 標準出力・標準エラーをフロントエンドへ転送する（PowerShellを介さず直接起動する）
+    #[serde(default, alias = "captureOutput")]
+    pub capture_output: bool,
+    // 起動時のプロセスの優先度（"low" / "normal" / "high"、未指定ならOSの既定）
+    #[serde(default)]
+    pub priority: Option<String>,
+    // 起動のたびにログファイルを空にする（無効なら追記。Start-Process でリダイレクトする場合は常に上書きされる）
+    #[serde(default, alias = "logTruncate")]
+    pub log_truncate: bool,
+    // パスと引数をコマンド文字列としてシェル（Windows: cmd /c、それ以外: sh -c）で実行する（メタ文字が解釈されるため信頼できる内容のみ。PIDはシェルのもの）
+    #[serde(default, alias = "useShell")]
+    pub use_shell: bool,
+    // 起動に失敗した場合に再試行する回数（ファイルが見つからない・起動できない形式の場合は再試行しない）
+    #[serde(default, alias = "launchRetries")]
+    pub launch_retries: u32,
+    // 再試行までの待ち時間（ミリ秒）
+    #[serde(default, alias = "launchRetryDelayMs")]
+    pub launch_retry_delay_ms: u64,
+    // 停止時に先に実行するコマンド（シェルで実行。一定時間内に終了しなければ強制終了する）
+    #[serde(default, alias = "stopCommand")]
+    pub stop_command: Option<String>,
+    // 実行するCPUコアの番号（Windows・Linuxのみ。存在しないコアは無視する）
+    #[serde(default, alias = "cpuAffinity")]
+    pub cpu_affinity: Option<Vec<usize>>,
+    // ポート・HTTPの応答でアプリが使える状態か確認する（依存アプリ・グループ起動の待機にも使う）
+    #[serde(default, alias = "healthCheck")]
+    pub health_check: Option<HealthCheck>,
+    // 起動前に実行するコマンド（シェルで実行。出力はアプリのログに追記する）
+    #[serde(default, alias = "preLaunch")]
+    pub pre_launch: Option<String>,
+    // 起動した後に実行するコマンド（シェルで実行。出力はアプリのログに追記する）
+    #[serde(default, alias = "postLaunch")]
+    pub post_launch: Option<String>,
+    // 起動前のコマンドが失敗したら起動せず、起動後のコマンドの失敗は通知する（無効ならどちらも警告して続行する）
+    #[serde(default = "default_enabled", alias = "hooksRequired")]
+    pub hooks_required: bool,
+    // ランチャーから切り離して起動する（ランチャーの終了・終了時の停止の影響を受けない）
+    #[serde(default)]
+    pub detached: bool,
+    // 引数の {name} を起動時に値で置き換える（{file} は指定がなければファイルを選ぶ）
+    #[serde(default)]
+    pub placeholders: bool,
+    // 条件を満たす場合だけ自動起動する（時間帯・プロセスの実行・TCPの接続）
+    #[serde(default, alias = "launchCondition")]
+    pub launch_condition: Option<LaunchCondition>,
+    // OSの起動ごとに1回だけ自動起動する（ランチャーを起動し直しても再び起動しない）
+    #[serde(default, alias = "launchOncePerBoot")]
+    pub launch_once_per_boot: bool,
+    // 一覧・トレイに表示するアイコンの画像（PNG・ICO。なければ実行ファイルのアイコン）
+    #[serde(default, alias = "iconPath")]
+    pub icon_path: Option<String>,
+    // 一覧で見分けるための色（#rgb・#rrggbb）
+    #[serde(default)]
+    pub color: Option<String>,
+    // 起動時に選べる引数の組み合わせ（launch_application の preset_name で指定する）
+    #[serde(default, alias = "argPresets")]
+    pub arg_presets: Vec<ArgPreset>,
+    // この時間（ミリ秒）より早く終了した場合は起動直後の失敗とみなし、自動再起動を早めに諦める（0なら区別しない）
+    #[serde(default, alias = "minHealthyRuntimeMs")]
+    pub min_healthy_runtime_ms: u64,
+    // パスのワイルドカードに複数のファイルが一致した場合に選ぶもの
+    #[serde(default, alias = "pathGlobPick")]
+    pub path_glob_pick: PathGlobPick,
+    // 起動してからこの時間（ミリ秒）が経つと自動で停止する（自動再起動はその回は行わない）
+    #[serde(default, alias = "autoStopAfterMs")]
+    pub auto_stop_after_ms: Option<u64>,
+    // 実行ファイルのマニフェストが管理者権限を要求する場合だけ管理者として実行する（Windowsのみ）
+    #[serde(default, alias = "autoElevate")]
+    pub auto_elevate: bool,
+    // 既に実行中のアプリを起動しようとした場合の動作
+    #[serde(default, alias = "onAlreadyRunning")]
+    pub on_already_running: OnAlreadyRunning,
+    // 停止して終了を確認した後、stop_application が戻るまで待つ時間（ミリ秒。ポート・ロックファイルの解放を待つ）
+    #[serde(default, alias = "stopSettleMs")]
+    pub stop_settle_ms: u64,
+    // 起動前に確認する実行ファイルのSHA-256（16進数。一致しない場合は起動しない）
+    #[serde(default, alias = "expectedSha256")]
+    pub expected_sha256: Option<String>,
+    // 別のユーザーとして実行する（Windowsのみ。パスワードはOSの資格情報の保管場所に保存し、PIDは取得できない）
+    #[serde(default, alias = "runAsUser")]
+    pub run_as_user: Option<String>,
 }
 
-// アプリケーション設定
-#[derive(Debug, Serialize, Deserialize, Default, Clone)]
-pub struct AppConfig {
-    pub registered_apps: Vec<RegisteredApp>,
+impl RegisteredApp {
+    // 登録・更新の入力から、ランチャーが管理する項目（ID・表示順・起動回数）を入れ直したアプリを作る
+    // （表示順・起動回数は呼び出し側で設定する）
+    fn with_managed_fields_reset(mut self, id: String) -> Self {
+        self.id = id;
+        self.sort_order = 0;
+        self.launch_count = 0;
+        self.last_launched = None;
+        self
+    }
 }
 
-// グローバル状態
-pub struct AppState {
-    pub config: Mutex<AppConfig>,
-    pub running_processes: Mutex<HashMap<String, u32>>, // app_id -> process_id
+// パスのワイルドカード（C:\Apps\Tool-*\tool.exe など）に複数のファイルが一致した場合に選ぶもの
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PathGlobPick {
+    // パスの名前順で最後のもの（Tool-1.9 と Tool-1.10 では Tool-1.9 になる点に注意）
+    #[default]
+    HighestName,
+    // 更新日時が最も新しいもの
+    Newest,
 }
 
-#[tauri::command]
-fn show_window(window: Window) {
-    window.show().unwrap();
+// 既に実行中のアプリを起動しようとした場合の動作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OnAlreadyRunning {
+    // 特に何もしない（これまで通り。自動起動では skip_if_running・prevent_duplicate に従う）
+    #[default]
+    DoNothing,
+    // 起動せず、実行中のアプリのウィンドウを前面に表示する
+    Focus,
+    // 実行中のアプリを停止してから起動し直す
+    Relaunch,
 }
 
-#[tauri::command]
-fn hide_window(window: Window) {
-    window.hide().unwrap();
+// 名前を付けた引数の組み合わせ（プロファイル・設定ファイルの切り替えなど）
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ArgPreset {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
 }
 
-// 設定ファイルのパスを取得
-fn get_config_path(app: &AppHandle) -> PathBuf {
-    let app_dir = app
-        .path()
-        .app_config_dir()
-        .expect("Failed to get app config dir");
-    std::fs::create_dir_all(&app_dir).expect("Failed to create app config dir");
-    app_dir.join("config.json")
+// まとめて起動するアプリのグループ
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaunchGroup {
+    pub id: String,
+    pub name: String,
+    #[serde(default, alias = "appIds")]
+    pub app_ids: Vec<String>,
+    // 同時に起動中（起動してからヘルスチェックが通るまで）にするアプリの上限
+    // （None の場合はこれまで通り1つずつ順番に起動する）
+    #[serde(default, alias = "maxConcurrent")]
+    pub max_concurrent: Option<usize>,
 }
 
-// 設定ファイルを読み込み
-fn load_config(app: &AppHandle) -> AppConfig {
-    let config_path = get_config_path(app);
-    if config_path.exists() {
-        let config_str = std::fs::read_to_string(config_path).unwrap_or_default();
-        serde_json::from_str(&config_str).unwrap_or_default()
-    } else {
-        AppConfig::default()
-    }
+// アプリケーション設定
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppConfig {
+    // 設定ファイルの形式のバージョン（ない場合は 0。古い形式は読み込むときに変換する）
+    #[serde(default, alias = "configVersion")]
+    pub config_version: u32,
+    pub registered_apps: Vec<RegisteredApp>,
+    #[serde(default)]
+    pub launch_groups: Vec<LaunchGroup>,
+    // ランチャー終了時に起動したアプリも停止する
+    #[serde(default)]
+    pub stop_apps_on_quit: bool,
+    // 起動の成功・失敗をデスクトップ通知する
+    #[serde(default = "default_notifications_enabled")]
+    pub notifications_enabled: bool,
+    // 自動起動アプリを1つずつ順番に起動する（遅延は前のアプリの起動後から数える）
+    #[serde(default, alias = "sequentialStartup")]
+    pub sequential_startup: bool,
+    // 実行中のアプリがあってもトレイの「Quit」で確認せずに終了する
+    #[serde(default, alias = "skipQuitConfirmation")]
+    pub skip_quit_confirmation: bool,
+    // トレイメニュー・通知の表示言語（"en"・"ja"）
+    #[serde(default = "default_language")]
+    pub language: String,
+    // ランチャーのウィンドウを常に最前面に表示する
+    #[serde(default, alias = "alwaysOnTop")]
+    pub always_on_top: bool,
+    // 起動時にウィンドウを表示せずトレイに常駐する（ログイン時の自動起動向け）
+    #[serde(default, alias = "startHidden")]
+    pub start_hidden: bool,
+    // 停止時に終了を要求してから強制終了するまでの猶予時間（ミリ秒、0の場合はすぐに強制終了する）
+    #[serde(default = "default_stop_grace_period_ms", alias = "stopGracePeriodMs")]
+    pub stop_grace_period_ms: u64,
+    // 同じアプリを続けて起動しようとした場合に2回目以降を無視する間隔（ミリ秒、0の場合は無視しない）
+    #[serde(default = "default_launch_debounce_ms", alias = "launchDebounceMs")]
+    pub launch_debounce_ms: u64,
+    // 自動起動がすべて終わったら、アプリごとの結果（起動した・スキップした・失敗した）をまとめて通知する
+    #[serde(default, alias = "showStartupSummary")]
+    pub show_startup_summary: bool,
+    // 相対パスの実行ファイルの基準ディレクトリ（空の場合は設定ディレクトリ。相対パスは設定ディレクトリを基準にする）
+    #[serde(default, alias = "baseDirectory")]
+    pub base_directory: String,
+    // 自動再起動の通知を1つのアプリにつき1回まで出す間隔（ミリ秒。その間の再起動は回数をまとめて通知する。0の場合はまとめない）
+    #[serde(
+        default = "default_restart_notification_interval_ms",
+        alias = "restartNotificationIntervalMs"
+    )]
+    pub restart_notification_interval_ms: u64,
+    // メンテナンスの期間（この間は自動起動・スケジュールの起動を行わない。アプリごとの条件とは別に全体に適用する）
+    #[serde(default, alias = "maintenanceWindow")]
+    pub maintenance_window: Option<MaintenanceWindow>,
+    // 次回の自動起動を1回だけスキップする（スキップした時点で解除する）
+    #[serde(default, alias = "suppressNextStartup")]
+    pub suppress_next_startup: bool,
 }
 
-// 設定ファイルを保存
-fn save_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
-    let config_path = get_config_path(app);
-    let config_str = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
-    std::fs::write(config_path, config_str).map_err(|e| e.to_string())?;
-    Ok(())
+// メンテナンスの期間（現地時刻の "YYYY-MM-DD HH:MM" または "YYYY-MM-DD"。開始は含み、終了は含まない）
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MaintenanceWindow {
+    pub start: String,
+    pub end: String,
 }
 
-// 登録されたアプリケーション一覧を取得
-#[tauri::command]
-fn get_registered_apps(app: AppHandle) -> Result<Vec<RegisteredApp>, String> {
-    let state: tauri::State<AppState> = app.state();
-    let config = state.config.lock().unwrap();
-    Ok(config.registered_apps.clone())
+fn default_notifications_enabled() -> bool {
+    true
 }
 
-// 設定をリセット（開発・デバッグ用）
-#[tauri::command]
-fn reset_config(app: AppHandle) -> Result<(), String> {
-    let state: tauri::State<AppState> = app.state();
-    let mut config = state.config.lock().unwrap();
-
-    // 設定をクリア
-    config.registered_apps.clear();
+fn default_stop_grace_period_ms() -> u64 {
+    5000
+}
 
-    // 設定ファイルを保存
-    save_config(&app, &config)?;
+fn default_launch_debounce_ms() -> u64 {
+    500
+}
 
-    println!("Configuration has been reset");
-    Ok(())
+fn default_restart_notification_interval_ms() -> u64 {
+    60_000
 }
 
-// アプリケーションを登録
-#[tauri::command]
-fn add_registered_app(
-    app: AppHandle,
-    name: String,
-    path: String,
-    arguments: String,
-    description: String,
-    delay: u64,
-    prevent_duplicate: bool,
-    auto_start: bool,
-) -> Result<RegisteredApp, String> {
-    let state: tauri::State<AppState> = app.state();
-    let mut config = state.config.lock().unwrap();
+fn default_language() -> String {
+    i18n::DEFAULT_LANGUAGE.to_string()
+}
 
-    let new_app = RegisteredApp {
-        id: uuid::Uuid::new_v4().to_string(),
-        name,
-        path,
-        arguments,
-        description,
-        delay,
-        prevent_duplicate,
-        auto_start,
-    };
+fn default_enabled() -> bool {
+    true
+}
 
-    config.registered_apps.push(new_app.clone());
-    save_config(&app, &config)?;
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            config_version: CONFIG_VERSION,
+            registered_apps: Vec::new(),
+            launch_groups: Vec::new(),
+            stop_apps_on_quit: false,
+            notifications_enabled: default_notifications_enabled(),
+            sequential_startup: false,
+            skip_quit_confirmation: false,
+            language: default_language(),
+            always_on_top: false,
+            start_hidden: false,
+            stop_grace_period_ms: default_stop_grace_period_ms(),
+            launch_debounce_ms: default_launch_debounce_ms(),
+            show_startup_summary: false,
+            base_directory: String::new(),
+            restart_notification_interval_ms: default_restart_notification_interval_ms(),
+            maintenance_window: None,
+            suppress_next_startup: false,
+        }
+    }
+}
 
-    Ok(new_app)
+// グローバル状態
+pub struct AppState {
+    pub config: Mutex<AppConfig>,
+    pub running_processes: Mutex<HashMap<String, u32>>, // app_id -> process_id
+    pub file_watchers: Mutex<HashMap<String, (String, notify::RecommendedWatcher)>>, // app_id -> (watch_path, watcher)
+    pub watchers_paused: AtomicBool,
+    pub intentionally_stopped: Mutex<HashSet<String>>, // ユーザーが停止したアプリ（自動再起動しない）
+    pub suspended_apps: Mutex<HashMap<String, u32>>, // app_id -> 一時停止したプロセスのPID（再起動後の別のプロセスと区別する）
+    pub restart_monitors: Mutex<HashSet<String>>,    // 自動再起動の監視中のアプリ
+    pub hotkeys: Mutex<HashMap<u32, String>>,        // shortcut id -> app_id
+    pub active_profile: Mutex<String>,               // 使用中のプロファイル名
+    pub output_captures: Mutex<HashMap<String, Arc<AtomicBool>>>, // app_id -> 出力を転送中か
+    pub exit_waiters: Mutex<HashMap<u32, Vec<tokio::sync::oneshot::Sender<Option<i32>>>>>, // process_id -> 終了を待っている呼び出し
+    pub last_saved_config: Mutex<Option<String>>, // 最後に保存・読み込みした設定ファイルの内容
+    pub pending_config_write: Mutex<Option<(PathBuf, String)>>, // 書き込みを待っている設定ファイル（パス、内容）
+    pub config_write_lock: Mutex<()>, // 設定ファイルの書き込みを1つずつ行う（古い内容で上書きしないように）
+    pub config_watcher: Mutex<Option<notify::RecommendedWatcher>>, // 設定ファイルの外部変更の監視
+    pub startup_warnings: Mutex<Vec<String>>, // 起動時にフロントエンドへ表示する警告
+    pub launcher: Arc<dyn ProcessLauncher>, // プロセスの起動・停止
+    pub safe_mode: bool,              // セーフモードで起動した（自動起動しない）
+    pub keep_apps_on_exit: AtomicBool, // 終了時に stop_apps_on_quit を適用しない（--quit-after）
+    pub process_stats: Mutex<sysinfo::System>, // リソース使用量の取得用（CPU使用率は前回の取得からの差分で求まる）
+    pub config_history: Mutex<ConfigHistory>,  // 元に戻す・やり直すための設定の履歴
+    pub launched_this_boot: Mutex<HashSet<String>>, // OSの起動後に起動した launch_once_per_boot のアプリ
+    pub recent_launches: Mutex<HashMap<String, Instant>>, // app_id -> 最後に起動しようとした時刻
+    pub auto_stops: Mutex<HashMap<String, Instant>>, // app_id -> 自動停止を予約した時刻（起動し直す・停止すると取り消す）
+    pub autostart_wanted: Mutex<Option<bool>>, // 最後にOSの自動起動を同期したときの launcher_autostart_wanted（未同期なら None）
 }
 
-// アプリケーション情報を更新
-#[tauri::command]
-fn update_registered_app(
-    app: AppHandle,
-    id: String,
-    name: String,
-    path: String,
-    arguments: String,
-    description: String,
-    delay: u64,
-    prevent_duplicate: bool,
-    auto_start: bool,
-) -> Result<(), String> {
-    let state: tauri::State<AppState> = app.state();
-    let mut config = state.config.lock().unwrap();
+impl AppState {
+    // 読み込んだ設定から状態を作成（管理テーブル・監視などは空の状態で始める）
+    fn new(
+        config: AppConfig,
+        active_profile: String,
+        startup_warnings: Vec<String>,
+        launcher: Arc<dyn ProcessLauncher>,
+        safe_mode: bool,
+    ) -> Self {
+        AppState {
+            config: Mutex::new(config),
+            running_processes: Mutex::new(HashMap::new()),
+            file_watchers: Mutex::new(HashMap::new()),
+            watchers_paused: AtomicBool::new(false),
+            intentionally_stopped: Mutex::new(HashSet::new()),
+            auto_stops: Mutex::new(HashMap::new()),
+            autostart_wanted: Mutex::new(None),
+            suspended_apps: Mutex::new(HashMap::new()),
+            restart_monitors: Mutex::new(HashSet::new()),
+            hotkeys: Mutex::new(HashMap::new()),
+            active_profile: Mutex::new(active_profile),
+            output_captures: Mutex::new(HashMap::new()),
+            exit_waiters: Mutex::new(HashMap::new()),
+            last_saved_config: Mutex::new(None),
+            pending_config_write: Mutex::new(None),
+            config_write_lock: Mutex::new(()),
+            config_watcher: Mutex::new(None),
+            process_stats: Mutex::new(sysinfo::System::new()),
+            config_history: Mutex::new(ConfigHistory::default()),
+            launched_this_boot: Mutex::new(HashSet::new()),
+            recent_launches: Mutex::new(HashMap::new()),
+            startup_warnings: Mutex::new(startup_warnings),
+            launcher,
+            keep_apps_on_exit: AtomicBool::new(false),
+            safe_mode,
+        }
+    }
+}
 
-    if let Some(app_entry) = config.registered_apps.iter_mut().find(|a| a.id == id) {
-        app_entry.name = name;
-        app_entry.path = path;
-        app_entry.arguments = arguments;
-        app_entry.description = description;
-        app_entry.delay = delay;
-        app_entry.prevent_duplicate = prevent_duplicate;
-        app_entry.auto_start = auto_start;
+// ロックの取得（他のスレッドがロック中にパニックしていても中身を取り出して続行する）
+trait MutexExt<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
 
-        save_config(&app, &config)?;
-        Ok(())
-    } else {
-        Err("Application not found".to_string())
+impl<T> MutexExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|poisoned| {
+            warn!("Recovering from a poisoned lock");
+            poisoned.into_inner()
+        })
     }
 }
 
-// アプリケーションを削除
-#[tauri::command]
-fn remove_registered_app(app: AppHandle, id: String) -> Result<(), String> {
-    let state: tauri::State<AppState> = app.state();
-    let mut config = state.config.lock().unwrap();
-
-    config.registered_apps.retain(|a| a.id != id);
-    save_config(&app, &config)?;
+// 設定のロックを取得
+fn lock_config(state: &AppState) -> MutexGuard<'_, AppConfig> {
+    state.config.lock_or_recover()
+}
 
-    Ok(())
+// プロセス管理テーブルのロックを取得
+fn lock_processes(state: &AppState) -> MutexGuard<'_, HashMap<String, u32>> {
+    state.running_processes.lock_or_recover()
 }
 
-// アプリケーションを起動
 #[tauri::command]
-async fn launch_application(
-    app: AppHandle,
-    app_id: String,
-    path: String,
-    arguments: String,
-) -> Result<(), String> {
-    // 登録されたアプリケーションの情報を確認
-    let state: tauri::State<AppState> = app.state();
-    let config = state.config.lock().unwrap();
-    let registered_app = config.registered_apps.iter().find(|app| app.id == app_id);
-    let is_registered_app = registered_app.is_some();
-    let prevent_duplicate = registered_app
-        .map(|app| app.prevent_duplicate)
-        .unwrap_or(false);
-    drop(config);
-
-    if is_registered_app {
-        // 登録されたアプリケーションの場合
-        #[cfg(target_os = "windows")]
-        {
-            if prevent_duplicate {
-                // 重複起動禁止の場合はプロセスIDを取得せずシンプルに起動
-                let quoted_path = format!("'{}'", path);
-                let mut powershell_command = format!("Start-Process -FilePath {}", quoted_path);
-
-                if !arguments.trim().is_empty() {
-                    let quoted_args = format!("'{}'", arguments);
-                    powershell_command = format!(
-                        "Start-Process -FilePath {} -ArgumentList {}",
-                        quoted_path, quoted_args
-                    );
-                }
+fn show_window(window: Window) -> Result<(), CommandError> {
+    window.show().map_err(|e| CommandError::from(e.to_string()))
+}
 
-                println!(
-                    "Executing simple launch command (prevent_duplicate): {}",
-                    powershell_command
-                );
+#[tauri::command]
+fn hide_window(window: Window) -> Result<(), CommandError> {
+    window.hide().map_err(|e| CommandError::from(e.to_string()))
+}
 
-                let output = Command::new("powershell")
-                    .args(&["-WindowStyle", "Hidden", "-Command", &powershell_command])
-                    .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
-                    .output()
-                    .map_err(|e| format!("Failed to launch application: {}", e))?;
+// 設定ファイルのパスを取得
+fn get_config_path(app: &AppHandle) -> PathBuf {
+    let profile = match app.try_state::<AppState>() {
+        Some(state) => state.active_profile.lock_or_recover().clone(),
+        None => load_active_profile(app),
+    };
+    profile_config_path(app, &profile)
+}
 
-                if output.status.success() {
-                    println!(
-                        "Application launched successfully (prevent_duplicate, no PID tracking)"
-                    );
+// 設定ディレクトリの上書き（明示的な指定がなければ、実行ファイルと同じ場所のポータブルモードのディレクトリ）
+fn config_dir_override() -> Option<PathBuf> {
+    explicit_config_dir()
+        .cloned()
+        .or_else(|| PORTABLE_DIR.lock_or_recover().clone())
+}
 
-                    // プロセス名ベース管理のマーカーを記録
-                    let mut processes = state.running_processes.lock().unwrap();
-                    processes.insert(format!("{}:name", app_id), 0);
-                    println!(
-                        "Stored process name tracking for app_id: {} (prevent_duplicate)",
-                        app_id
-                    );
+// 使用中のポータブルモードのディレクトリ（migrate_config_location で切り替える）
+static PORTABLE_DIR: std::sync::LazyLock<Mutex<Option<PathBuf>>> = std::sync::LazyLock::new(|| {
+    Mutex::new(portable_dir_path().filter(|dir| dir.join("config.json").exists()))
+});
 
-                    return Ok(());
-                } else {
-                    let error_msg = String::from_utf8_lossy(&output.stderr);
-                    return Err(format!("Start-Process failed: {}", error_msg));
-                }
-            } else {
-                // 通常の場合はプロセスIDを取得
-                let quoted_path = format!("'{}'", path);
-                let mut powershell_command = format!(
-                    "$process = Start-Process -FilePath {} -PassThru",
-                    quoted_path
-                );
+// 実行ファイルと同じ場所のポータブルモードのディレクトリのパス
+fn portable_dir_path() -> Option<PathBuf> {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(PORTABLE_DIR_NAME)))
+}
 
-                if !arguments.trim().is_empty() {
-                    let quoted_args = format!("'{}'", arguments);
-                    powershell_command = format!(
-                        "$process = Start-Process -FilePath {} -ArgumentList {} -PassThru",
-                        quoted_path, quoted_args
-                    );
+// 明示的に指定された設定ディレクトリ（コマンドライン引数 --config-dir、なければ環境変数 AJIPONZU_CONFIG_DIR）
+// 相対パスは実行ファイルのあるディレクトリを基準にする（USBメモリなどから起動する場合のため）
+fn explicit_config_dir() -> Option<&'static PathBuf> {
+    static OVERRIDE: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
+    OVERRIDE
+        .get_or_init(|| {
+            let mut args = std::env::args_os().skip(1);
+            let mut from_args = None;
+            while let Some(arg) = args.next() {
+                let arg = arg.to_string_lossy().into_owned();
+                if arg == CONFIG_DIR_ARG {
+                    from_args = args.next().map(PathBuf::from);
+                } else if let Some(dir) = arg.strip_prefix(&format!("{}=", CONFIG_DIR_ARG)) {
+                    from_args = Some(PathBuf::from(dir));
                 }
+            }
+            let dir = from_args.or_else(|| {
+                std::env::var_os(CONFIG_DIR_ENV)
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+            })?;
+            if dir.is_absolute() {
+                return Some(dir);
+            }
+            let exe_dir = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))
+                .unwrap_or_default();
+            Some(exe_dir.join(dir))
+        })
+        .as_ref()
+}
 
-                powershell_command.push_str("; Write-Output $process.Id");
-
-                println!(
-                    "Executing PID tracking launch command: {}",
-                    powershell_command
-                );
-
-                let output = Command::new("powershell")
-                    .args(&["-WindowStyle", "Hidden", "-Command", &powershell_command])
-                    .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
-                    .output()
-                    .map_err(|e| {
-                        format!("Failed to launch application with Start-Process: {}", e)
-                    })?;
+// ディレクトリに書き込めるか確認（存在しない場合は作成する）
+fn check_dir_writable(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".write-test");
+    std::fs::write(&probe, b"").map_err(|e| format!("Cannot write to {}: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
 
-                if output.status.success() {
-                    let pid_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    if let Ok(actual_pid) = pid_str.parse::<u32>() {
-                        println!("Started application with PID: {}", actual_pid);
+// ランチャーとアプリのログを保存するディレクトリ（ポータブルモードでは設定ディレクトリの logs）
+fn portable_log_dir() -> Option<PathBuf> {
+    config_dir_override().map(|dir| dir.join("logs"))
+}
 
-                        let mut processes = state.running_processes.lock().unwrap();
-                        processes.insert(app_id.clone(), actual_pid);
-                        println!("Stored PID {} for app_id: {}", actual_pid, app_id);
+// 取り出したアイコンのキャッシュのディレクトリを取得
+fn icon_cache_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = match config_dir_override() {
+        Some(dir) => dir.join("icons"),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to get app data dir: {}", e))?
+            .join("icons"),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create icon dir: {}", e))?;
+    Ok(dir)
+}
 
-                        return Ok(());
-                    } else {
-                        return Err(format!("Failed to parse process ID: {}", pid_str));
-                    }
-                } else {
-                    let error_msg = String::from_utf8_lossy(&output.stderr);
-                    return Err(format!("Start-Process failed: {}", error_msg));
-                }
-            }
-        }
+// 設定ディレクトリを取得
+// ディレクトリは作成しない（起動時に ensure_config_dir で作成し、書き込み時にない場合も作成する）
+fn get_config_dir(app: &AppHandle) -> PathBuf {
+    // ポータブルモードでは指定されたディレクトリを使う（書き込めるかは起動時に確認する）
+    if let Some(dir) = config_dir_override() {
+        return dir;
+    }
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            // Windows以外では従来通り
-            let mut cmd = Command::new(&path);
-            if !arguments.trim().is_empty() {
-                let args: Vec<&str> = arguments.split_whitespace().collect();
-                cmd.args(&args);
-            }
-            let child = cmd
-                .spawn()
-                .map_err(|e| format!("Failed to launch application: {}", e))?;
-
-            // プロセスIDを記録
-            let mut processes = state.running_processes.lock().unwrap();
-            processes.insert(app_id, child.id());
-            return Ok(());
+    match app.path().app_config_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            // ホームディレクトリが分からない場合など。終了せずに実行ファイルと同じ場所の portable を使う
+            error!("Failed to get app config dir: {}", e);
+            portable_dir_path().unwrap_or_else(|| PathBuf::from(PORTABLE_DIR_NAME))
         }
-    } else {
-        // システムツールの場合は従来通り
-        let mut cmd = Command::new(&path);
-        if !arguments.trim().is_empty() {
-            let args: Vec<&str> = arguments.split_whitespace().collect();
-            cmd.args(&args);
-        }
-        let child = cmd
-            .spawn()
-            .map_err(|e| format!("Failed to launch application: {}", e))?;
-
-        // プロセスIDを記録
-        let mut processes = state.running_processes.lock().unwrap();
-        processes.insert(app_id, child.id());
-        return Ok(());
     }
 }
 
-// アプリケーションを停止
-#[tauri::command]
-fn stop_application(app: AppHandle, app_id: String) -> Result<(), String> {
-    let state: tauri::State<AppState> = app.state();
-
-    // 登録されたアプリケーションの情報を取得
-    let config = state.config.lock().unwrap();
-    let registered_app = config.registered_apps.iter().find(|app| app.id == app_id);
-    let prevent_duplicate = registered_app
-        .map(|app| app.prevent_duplicate)
-        .unwrap_or(false);
-    let app_name = registered_app.map(|app| app.name.clone());
-    drop(config);
-
-    // プロセス管理テーブルから確認
-    let mut processes = state.running_processes.lock().unwrap();
-
-    // 重複起動禁止の場合は特別なキーで確認
-    let process_key = if prevent_duplicate {
-        format!("{}:name", app_id)
-    } else {
-        app_id.clone()
-    };
-
-    let pid = processes.get(&process_key).copied();
-
-    if let Some(pid) = pid {
-        processes.remove(&process_key);
-        drop(processes);
+// 設定ディレクトリを作成する（ネットワークドライブに届かない場合は時間を区切って何度か試す）
+fn ensure_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = get_config_dir(app);
+    config_io(|| {
+        let dir = dir.clone();
+        move || std::fs::create_dir_all(&dir)
+    })
+    .map_err(|e| format!("Failed to create config dir {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
 
-        if prevent_duplicate {
-            // 重複起動禁止の場合はアプリ名で停止
-            if let Some(process_name) = app_name {
-                println!(
-                    "Attempting to stop process by name: {} for app: {} (prevent_duplicate)",
-                    process_name, app_id
+// 設定ディレクトリの読み書きを、時間を区切って何度か試す（ネットワークドライブが一時的に届かない場合のため）
+// prepare は試すたびに呼び出し元のスレッドで呼ばれ、返した操作を別のスレッドで実行する
+// 時間内に終わらない操作は別のスレッドに残したまま諦める。ファイルがない場合は試し直さない
+fn config_io<T, F, O>(prepare: F) -> std::io::Result<T>
+where
+    T: Send + 'static,
+    F: Fn() -> O,
+    O: FnOnce() -> std::io::Result<T> + Send + 'static,
+{
+    let mut attempt = 1;
+    loop {
+        let (tx, rx) = mpsc::channel();
+        let operation = prepare();
+        std::thread::spawn(move || {
+            let _ = tx.send(operation());
+        });
+        let result = rx.recv_timeout(CONFIG_IO_TIMEOUT).unwrap_or_else(|_| {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("timed out after {:?}", CONFIG_IO_TIMEOUT),
+            ))
+        });
+        match result {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound && attempt < CONFIG_IO_ATTEMPTS => {
+                warn!(
+                    "Config dir access failed (attempt {} of {}), retrying: {}",
+                    attempt, CONFIG_IO_ATTEMPTS, e
                 );
+                attempt += 1;
+                std::thread::sleep(CONFIG_IO_RETRY_DELAY);
+            }
+            result => return result,
+        }
+    }
+}
 
-                #[cfg(target_os = "windows")]
-                {
-                    let output = Command::new("powershell")
-                        .args(&[
-                            "-WindowStyle",
-                            "Hidden",
-                            "-Command",
-                            &format!("Stop-Process -Name '{}' -Force", process_name),
-                        ])
-                        .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
-                        .output();
-
-                    return match output {
-                        Ok(result) => {
-                            if result.status.success() {
-                                println!("Successfully stopped process by name: {}", process_name);
-                                Ok(())
-                            } else {
-                                let error_msg = String::from_utf8_lossy(&result.stderr);
-                                println!("Stop-Process by name failed: {}", error_msg);
-                                Err(format!(
-                                    "Failed to stop process '{}': {}",
-                                    process_name, error_msg
-                                ))
-                            }
-                        }
-                        Err(e) => {
-                            println!("Failed to execute Stop-Process by name: {}", e);
-                            Err(format!(
-                                "Failed to stop application with Stop-Process: {}",
-                                e
-                            ))
-                        }
-                    };
-                }
+// 設定ファイルの書き込みを試した回数（最新の試みだけがファイルを置き換えられる）
+static CONFIG_WRITE_GENERATION: AtomicU64 = AtomicU64::new(0);
 
-                #[cfg(not(target_os = "windows"))]
-                {
-                    return Err(
-                        "Process name based termination not supported on this platform".to_string(),
-                    );
-                }
-            } else {
-                return Err("Application path not found".to_string());
-            }
-        } else {
-            // 通常のアプリの場合はPIDで停止
-            println!("Attempting to stop process ID: {} for app: {}", pid, app_id);
+// 世代の確認と置き換えを、他の試みの置き換えと重ならないように行う
+static CONFIG_RENAME_LOCK: Mutex<()> = Mutex::new(());
 
-            #[cfg(target_os = "windows")]
-            {
-                let output = Command::new("powershell")
-                    .args(&[
-                        "-WindowStyle",
-                        "Hidden",
-                        "-Command",
-                        &format!("Stop-Process -Id {} -Force", pid),
-                    ])
-                    .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
-                    .output();
-
-                return match output {
-                    Ok(result) => {
-                        if result.status.success() {
-                            println!("Successfully stopped process {}", pid);
-                            Ok(())
-                        } else {
-                            let error_msg = String::from_utf8_lossy(&result.stderr);
-                            println!("Stop-Process failed: {}", error_msg);
-                            Err(format!("Failed to stop process {}: {}", pid, error_msg))
-                        }
-                    }
-                    Err(e) => {
-                        println!("Failed to execute Stop-Process: {}", e);
-                        Err(format!(
-                            "Failed to stop application with Stop-Process: {}",
-                            e
-                        ))
-                    }
-                };
+// 設定ファイルの書き込みを1回試す操作（作った時点で世代を進める）
+// 時間切れで残った前の試みは、後から古い内容で置き換えずに一時ファイルを消して終わる
+fn config_write_attempt(
+    path: &Path,
+    contents: &str,
+) -> impl FnOnce() -> std::io::Result<()> + Send + 'static {
+    let generation = CONFIG_WRITE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let (path, contents) = (path.to_path_buf(), contents.to_string());
+    move || {
+        write_file_replacing(&path, &contents, |tmp_path, path| {
+            let _rename_guard = CONFIG_RENAME_LOCK.lock_or_recover();
+            if CONFIG_WRITE_GENERATION.load(Ordering::SeqCst) != generation {
+                return Err(std::io::Error::other("superseded by a newer write"));
             }
+            std::fs::rename(tmp_path, path)
+        })
+    }
+}
 
-            #[cfg(not(target_os = "windows"))]
-            {
-                let output = Command::new("kill")
-                    .args(&["-9", &pid.to_string()])
-                    .output();
+// 設定ディレクトリに届かないため、設定をメモリ上だけで扱っているか
+// （その間の保存は書き込みを待たせておき、届くようになったら書き込む）
+static CONFIG_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
 
-                return match output {
-                    Ok(_) => Ok(()),
-                    Err(e) => Err(format!("Failed to stop application: {}", e)),
-                };
-            }
+// 設定ディレクトリに届かないことを記録し、届くようになるまで定期的に確かめる
+// 起動処理中（AppState がない場合）は、起動処理の最後に確かめ始める
+fn mark_config_unavailable(app: &AppHandle, reason: &str) {
+    if CONFIG_UNAVAILABLE.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    warn!(
+        "Config dir is unavailable; keeping settings in memory: {}",
+        reason
+    );
+    if app.try_state::<AppState>().is_some() {
+        if let Err(e) = app.emit("config-unavailable", reason) {
+            error!("Failed to emit config-unavailable: {}", e);
         }
+        start_config_recovery(app);
     }
-
-    Err("Application not found or not running".to_string())
 }
 
-// アプリケーションの実行状態を確認
-#[tauri::command]
-fn is_application_running(app: AppHandle, app_id: String) -> bool {
-    let state: tauri::State<AppState> = app.state();
-    let processes = state.running_processes.lock().unwrap();
-    processes.contains_key(&app_id)
+// 設定ディレクトリに届くようになるまで定期的に確かめるスレッドを開始（既に確かめている場合は何もしない）
+fn start_config_recovery(app: &AppHandle) {
+    static RUNNING: AtomicBool = AtomicBool::new(false);
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(CONFIG_RECOVERY_INTERVAL);
+            if !CONFIG_UNAVAILABLE.load(Ordering::SeqCst) {
+                break;
+            }
+            if let Err(e) = recover_config(&app) {
+                warn!("Config dir is still unavailable: {}", e);
+            }
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+    });
 }
 
-// 登録された全アプリケーションを起動（自動起動用）
-#[tauri::command]
-async fn launch_startup_apps(app: AppHandle) -> Result<(), String> {
+// 設定ディレクトリに届くか確かめ、届く場合はメモリ上の変更を書き込む（変更がなければ設定を読み込み直す）
+// 書き込む場合は、届かなかった間のファイルの内容を「.bak」に退避してから上書きする
+fn recover_config(app: &AppHandle) -> Result<(), String> {
+    ensure_config_dir(app)?;
     let state: tauri::State<AppState> = app.state();
-    let config = state.config.lock().unwrap().clone();
-
-    for registered_app in config.registered_apps.iter().filter(|a| a.auto_start) {
-        let app_id = registered_app.id.clone();
-        let path = registered_app.path.clone();
-        let arguments = registered_app.arguments.clone();
-        let delay = registered_app.delay;
-        let prevent_duplicate = registered_app.prevent_duplicate;
-        let app_handle_clone = app.clone();
-
-        // 重複起動禁止が有効な場合、既存プロセスを停止
-        if prevent_duplicate {
-            let process_name = registered_app.name.clone();
-
-            println!("Preventing duplicate launch for: {}", process_name);
-
-            #[cfg(target_os = "windows")]
-            {
-                use std::process::Command;
-                let _output = Command::new("powershell")
-                    .args(&[
-                        "-WindowStyle",
-                        "Hidden",
-                        "-Command",
-                        &format!(
-                            "Stop-Process -Name '{}' -Force -ErrorAction SilentlyContinue",
-                            process_name
-                        ),
-                    ])
-                    .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
-                    .output();
-                // エラーは無視（プロセスが存在しない場合もあるため）
+    let has_changes = state.pending_config_write.lock_or_recover().is_some();
+    if has_changes {
+        let config_path = get_config_path(app);
+        let mut backup_name = config_path.file_name().unwrap_or_default().to_os_string();
+        backup_name.push(".bak");
+        let backup_path = config_path.with_file_name(backup_name);
+        match config_io(|| {
+            let (config_path, backup_path) = (config_path.clone(), backup_path.clone());
+            move || std::fs::copy(&config_path, &backup_path)
+        }) {
+            Ok(_) => warn!(
+                "Backed up {} before saving the changes made while it was unavailable",
+                config_path.display()
+            ),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(format!(
+                    "Failed to back up {}: {}",
+                    config_path.display(),
+                    e
+                ))
             }
         }
-
-        // 遅延がある場合は待機
-        if delay > 0 {
-            tokio::time::sleep(Duration::from_secs(delay)).await;
+        CONFIG_UNAVAILABLE.store(false, Ordering::SeqCst);
+        flush_config(app)?;
+    } else {
+        // 読み込めるまでは CONFIG_UNAVAILABLE のままにし、既定の設定を書き込まないようにする
+        let profile = load_active_profile(app);
+        let config_path = profile_config_path(app, &profile);
+        let contents = match config_io(|| {
+            let config_path = config_path.clone();
+            move || std::fs::read_to_string(&config_path)
+        }) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("Failed to read {}: {}", config_path.display(), e)),
+        };
+        let config = match contents.as_deref().map(parse_config) {
+            Some(Ok(config)) => Some(config),
+            Some(Err(e)) => {
+                // 壊れている場合は現在の設定を維持し、次の保存で書き込む
+                warn!(
+                    "Ignoring invalid config {} after it became available: {}",
+                    config_path.display(),
+                    e
+                );
+                None
+            }
+            None => Some(AppConfig::default()),
+        };
+        *state.active_profile.lock_or_recover() = profile;
+        CONFIG_UNAVAILABLE.store(false, Ordering::SeqCst);
+        if let Some(config) = config {
+            update_base_directory(app, &config);
+            apply_reloaded_config(app, config, contents);
         }
+    }
 
-        // アプリケーションを起動
-        let result = launch_application(app_handle_clone, app_id, path, arguments).await;
-        if let Err(e) = result {
-            eprintln!("Failed to launch {}: {}", registered_app.name, e);
+    if state.config_watcher.lock_or_recover().is_none() {
+        match create_config_watcher(app) {
+            Ok(watcher) => *state.config_watcher.lock_or_recover() = Some(watcher),
+            Err(e) => error!("Failed to watch config directory: {}", e),
         }
     }
-
+    info!("Config dir is available again");
     Ok(())
 }
 
-// ファイル選択ダイアログを開く
-#[tauri::command]
-fn open_file_dialog(app: AppHandle) -> Result<Option<String>, String> {
-    use tauri_plugin_dialog::DialogExt;
+// プロファイルの設定ファイルのパス（既定のプロファイルは従来の config.json）
+fn profile_config_path(app: &AppHandle, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        get_config_dir(app).join("config.json")
+    } else {
+        get_config_dir(app)
+            .join(PROFILES_DIR_NAME)
+            .join(format!("{}.json", profile))
+    }
+}
 
-    let file_path = app
-        .dialog()
+// 前回使用していたプロファイル名を読み込み（存在しない場合は既定のプロファイル）
+fn load_active_profile(app: &AppHandle) -> String {
+    let profile = std::fs::read_to_string(get_config_dir(app).join(ACTIVE_PROFILE_FILE_NAME))
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default();
+    if validate_profile_name(&profile).is_ok() && profile_config_path(app, &profile).exists() {
+        profile
+    } else {
+        DEFAULT_PROFILE.to_string()
+    }
+}
+
+// プロファイル名に使える文字か確認（ファイル名として使うため英数字・'-'・'_'のみ）
+fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Profile name is required".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid profile name '{}': use letters, digits, '-' or '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+// アプリごとのログを保存するディレクトリ
+fn app_log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    match portable_log_dir() {
+        Some(log_dir) => Ok(log_dir),
+        None => user_app_log_dir(app),
+    }
+}
+
+// ポータブルモードでない場合のアプリごとのログのディレクトリ
+fn user_app_log_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("logs"))
+}
+
+// アプリごとのログファイルのパスを取得（標準出力用。標準エラーは .err.log）
+fn app_log_file_path(app: &AppHandle, app_id: &str) -> Result<PathBuf, String> {
+    let log_dir = app_log_dir(app)?;
+    std::fs::create_dir_all(&log_dir).map_err(|e| format!("Failed to create log dir: {}", e))?;
+    Ok(log_dir.join(format!("{}.log", app_id)))
+}
+
+// 標準エラー出力用のログファイルのパス
+fn app_err_log_file_path(log_path: &Path) -> PathBuf {
+    log_path.with_extension("err.log")
+}
+
+// 上限サイズを超えたログファイルを .1 に退避する
+fn rotate_app_log(log_path: &Path) {
+    let too_large = std::fs::metadata(log_path)
+        .map(|m| m.len() > APP_LOG_MAX_BYTES)
+        .unwrap_or(false);
+    if too_large {
+        let mut rotated = log_path.as_os_str().to_owned();
+        rotated.push(".1");
+        if let Err(e) = std::fs::rename(log_path, PathBuf::from(rotated)) {
+            error!("Failed to rotate log file {}: {}", log_path.display(), e);
+        }
+    }
+}
+
+// ログ出力が有効なアプリのログファイルを準備（標準出力, 標準エラー）
+// truncate の場合は前回までの内容を消去し、それ以外は上限を超えていればローテーションする
+fn prepare_app_log(
+    app: &AppHandle,
+    app_id: &str,
+    truncate: bool,
+) -> Result<(PathBuf, PathBuf), String> {
+    let log_path = app_log_file_path(app, app_id)?;
+    let err_log_path = app_err_log_file_path(&log_path);
+    for path in [&log_path, &err_log_path] {
+        if truncate {
+            std::fs::File::create(path)
+                .map_err(|e| format!("Failed to truncate log file {}: {}", path.display(), e))?;
+        } else {
+            rotate_app_log(path);
+        }
+    }
+    Ok((log_path, err_log_path))
+}
+
+// アプリの標準出力のログファイルを既定のアプリで開く
+#[tauri::command]
+fn open_app_log_file(app: AppHandle, id: String) -> Result<(), CommandError> {
+    let log_path = app_log_file_path(&app, &id)?;
+    if !log_path.exists() {
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            format!("Log file not found: {}", log_path.display()),
+        ));
+    }
+    app.opener()
+        .open_path(log_path.to_string_lossy(), None::<&str>)
+        .map_err(|e| CommandError::new(ErrorCode::Io, format!("Failed to open log file: {}", e)))
+}
+
+// アプリの実行ファイルをファイルマネージャー（エクスプローラー・Finder）で表示
+// （ショートカットはリンク先を表示する。URL・カスタムプロトコルは対象外）
+#[tauri::command]
+fn reveal_app_location(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let path = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .map(|a| a.path.clone())
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::AppNotFound, format!("App not found: {}", app_id))
+        })?;
+    if is_url_path(&path) {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            format!(
+                "Reveal location is not applicable to URLs and protocols: {}",
+                path
+            ),
+        ));
+    }
+
+    let (target, _) = resolve_launch_target(&resolve_relative_path(&path), "");
+    if !Path::new(&target).exists() {
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            format!("File not found: {}", target),
+        ));
+    }
+    app.opener().reveal_item_in_dir(&target).map_err(|e| {
+        CommandError::new(ErrorCode::Io, format!("Failed to reveal {}: {}", target, e))
+    })
+}
+
+// 設定ファイルを読み込み（不明な項目は無視する）
+// 読み込めない場合は壊れたファイルを「.bak」に退避して空の設定を返し、理由を警告として返す
+fn load_config(app: &AppHandle) -> (AppConfig, Option<String>) {
+    let (config, warning) = read_config_file(app);
+    update_base_directory(app, &config);
+    (config, warning)
+}
+
+// 設定ファイルの内容を読み込む（古い形式の場合は現在の形式に変換してから読み込む）
+fn parse_config(contents: &str) -> serde_json::Result<AppConfig> {
+    #[derive(Deserialize)]
+    struct Version {
+        #[serde(default, alias = "configVersion")]
+        config_version: u32,
+    }
+    let version = serde_json::from_str::<Version>(contents)?.config_version;
+    if version >= CONFIG_VERSION {
+        return serde_json::from_str(contents);
+    }
+    let mut value = serde_json::from_str(contents)?;
+    migrate_config(&mut value, version);
+    serde_json::from_value(value)
+}
+
+// 古い形式の設定を from_version から順に現在の形式へ変換する
+fn migrate_config(value: &mut serde_json::Value, from_version: u32) {
+    let Some(root) = value.as_object_mut() else {
+        return;
+    };
+    // 0 → 1: キャメルケースの項目名（autoStart など）をスネークケースにそろえる
+    // （両方ある場合はスネークケースの値を使う。別名のままでは両方あると読み込めない）
+    if from_version < 1 {
+        snake_case_keys(root);
+        for list in ["registered_apps", "launch_groups"] {
+            let entries = root.get_mut(list).and_then(|v| v.as_array_mut());
+            for entry in entries.into_iter().flatten() {
+                if let Some(entry) = entry.as_object_mut() {
+                    snake_case_keys(entry);
+                }
+            }
+        }
+    }
+    root.insert("config_version".to_string(), CONFIG_VERSION.into());
+}
+
+// オブジェクトの項目名をスネークケースに変換する（既にある項目は上書きしない）
+fn snake_case_keys(object: &mut serde_json::Map<String, serde_json::Value>) {
+    let camel_keys: Vec<String> = object
+        .keys()
+        .filter(|key| key.chars().any(|c| c.is_ascii_uppercase()))
+        .cloned()
+        .collect();
+    for key in camel_keys {
+        let mut snake_key = String::new();
+        for c in key.chars() {
+            if c.is_ascii_uppercase() {
+                snake_key.push('_');
+            }
+            snake_key.push(c.to_ascii_lowercase());
+        }
+        if let Some(value) = object.remove(&key) {
+            object.entry(snake_key).or_insert(value);
+        }
+    }
+}
+
+fn read_config_file(app: &AppHandle) -> (AppConfig, Option<String>) {
+    // 書き込みを待っている内容があれば先に書き込み、ファイルの古い内容を読まないようにする
+    if let Err(e) = flush_config(app) {
+        error!("Failed to save config before reading it: {}", e);
+    }
+    let config_path = get_config_path(app);
+    let error = match config_io(|| {
+        let config_path = config_path.clone();
+        move || std::fs::read_to_string(&config_path)
+    }) {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return (AppConfig::default(), None);
+        }
+        // UTF-8 でないファイルは壊れたファイルとして退避する
+        Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+            format!("Failed to read config {}: {}", config_path.display(), e)
+        }
+        // 届かない場合（ネットワークドライブなど）は壊れたファイルとして退避せず、既定の設定で続ける
+        Err(e) => {
+            let error = format!("Failed to read config {}: {}", config_path.display(), e);
+            error!("{}", error);
+            mark_config_unavailable(app, &error);
+            return (
+                AppConfig::default(),
+                Some(format!(
+                    "{}\nStarting with the default settings in memory; the config will be read again when it becomes available",
+                    error
+                )),
+            );
+        }
+        Ok(config_str) => match parse_config(&config_str) {
+            Ok(config) => return (config, None),
+            Err(e) => format!(
+                "Invalid config {} at line {}, column {}: {}",
+                config_path.display(),
+                e.line(),
+                e.column(),
+                e
+            ),
+        },
+    };
+    error!("{}", error);
+
+    // 次の保存で上書きされないよう退避しておく
+    let mut backup_name = config_path.file_name().unwrap_or_default().to_os_string();
+    backup_name.push(".bak");
+    let backup_path = config_path.with_file_name(backup_name);
+    let warning = match std::fs::copy(&config_path, &backup_path) {
+        Ok(_) => {
+            warn!("Backed up broken config to {}", backup_path.display());
+            format!(
+                "{}\nThe original file was saved to {}",
+                error,
+                backup_path.display()
+            )
+        }
+        Err(e) => {
+            error!("Failed to back up broken config: {}", e);
+            error
+        }
+    };
+    (AppConfig::default(), Some(warning))
+}
+
+// 起動時の設定の読み込みで発生した警告を取得
+#[tauri::command]
+fn get_startup_warnings(app: AppHandle) -> Vec<String> {
+    let state: tauri::State<AppState> = app.state();
+    let warnings = state.startup_warnings.lock_or_recover().clone();
+    warnings
+}
+
+// 同じディレクトリの一時ファイルに書き込んでから置き換える（書き込み途中で壊れないように）
+// Windowsでも std::fs::rename は既存ファイルを置き換える（MOVEFILE_REPLACE_EXISTING）
+fn write_file_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    write_file_replacing(path, contents, |tmp_path, path| {
+        std::fs::rename(tmp_path, path)
+    })
+    .map_err(|e| e.to_string())
+}
+
+// 一時ファイルの名前に付ける番号（時間切れで残った書き込みと同じ一時ファイルに書き込まないように）
+static TMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// 書き込みごとに別の名前の一時ファイルに書き込み、replace で置き換える
+// （置き換えに失敗した場合は一時ファイルを消し、元のファイルはそのまま残る）
+fn write_file_replacing(
+    path: &Path,
+    contents: &str,
+    replace: impl FnOnce(&Path, &Path) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(format!(
+        ".{}-{}.tmp",
+        std::process::id(),
+        TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let write_tmp = || -> std::io::Result<()> {
+        // ディレクトリがない場合は作成する（設定ディレクトリは起動後に作成される場合がある）
+        let mut file = match std::fs::File::create(&tmp_path) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::File::create(&tmp_path)?
+            }
+            result => result?,
+        };
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    };
+    if let Err(e) = write_tmp() {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(std::io::Error::new(
+            e.kind(),
+            format!("Failed to write {}: {}", tmp_path.display(), e),
+        ));
+    }
+
+    replace(&tmp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        std::io::Error::new(
+            e.kind(),
+            format!("Failed to replace {}: {}", path.display(), e),
+        )
+    })
+}
+
+// 設定ファイルを保存（変更前の内容は元に戻せるよう履歴に積む）
+fn save_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
+    write_config(app, config, true)
+}
+
+// 設定ファイルを保存（record_history が false の場合は元に戻す履歴に積まない。起動回数の記録など）
+// メモリ上の設定を正とし、ファイルへの書き込みは CONFIG_SAVE_DEBOUNCE の間の変更をまとめて行う
+fn write_config(app: &AppHandle, config: &AppConfig, record_history: bool) -> Result<(), String> {
+    let config_path = get_config_path(app);
+    let config_str = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
+    // 変更前の内容（書き込み待ちの場合はファイルがまだ古いため、最後に保存した内容を使う）
+    let previous = app
+        .try_state::<AppState>()
+        .and_then(|state| state.last_saved_config.lock_or_recover().clone())
+        .or_else(|| std::fs::read_to_string(&config_path).ok());
+    // 保存による変更を外部での変更として読み込み直さないよう、書き込む内容を先に記録
+    match app.try_state::<AppState>() {
+        Some(state) => {
+            *state.last_saved_config.lock_or_recover() = Some(config_str.clone());
+            schedule_config_write(app, &state, config_path, config_str.clone());
+        }
+        None => write_file_atomically(&config_path, &config_str)?,
+    }
+    sync_autostart_if_changed(app, config);
+    update_base_directory(app, config);
+
+    if let (true, Some(previous), Some(state)) =
+        (record_history, previous, app.try_state::<AppState>())
+    {
+        if previous != config_str {
+            state.config_history.lock_or_recover().record(previous);
+        }
+    }
+    Ok(())
+}
+
+// 設定ファイルの書き込みを予約する（予約済みの場合は内容だけを新しくし、書き込みは1回にする）
+fn schedule_config_write(app: &AppHandle, state: &AppState, path: PathBuf, contents: String) {
+    let already_scheduled = state
+        .pending_config_write
+        .lock_or_recover()
+        .replace((path, contents))
+        .is_some();
+    if already_scheduled {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(CONFIG_SAVE_DEBOUNCE);
+        if let Err(e) = flush_config(&app) {
+            error!("Failed to save config: {}", e);
+            notify(&app, &tr(&app, "notify.config_save_failed", &[]), &e);
+        }
+    });
+}
+
+// 書き込みを待っている設定ファイルをすぐに書き込む（終了時・ファイルを読む前など）
+// 書き込めない場合は設定ディレクトリに届かないものとして予約したままにし、届くようになったら書き込む
+fn flush_config(app: &AppHandle) -> Result<(), String> {
+    let Some(state) = app.try_state::<AppState>() else {
+        return Ok(());
+    };
+    if CONFIG_UNAVAILABLE.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let _write_guard = state.config_write_lock.lock_or_recover();
+    let Some((path, contents)) = state.pending_config_write.lock_or_recover().take() else {
+        return Ok(());
+    };
+    let result = config_io(|| config_write_attempt(&path, &contents));
+    if let Err(e) = result {
+        let mut pending = state.pending_config_write.lock_or_recover();
+        if pending.is_none() {
+            *pending = Some((path, contents));
+        }
+        drop(pending);
+        let message = e.to_string();
+        mark_config_unavailable(app, &message);
+        return Err(message);
+    }
+    Ok(())
+}
+
+// 元に戻す・やり直すための設定ファイルの内容の履歴（メモリ上のみ。ランチャーを終了すると消える）
+#[derive(Default)]
+pub struct ConfigHistory {
+    undo: std::collections::VecDeque<String>,
+    redo: Vec<String>,
+}
+
+impl ConfigHistory {
+    // 変更前の内容を積む（新しい変更をしたらやり直しはできなくなる）
+    fn record(&mut self, previous: String) {
+        self.undo.push_back(previous);
+        if self.undo.len() > CONFIG_HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+// 自動起動が有効なアプリがあればランチャーをOSの自動起動に登録し、なければ解除
+// （ランチャーが起動すると launch_startup_apps で各アプリが起動される）
+// 登録済みのエントリが古い実行ファイルのパスのままの場合は登録し直す
+fn sync_autostart_entries(config: &AppConfig) {
+    let wanted = launcher_autostart_wanted(config);
+    if autostart::is_enabled() == wanted && (!wanted || autostart::is_current()) {
+        return;
+    }
+    match autostart::set_enabled(wanted) {
+        Ok(()) => info!(
+            "{} OS autostart entry: {}",
+            if wanted { "Registered" } else { "Removed" },
+            autostart::entry_location()
+        ),
+        Err(e) => error!("Failed to update OS autostart entry: {}", e),
+    }
+}
+
+// 自動起動が有効なアプリの有無が変わった場合だけOSの自動起動を同期する
+// （起動回数の記録などの保存のたびにレジストリ・ファイルを確認しないように。起動後の最初の保存では必ず同期する）
+fn sync_autostart_if_changed(app: &AppHandle, config: &AppConfig) {
+    let needed = app
+        .try_state::<AppState>()
+        .is_none_or(|state| autostart_sync_needed(&state, config));
+    if needed {
+        sync_autostart_entries(config);
+    }
+}
+
+// 前回の同期から launcher_autostart_wanted が変わったか（変わった場合は記録し直す）
+fn autostart_sync_needed(state: &AppState, config: &AppConfig) -> bool {
+    let wanted = launcher_autostart_wanted(config);
+    state.autostart_wanted.lock_or_recover().replace(wanted) != Some(wanted)
+}
+
+// 設定からランチャーをOSの自動起動に登録するべきか（自動起動が有効なアプリがある）
+fn launcher_autostart_wanted(config: &AppConfig) -> bool {
+    config
+        .registered_apps
+        .iter()
+        .any(|a| a.auto_start && a.enabled)
+}
+
+// OSの自動起動へのランチャーの登録状態
+#[derive(Debug, Serialize, Clone)]
+pub struct AutostartStatus {
+    // OSに登録されているか（設定とは関係なく、レジストリ・ファイルを確認する）
+    pub registered: bool,
+    // 登録されているエントリが今の実行ファイルを起動するか（登録されていない場合は false）
+    pub up_to_date: bool,
+    // 設定からは登録するべきか（自動起動が有効なアプリがある）
+    pub wanted: bool,
+    // レジストリのキーまたはファイルのパス
+    pub location: String,
+}
+
+// ランチャーがOSの自動起動に登録されているかを、実際のOSの状態から取得
+// （ユーザーが msconfig などで削除した場合は、設定と食い違う）
+#[tauri::command]
+fn get_autostart_status(app: AppHandle) -> Result<AutostartStatus, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let wanted = launcher_autostart_wanted(&lock_config(&state));
+    Ok(AutostartStatus {
+        registered: autostart::is_enabled(),
+        up_to_date: autostart::is_current(),
+        wanted,
+        location: autostart::entry_location(),
+    })
+}
+
+// ランチャーをOSの自動起動に直接登録・解除する
+// （設定を保存すると、自動起動が有効なアプリの有無に合わせて登録し直される）
+#[tauri::command]
+fn set_launcher_autostart(enabled: bool) -> Result<(), CommandError> {
+    autostart::set_enabled(enabled)?;
+    info!(
+        "{} OS autostart entry: {}",
+        if enabled { "Registered" } else { "Removed" },
+        autostart::entry_location()
+    );
+    Ok(())
+}
+
+// ランチャーがOSに登録したもの（自動起動・ホットキー・ディープリンク）を解除し、remove_data の場合は
+// 設定・ログのディレクトリも削除する。何も登録されていなくても成功し、行った内容の一覧を返す
+#[tauri::command]
+fn cleanup_system_integration(
+    app: AppHandle,
+    remove_data: bool,
+) -> Result<Vec<String>, CommandError> {
+    let mut summary = Vec::new();
+
+    if autostart::is_enabled() {
+        autostart::set_enabled(false)?;
+        summary.push(format!(
+            "Removed autostart entry: {}",
+            autostart::entry_location()
+        ));
+    }
+
+    let state: tauri::State<AppState> = app.state();
+    let mut hotkeys = state.hotkeys.lock_or_recover();
+    if !hotkeys.is_empty() {
+        app.global_shortcut()
+            .unregister_all()
+            .map_err(|e| format!("Failed to unregister hotkeys: {}", e))?;
+        summary.push(format!("Unregistered {} global hotkey(s)", hotkeys.len()));
+        hotkeys.clear();
+    }
+    drop(hotkeys);
+
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
+    if app
+        .deep_link()
+        .is_registered(DEEP_LINK_SCHEME)
+        .unwrap_or(false)
+    {
+        app.deep_link()
+            .unregister(DEEP_LINK_SCHEME)
+            .map_err(|e| format!("Failed to unregister deep link scheme: {}", e))?;
+        summary.push(format!(
+            "Unregistered deep link scheme {}://",
+            DEEP_LINK_SCHEME
+        ));
+    }
+
+    if remove_data {
+        // 削除したディレクトリを監視し続けないよう、先に設定ファイルの監視を止める
+        *state.config_watcher.lock_or_recover() = None;
+
+        if let Some(dir) = config_dir_override() {
+            // ポータブルモードのディレクトリは利用者が指定したもの（実行ファイルと同じ場所の場合もある）のため削除しない
+            summary.push(format!(
+                "Kept portable config dir (delete it manually if needed): {}",
+                dir.display()
+            ));
+        } else {
+            let path = app.path();
+            let mut dirs: Vec<PathBuf> = [
+                path.app_config_dir(),
+                path.app_data_dir(),
+                path.app_log_dir(),
+            ]
+            .into_iter()
+            .filter_map(Result::ok)
+            .collect();
+            dirs.dedup();
+            for dir in dirs.iter().filter(|dir| dir.exists()) {
+                // ランチャーのログは書き込み中のため削除できない場合がある
+                match std::fs::remove_dir_all(dir) {
+                    Ok(()) => summary.push(format!("Deleted {}", dir.display())),
+                    Err(e) => summary.push(format!("Failed to delete {}: {}", dir.display(), e)),
+                }
+            }
+        }
+    }
+
+    if summary.is_empty() {
+        summary.push("Nothing was registered".to_string());
+    }
+    for line in &summary {
+        info!("Cleanup: {}", line);
+    }
+    Ok(summary)
+}
+
+// 登録されたアプリケーション一覧を取得
+#[tauri::command]
+fn get_registered_apps(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    let mut registered_apps = config.registered_apps.clone();
+    drop(config);
+    registered_apps.sort_by_key(|a| a.sort_order);
+    Ok(registered_apps)
+}
+
+// IDを指定してアプリケーションを取得
+#[tauri::command]
+fn get_registered_app(app: AppHandle, id: String) -> Result<RegisteredApp, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let registered_app = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|a| a.id == id)
+        .cloned();
+    registered_app.ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))
+}
+
+// 指定したID順に表示順を並べ替え（存在しないIDは無視し、指定されなかったアプリは末尾に元の順序で並べる）
+#[tauri::command]
+fn reorder_apps(app: AppHandle, ordered_ids: Vec<String>) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    config.registered_apps.sort_by_key(|a| a.sort_order);
+    let mut next_order = 0;
+    for id in &ordered_ids {
+        if let Some(app_entry) = config.registered_apps.iter_mut().find(|a| &a.id == id) {
+            app_entry.sort_order = next_order;
+            next_order += 1;
+        }
+    }
+    for app_entry in config
+        .registered_apps
+        .iter_mut()
+        .filter(|a| !ordered_ids.contains(&a.id))
+    {
+        app_entry.sort_order = next_order;
+        next_order += 1;
+    }
+    config.registered_apps.sort_by_key(|a| a.sort_order);
+
+    save_config(&app, &config)?;
+    drop(config);
+
+    rebuild_tray_menu(&app);
+    Ok(())
+}
+
+// カテゴリで絞り込んだアプリケーション一覧を取得（空文字の場合は未分類のアプリ）
+#[tauri::command]
+fn get_apps_by_category(
+    app: AppHandle,
+    category: String,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    let category = category.trim();
+    Ok(config
+        .registered_apps
+        .iter()
+        .filter(|a| a.category.trim() == category)
+        .cloned()
+        .collect())
+}
+
+// クエリの文字が順番通りに含まれていればスコアを返す（大文字小文字は区別しない。含まれない場合は None）
+// 連続して一致する文字・単語の先頭で一致する文字ほど高く、間に挟まる文字が多いほど低くなる
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let index = position + text[position..].iter().position(|&c| c == query_char)?;
+        let word_start = index == 0 || !text[index - 1].is_alphanumeric();
+        score += match previous {
+            Some(previous) if previous + 1 == index => 8,
+            _ if word_start => 6,
+            _ => 1,
+        };
+        score -= previous
+            .map_or(index, |previous| index - previous - 1)
+            .min(10) as i64;
+        previous = Some(index);
+        position = index + 1;
+    }
+    // 短い文字列ほど一致の割合が高いため優先する
+    Some(score * 100 - text.len() as i64)
+}
+
+// 名前・パス・説明をあいまい検索し、一致度の高い順に返す（名前で一致したものを優先。空のクエリは全件）
+#[tauri::command]
+fn search_apps(app: AppHandle, query: String) -> Result<Vec<RegisteredApp>, CommandError> {
+    let mut registered_apps = get_registered_apps(app)?;
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(registered_apps);
+    }
+
+    // (一致した項目の優先順位, スコア) の良い方を採用する
+    let rank = |registered_app: &RegisteredApp| {
+        [
+            &registered_app.name,
+            &registered_app.path,
+            &registered_app.description,
+        ]
+        .iter()
+        .enumerate()
+        .filter_map(|(field, text)| fuzzy_score(query, text).map(|score| (field, -score)))
+        .min()
+    };
+    let mut ranked: Vec<_> = registered_apps
+        .drain(..)
+        .filter_map(|registered_app| rank(&registered_app).map(|r| (r, registered_app)))
+        .collect();
+    // 同じ順位の場合は表示順を保つ
+    ranked.sort_by_key(|(r, _)| *r);
+    Ok(ranked.into_iter().map(|(_, a)| a).collect())
+}
+
+// 設定をリセット（開発・デバッグ用）
+#[tauri::command]
+fn reset_config(app: AppHandle) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    // 設定をクリア
+    config.registered_apps.clear();
+    config.launch_groups.clear();
+
+    // 設定ファイルを保存
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!("Configuration has been reset");
+    Ok(())
+}
+
+// 名前が他のアプリと重複していないか確認（前後の空白を除き、大文字・小文字を区別せずに比較）
+// 重複している場合は既存のアプリのIDをエラーに含める
+fn check_duplicate_name(
+    config: &AppConfig,
+    name: &str,
+    exclude_id: Option<&str>,
+) -> Result<(), String> {
+    let key = name.trim().to_lowercase();
+    match config
+        .registered_apps
+        .iter()
+        .find(|a| Some(a.id.as_str()) != exclude_id && a.name.trim().to_lowercase() == key)
+    {
+        Some(existing) => Err(format!(
+            "{} (id: {}): an app named '{}' already exists",
+            DUPLICATE_NAME_ERROR, existing.id, existing.name
+        )),
+        None => Ok(()),
+    }
+}
+
+// アプリケーションを登録
+#[tauri::command]
+fn add_registered_app(
+    app: AppHandle,
+    input: RegisteredApp,
+    allow_duplicate_name: Option<bool>,
+) -> Result<RegisteredApp, CommandError> {
+    let mut new_app = input.with_managed_fields_reset(uuid::Uuid::new_v4().to_string());
+    validate_registered_app(&new_app).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    if !allow_duplicate_name.unwrap_or(false) {
+        check_duplicate_name(&config, &new_app.name, None)
+            .map_err(|e| CommandError::new(ErrorCode::DuplicateName, e))?;
+    }
+
+    // 新しいアプリは末尾に追加
+    new_app.sort_order = config
+        .registered_apps
+        .iter()
+        .map(|a| a.sort_order + 1)
+        .max()
+        .unwrap_or(0);
+
+    config.registered_apps.push(new_app.clone());
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    Ok(new_app)
+}
+
+// アプリケーション情報を更新（表示順・起動回数は変更しない）
+#[tauri::command]
+fn update_registered_app(
+    app: AppHandle,
+    id: String,
+    input: RegisteredApp,
+    allow_duplicate_name: Option<bool>,
+) -> Result<(), CommandError> {
+    let mut updated = input.with_managed_fields_reset(id.clone());
+    validate_registered_app(&updated).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    if !allow_duplicate_name.unwrap_or(false) {
+        check_duplicate_name(&config, &updated.name, Some(&id))
+            .map_err(|e| CommandError::new(ErrorCode::DuplicateName, e))?;
+    }
+
+    if let Some(app_entry) = config.registered_apps.iter_mut().find(|a| a.id == id) {
+        updated.sort_order = app_entry.sort_order;
+        updated.launch_count = app_entry.launch_count;
+        updated.last_launched = app_entry.last_launched.take();
+        *app_entry = updated;
+
+        save_config(&app, &config)?;
+        drop(config);
+
+        sync_file_watchers(&app);
+        sync_hotkeys(&app);
+        rebuild_tray_menu(&app);
+        Ok(())
+    } else {
+        Err(CommandError::new(
+            ErrorCode::AppNotFound,
+            "Application not found",
+        ))
+    }
+}
+
+// 登録・更新・インポートするアプリの内容を確認（パス・引数・コマンド・スケジュールなど）
+fn validate_registered_app(registered_app: &RegisteredApp) -> Result<(), String> {
+    validate_launch_target(&registered_app.path, registered_app.use_shell)?;
+    reject_control_characters("Arguments", &registered_app.arguments)?;
+    reject_control_characters("Working directory", &registered_app.working_directory)?;
+    let commands = [
+        ("Stop command", &registered_app.stop_command),
+        ("Pre-launch hook", &registered_app.pre_launch),
+        ("Post-launch hook", &registered_app.post_launch),
+    ];
+    for (label, command) in commands {
+        if let Some(command) = command {
+            reject_control_characters(label, command)?;
+        }
+    }
+    validate_schedule(&registered_app.schedule)?;
+    validate_color(&registered_app.color)?;
+    validate_sha256(&registered_app.expected_sha256)?;
+    validate_run_as_user(&registered_app.run_as_user, registered_app.run_as_admin)?;
+    validate_arg_presets(&registered_app.arg_presets)?;
+    if let Some(health_check) = &registered_app.health_check {
+        health::validate(health_check)?;
+    }
+    if let Some(launch_condition) = &registered_app.launch_condition {
+        condition::validate(launch_condition)?;
+    }
+    Ok(())
+}
+
+// patch_registered_app で変更できない項目（IDと、ランチャーが記録する起動回数・表示順）
+const READ_ONLY_APP_FIELDS: &[&str] = &["id", "sort_order", "launch_count", "last_launched"];
+
+// アプリの一部の項目だけを更新し、更新後のアプリを返す（指定しなかった項目はそのまま残す）
+// 項目名は snake_case・camelCase のどちらでもよい。存在しない項目・変更できない項目はエラーにする
+#[tauri::command]
+fn patch_registered_app(
+    app: AppHandle,
+    id: String,
+    patch: serde_json::Value,
+) -> Result<RegisteredApp, CommandError> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "The patch must be a JSON object",
+        ));
+    };
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    let index = config
+        .registered_apps
+        .iter()
+        .position(|a| a.id == id)
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+
+    let serde_json::Value::Object(mut fields) =
+        serde_json::to_value(&config.registered_apps[index]).map_err(|e| e.to_string())?
+    else {
+        return Err("Failed to serialize the application".into());
+    };
+    let mut name_changed = false;
+    for (key, value) in patch {
+        let field = camel_to_snake_case(&key);
+        if READ_ONLY_APP_FIELDS.contains(&field.as_str()) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("Field '{}' cannot be changed with a patch", key),
+            ));
+        }
+        if !fields.contains_key(&field) {
+            return Err(CommandError::new(
+                ErrorCode::InvalidInput,
+                format!("Unknown field '{}'", key),
+            ));
+        }
+        name_changed |= field == "name";
+        fields.insert(field, value);
+    }
+    let patched: RegisteredApp = serde_json::from_value(serde_json::Value::Object(fields))
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, format!("Invalid patch: {}", e)))?;
+
+    // update_registered_app と同じ確認を行う
+    validate_registered_app(&patched).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    if name_changed {
+        check_duplicate_name(&config, &patched.name, Some(&id))
+            .map_err(|e| CommandError::new(ErrorCode::DuplicateName, e))?;
+    }
+
+    config.registered_apps[index] = patched.clone();
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+    Ok(patched)
+}
+
+// camelCase の項目名を snake_case にする（既に snake_case の場合はそのまま）
+fn camel_to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+// アプリケーションを削除
+#[tauri::command]
+fn remove_registered_app(app: AppHandle, id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    config.registered_apps.retain(|a| a.id != id);
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    Ok(())
+}
+
+// 複数のアプリの有効・無効をまとめて切り替え（存在しないIDは無視）
+#[tauri::command]
+fn set_apps_enabled(app: AppHandle, ids: Vec<String>, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    for app_entry in config
+        .registered_apps
+        .iter_mut()
+        .filter(|a| ids.contains(&a.id))
+    {
+        app_entry.enabled = enabled;
+    }
+
+    save_config(&app, &config)?;
+    drop(config);
+
+    // 他の変更と同じく、ファイル監視・ホットキー・トレイのメニューを設定に合わせ直す
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    Ok(())
+}
+
+// アプリケーションを複製（新しいIDと「 (copy)」付きの名前で元のアプリの直後に追加）
+#[tauri::command]
+fn duplicate_registered_app(app: AppHandle, id: String) -> Result<RegisteredApp, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    let index = config
+        .registered_apps
+        .iter()
+        .position(|a| a.id == id)
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+
+    let mut new_app = config.registered_apps[index].clone();
+    new_app.id = uuid::Uuid::new_v4().to_string();
+    new_app.name = format!("{} (copy)", new_app.name);
+    new_app.launch_count = 0;
+    new_app.last_launched = None;
+    // ホットキーは同じ組み合わせを登録できないため引き継がない
+    new_app.hotkey = None;
+
+    // 元のアプリより後ろのアプリを1つずつ後ろにずらす
+    new_app.sort_order += 1;
+    for app_entry in config
+        .registered_apps
+        .iter_mut()
+        .filter(|a| a.sort_order >= new_app.sort_order)
+    {
+        app_entry.sort_order += 1;
+    }
+    config.registered_apps.insert(index + 1, new_app.clone());
+
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_file_watchers(&app);
+    rebuild_tray_menu(&app);
+
+    Ok(new_app)
+}
+
+// 環境変数を展開（未定義の変数は警告を記録してそのまま残す）
+fn expand_env(input: &str) -> String {
+    expand_env_with(input, |name| {
+        warn!("Environment variable not found: {}", name)
+    })
+}
+
+// 未定義の環境変数の名前を返す
+fn unresolved_env_vars(input: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    expand_env_with(input, |name| names.push(name.to_string()));
+    names
+}
+
+// 環境変数を展開（Windows: %VAR%、それ以外: $VAR / ${VAR}）。未定義の変数は on_missing に渡してそのまま残す
+#[cfg(target_os = "windows")]
+fn expand_env_with(input: &str, mut on_missing: impl FnMut(&str)) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let name = &after[..end];
+        match std::env::var(name).ok().filter(|_| !name.is_empty()) {
+            Some(value) => result.push_str(&value),
+            None => {
+                if !name.is_empty() {
+                    on_missing(name);
+                }
+                result.push_str(&rest[start..start + end + 2]);
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// 環境変数を展開（Windows: %VAR%、それ以外: $VAR / ${VAR}）。未定義の変数は on_missing に渡してそのまま残す
+#[cfg(not(target_os = "windows"))]
+fn expand_env_with(input: &str, mut on_missing: impl FnMut(&str)) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        // ${VAR} 形式と $VAR 形式の変数名と、変数部分の長さを取得
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .char_indices()
+                .find(|&(i, c)| {
+                    !(c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()))
+                })
+                .map(|(i, _)| i)
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+
+        if name.is_empty() {
+            result.push('$');
+            rest = after;
+            continue;
+        }
+        match std::env::var(name) {
+            Ok(value) => result.push_str(&value),
+            Err(_) => {
+                on_missing(name);
+                result.push('$');
+                result.push_str(&after[..consumed]);
+            }
+        }
+        rest = &after[consumed..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// パスがURL・カスタムプロトコル（https://... や steam://run/440 など）か判定
+// （Windowsのドライブレター "C:\..." と区別するためスキームは2文字以上とする）
+fn is_url_path(path: &str) -> bool {
+    let path = path.trim();
+    match path.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() >= 2
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+// 相対パスの実行ファイルの基準ディレクトリ（設定の読み込み・保存のたびに更新する）
+static BASE_DIRECTORY: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// 設定の base_directory から基準ディレクトリを求めて更新する
+fn update_base_directory(app: &AppHandle, config: &AppConfig) {
+    *BASE_DIRECTORY.lock_or_recover() = base_directory_path(app, &config.base_directory);
+}
+
+// base_directory の値から基準ディレクトリのパスを求める（空の場合・相対パスは設定ディレクトリを基準にする）
+fn base_directory_path(app: &AppHandle, base_directory: &str) -> Option<PathBuf> {
+    let config_dir = get_config_path(app).parent().map(Path::to_path_buf);
+    let base_directory = expand_env(base_directory.trim());
+    if base_directory.is_empty() {
+        return config_dir;
+    }
+    let base = PathBuf::from(base_directory);
+    match config_dir {
+        Some(dir) if !base.is_absolute() => Some(dir.join(base)),
+        _ => Some(base),
+    }
+}
+
+// 環境変数を展開し、相対パスは基準ディレクトリからのパスに解決する（絶対パス・URLはそのまま）
+// ディレクトリを含まないファイル名だけのパスは、基準ディレクトリにある場合だけ解決する（なければ PATH から探す）
+fn resolve_relative_path(path: &str) -> String {
+    let expanded = expand_env(path.trim());
+    let relative = Path::new(&expanded);
+    if expanded.is_empty()
+        || is_url_path(&expanded)
+        || relative.is_absolute()
+        || relative.has_root()
+    {
+        return expanded;
+    }
+    let Some(base) = BASE_DIRECTORY.lock_or_recover().clone() else {
+        return expanded;
+    };
+    let resolved = base.join(relative);
+    if relative.components().count() > 1 || resolved.exists() {
+        resolved.to_string_lossy().into_owned()
+    } else {
+        expanded
+    }
+}
+
+// 実行ファイルのパスが存在し、起動できる形式か確認（URLの場合は確認しない）
+fn validate_app_path(path: &str) -> Result<(), String> {
+    if is_url_path(path) {
+        return Ok(());
+    }
+    // ワイルドカードは起動時に解決するため、形式だけ確認する（今は一致しなくても登録できる）
+    if is_glob_path(path) {
+        return validate_path_glob(path);
+    }
+    let path = &resolve_relative_path(path);
+
+    let path = Path::new(path);
+    if !path.exists() {
+        return Err(format!("File not found: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(format!("Not an executable file: {}", path.display()));
+    }
+
+    // ショートカット(.lnk)はファイルが存在すれば起動できる
+    #[cfg(target_os = "windows")]
+    {
+        const LAUNCHABLE_EXTENSIONS: &[&str] = &["exe", "com", "bat", "cmd", "msc", "lnk"];
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !LAUNCHABLE_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(format!(
+                "Not an executable file (expected one of {}): {}",
+                LAUNCHABLE_EXTENSIONS.join(", "),
+                path.display()
+            ));
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .permissions()
+            .mode();
+        if mode & 0o111 == 0 {
+            return Err(format!(
+                "Not an executable file (missing execute permission): {}",
+                path.display()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// 起動する対象を確認（シェルで実行する場合はパスがコマンドの一部のため空でないことだけ確認）
+fn validate_launch_target(path: &str, use_shell: bool) -> Result<(), String> {
+    reject_control_characters("Path", path)?;
+    if use_shell {
+        if path.trim().is_empty() {
+            return Err("Command must not be empty".to_string());
+        }
+        return Ok(());
+    }
+    if !is_url_path(path) && looks_like_command_injection(path) {
+        return Err(format!(
+            "Path contains characters that could be interpreted as a command: {}",
+            path
+        ));
+    }
+    validate_app_path(path)
+}
+
+// パスにワイルドカード（* ? [）を含むか（更新のたびにインストール先が変わるアプリ向け）
+fn is_glob_path(path: &str) -> bool {
+    !is_url_path(path) && path.contains(['*', '?', '['])
+}
+
+// ワイルドカードの比較方法（Windowsでは大文字小文字を区別しない）
+fn glob_match_options() -> glob::MatchOptions {
+    glob::MatchOptions {
+        case_sensitive: !cfg!(target_os = "windows"),
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    }
+}
+
+// ワイルドカードのパスの形式を確認し、今一致するファイルがなければ警告を記録する
+fn validate_path_glob(path: &str) -> Result<(), String> {
+    let pattern = resolve_relative_path(path);
+    glob::Pattern::new(&pattern).map_err(|e| format!("Invalid path pattern {}: {}", pattern, e))?;
+    match resolve_path_glob(path, PathGlobPick::default()) {
+        Ok(resolved) => info!("Path pattern {} currently matches {}", pattern, resolved),
+        Err(e) => warn!("{}", e),
+    }
+    Ok(())
+}
+
+// ワイルドカードのパスを一致するファイルに解決（一致しない場合はエラー、複数一致した場合は選んだものをログに記録）
+fn resolve_path_glob(path: &str, pick: PathGlobPick) -> Result<String, String> {
+    let pattern = resolve_relative_path(path);
+    let matches: Vec<PathBuf> = glob::glob_with(&pattern, glob_match_options())
+        .map_err(|e| format!("Invalid path pattern {}: {}", pattern, e))?
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect();
+    let chosen = match pick {
+        PathGlobPick::HighestName => matches.iter().max(),
+        PathGlobPick::Newest => matches.iter().max_by_key(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        }),
+    }
+    .ok_or_else(|| format!("File not found: no file matches {}", pattern))?;
+    if matches.len() > 1 {
+        info!(
+            "Path pattern {} matched {} files; using {}",
+            pattern,
+            matches.len(),
+            chosen.display()
+        );
+    }
+    Ok(chosen.to_string_lossy().into_owned())
+}
+
+// ワイルドカードのパスが今どのファイルに一致するかを取得（登録前の確認用。一致しない場合はエラー）
+#[tauri::command]
+fn resolve_path_pattern(path: String, pick: Option<PathGlobPick>) -> Result<String, CommandError> {
+    if !is_glob_path(&path) {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Not a path pattern: {}", path),
+        ));
+    }
+    resolve_path_glob(&path, pick.unwrap_or_default())
+        .map_err(|e| CommandError::new(ErrorCode::NotFound, e))
+}
+
+// 登録したアプリの実行ファイルのパス（環境変数を展開し、ワイルドカードは解決する。解決できない場合はそのまま）
+fn registered_executable_path(registered_app: &RegisteredApp) -> String {
+    if is_glob_path(&registered_app.path) {
+        if let Ok(resolved) = resolve_path_glob(&registered_app.path, registered_app.path_glob_pick)
+        {
+            return resolved;
+        }
+    }
+    resolve_relative_path(&registered_app.path)
+}
+
+// 別のユーザーとして実行する場合のユーザー名（空欄の場合は None）
+fn runs_as_user(registered_app: &RegisteredApp) -> Option<&str> {
+    registered_app
+        .run_as_user
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+}
+
+// 管理者として実行するか（auto_elevate の場合は実行ファイルのマニフェストで判断する）
+fn runs_as_admin(registered_app: &RegisteredApp) -> bool {
+    if registered_app.run_as_admin {
+        return true;
+    }
+    if !cfg!(target_os = "windows")
+        || !registered_app.auto_elevate
+        || registered_app.use_shell
+        || runs_as_user(registered_app).is_some()
+    {
+        return false;
+    }
+    let (path, _) = resolve_launch_target(&registered_executable_path(registered_app), "");
+    executable::requires_administrator(&path)
+}
+
+// 改行などの制御文字を含む値を拒否する（コマンド文字列の途中で改行されると別のコマンドとして実行されるため）
+fn reject_control_characters(label: &str, value: &str) -> Result<(), String> {
+    match value.chars().find(|c| c.is_control() && *c != '\t') {
+        Some(c) => Err(format!(
+            "{} must not contain control characters (found U+{:04X})",
+            label, c as u32
+        )),
+        None => Ok(()),
+    }
+}
+
+// クォートを閉じて別のコマンドを続ける・サブ式を埋め込むなど、パスとしては不自然な並びを含むか
+// （例: C:\a'; Remove-Item C:\b。PowerShellのコマンドに入れる値はすべて quote_powershell でエスケープするため、
+// これは明らかに不自然なものを登録・インポート時に拒否するための補助的な確認）
+// バッククォートはフォルダー名などに使えるため、引用符・区切り文字をエスケープする並びだけを拒否する
+fn looks_like_command_injection(path: &str) -> bool {
+    let chars: Vec<char> = path.chars().collect();
+    chars.iter().enumerate().any(|(i, &c)| {
+        let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+        (is_quote_char(c) && matches!(next, Some(';' | '|' | '&')))
+            || (c == '$' && chars.get(i + 1) == Some(&'('))
+            || (c == '`'
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|&n| is_quote_char(n) || matches!(n, ';' | '|' | '&')))
+    })
+}
+
+// PowerShellが文字列の区切りとして扱う引用符（全角の引用符も含む）
+fn is_quote_char(c: char) -> bool {
+    matches!(
+        c,
+        '\'' | '"'
+            | '\u{2018}'
+            | '\u{2019}'
+            | '\u{201A}'
+            | '\u{201B}'
+            | '\u{201C}'
+            | '\u{201D}'
+            | '\u{201E}'
+    )
+}
+
+// 引数文字列をシェル風に分割
+// - シングル/ダブルクォートで囲まれた部分は空白を含めて1つの引数として扱う
+// - クォート外の \" \' \<空白> とダブルクォート内の \" はエスケープとして扱う
+// - それ以外のバックスラッシュはWindowsのパス区切りとしてそのまま残す
+fn parse_arguments(raw: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                } else if c == '\\' && q == '"' && chars.peek() == Some(&'"') {
+                    current.push('"');
+                    chars.next();
+                } else {
+                    current.push(c);
+                }
+            }
+            None => {
+                if c == '"' || c == '\'' {
+                    quote = Some(c);
+                    in_token = true;
+                } else if c == '\\'
+                    && matches!(chars.peek(), Some(&n) if n == '"' || n == '\'' || n.is_whitespace())
+                {
+                    current.push(chars.next().unwrap_or(c));
+                    in_token = true;
+                } else if c.is_whitespace() {
+                    if in_token {
+                        args.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                } else {
+                    current.push(c);
+                    in_token = true;
+                }
+            }
+        }
+    }
+
+    if in_token {
+        args.push(current);
+    }
+    args
+}
+
+// Windowsのコマンドライン規則に従って1つの引数をクォート
+fn quote_windows_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.chars().any(|c| c.is_whitespace() || c == '"') {
+        return arg.to_string();
+    }
+    quote_windows_arg_always(arg)
+}
+
+// 空白などを含まなくても必ずクォートする（cmd のメタ文字を含む引数など）
+fn quote_windows_arg_always(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+                backslashes = 0;
+            }
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(c);
+                backslashes = 0;
+            }
+        }
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+// PowerShellのシングルクォート文字列としてクォート
+// PowerShellは全角のシングルクォート（‘ ’ ‚ ‛）も区切りとして扱うため、それらも2つ重ねてエスケープする
+fn quote_powershell(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+// Start-Process の -ArgumentList に渡す配列を組み立て
+fn build_powershell_argument_list(arguments: &str) -> Option<String> {
+    let args = parse_arguments(arguments);
+    if args.is_empty() {
+        return None;
+    }
+    Some(
+        args.iter()
+            .map(|arg| quote_powershell(&quote_windows_arg(arg)))
+            .collect::<Vec<_>>()
+            .join(","),
+    )
+}
+
+// パスを比較用の文字列に変換（Windowsでは大文字小文字と区切り文字の違いを無視）
+#[cfg(target_os = "windows")]
+fn path_compare_key(path: &Path) -> String {
+    let text = path.to_string_lossy();
+    text.strip_prefix(r"\\?\")
+        .unwrap_or(&text)
+        .replace('/', "\\")
+        .to_lowercase()
+}
+
+// パスを比較用の文字列に変換
+#[cfg(not(target_os = "windows"))]
+fn path_compare_key(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+// 実行ファイルのパスを比較用に正規化（シンボリックリンク等も解決）
+// ファイルがない場合は「.」「..」だけを取り除く
+fn normalize_executable_path(path: &str) -> String {
+    let path = Path::new(path);
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| normalize_path_components(path));
+    path_compare_key(&resolved)
+}
+
+// ファイルを参照せずにパスの「.」「..」を取り除く（ルートより上の「..」は無視する）
+fn normalize_path_components(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else if !normalized.has_root() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// 同じ実行ファイルかを比べるためのパス（ショートカットはリンク先、URLは大文字小文字を区別しない）
+fn path_match_key(path: &str) -> String {
+    if is_url_path(path) {
+        return path.trim().to_lowercase();
+    }
+    let (target, _) = resolve_launch_target(path, "");
+    normalize_executable_path(&target)
+}
+
+// ショートカット(.lnk)のリンク先のパスと引数を取得（ショートカットでない・解決できない場合はNone）
+#[cfg(target_os = "windows")]
+fn resolve_shortcut(path: &str) -> Option<(String, String)> {
+    if !path.trim().to_lowercase().ends_with(".lnk") || !powershell_available() {
+        return None;
+    }
+
+    let script = format!(
+        "[Console]::OutputEncoding = [System.Text.Encoding]::UTF8; \
+         $shortcut = (New-Object -ComObject WScript.Shell).CreateShortcut({}); \
+         Write-Output $shortcut.TargetPath; Write-Output $shortcut.Arguments",
+        quote_powershell(path.trim())
+    );
+    let output = Command::new("powershell")
+        .args(["-WindowStyle", "Hidden", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    // リンク先がファイルでないショートカット（コントロールパネル等）はTargetPathが空になる
+    let target = lines.next()?.trim().to_string();
+    if target.is_empty() {
+        return None;
+    }
+    let arguments = lines.next().unwrap_or_default().trim().to_string();
+    Some((target, arguments))
+}
+
+// 起動する実行ファイルと引数を決定（Windowsではショートカットをリンク先に解決し、
+// ショートカットの引数の後ろに登録した引数を付け足す）
+#[cfg(target_os = "windows")]
+fn resolve_launch_target(path: &str, arguments: &str) -> (String, String) {
+    match resolve_shortcut(path) {
+        Some((target, shortcut_arguments)) => {
+            let arguments = [shortcut_arguments.as_str(), arguments.trim()]
+                .into_iter()
+                .filter(|a| !a.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (target, arguments)
+        }
+        None => (path.to_string(), arguments.to_string()),
+    }
+}
+
+// 起動する実行ファイルと引数を決定
+#[cfg(not(target_os = "windows"))]
+fn resolve_launch_target(path: &str, arguments: &str) -> (String, String) {
+    (path.to_string(), arguments.to_string())
+}
+
+// 実行ファイルのパスからプロセス名を求める（ディレクトリと拡張子を除いて小文字にする）
+// 「\」「/」のどちらの区切りのパスも扱う
+fn process_name_from_path(path: &str) -> Option<String> {
+    let file_name = path.trim().rsplit(['/', '\\']).next()?;
+    let stem = match file_name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file_name,
+    };
+    (!stem.is_empty()).then(|| stem.to_lowercase())
+}
+
+// プロセス名が一致する実行中プロセスの数を取得
+fn count_processes_by_name(name: &str) -> usize {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    system
+        .processes()
+        .values()
+        .filter(|process| {
+            process_name_from_path(&process.name().to_string_lossy()).as_deref() == Some(name)
+        })
+        .count()
+}
+
+// 重複起動禁止のアプリのプロセス名が多くのプロセスに一致する場合は警告文を返す
+// （実行ファイルのパスが取得できないプロセスはプロセス名で照合するため、無関係なプロセスを停止するおそれがある）
+#[tauri::command]
+fn check_process_name_conflicts(path: String) -> Option<String> {
+    let (path, _) = resolve_launch_target(&resolve_relative_path(&path), "");
+    let name = process_name_from_path(&path)?;
+    let count = count_processes_by_name(&name);
+    if count < PROCESS_NAME_WARN_THRESHOLD {
+        return None;
+    }
+    warn!(
+        "Process name '{}' matches {} running processes",
+        name, count
+    );
+    Some(format!(
+        "Process name '{}' matches {} running processes. Processes whose executable path cannot be read are matched by this name and may be stopped.",
+        name, count
+    ))
+}
+
+// 同じ実行ファイル（・URL）を登録しているアプリを取得（追加する前に重複を確認する用）
+// 環境変数の展開・相対パスの解決・ショートカットのリンク先の解決をしてから比べる。シェル経由のアプリは除く
+#[tauri::command]
+async fn find_apps_by_path(
+    app: AppHandle,
+    path: String,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    if path.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut registered_apps: Vec<RegisteredApp> = lock_config(&app.state::<AppState>())
+        .registered_apps
+        .iter()
+        .filter(|a| !a.use_shell)
+        .cloned()
+        .collect();
+    registered_apps.sort_by_key(|a| a.sort_order);
+    tauri::async_runtime::spawn_blocking(move || {
+        let key = path_match_key(&resolve_relative_path(&path));
+        registered_apps
+            .into_iter()
+            .filter(|a| path_match_key(&registered_executable_path(a)) == key)
+            .collect()
+    })
+    .await
+    .map_err(CommandError::background_task)
+}
+
+// 実行ファイルのアーキテクチャ・OSを調べ、このマシンで動作しない可能性があれば警告を付ける
+// （ショートカットはリンク先を調べる）
+#[tauri::command]
+fn inspect_executable(path: String) -> Result<executable::ExeInfo, CommandError> {
+    let (path, _) = resolve_launch_target(&resolve_relative_path(&path), "");
+    let info = executable::inspect(&path)?;
+    if let Some(warning) = &info.warning {
+        warn!("{}: {}", path, warning);
+    }
+    Ok(info)
+}
+
+// ファイルのSHA-256を計算する（expected_sha256 に入れる値。起動時と同じくショートカットはリンク先を計算し、
+// 大きなファイルでも画面が止まらないよう別スレッドで計算する）
+#[tauri::command]
+async fn compute_file_hash(path: String) -> Result<String, CommandError> {
+    let (path, _) = resolve_launch_target(&resolve_relative_path(&path), "");
+    tauri::async_runtime::spawn_blocking(move || executable::sha256(&path))
+        .await
+        .map_err(CommandError::background_task)?
+        .map_err(CommandError::from)
+}
+
+// 実行中のプロセス（list_system_processes の結果）
+#[derive(Debug, Serialize, Clone)]
+pub struct SystemProcess {
+    pub pid: u32,
+    pub name: String,
+    pub exe_path: String,
+    // コマンドライン（先頭は実行ファイル。取得できない場合は空）
+    pub cmd: Vec<String>,
+}
+
+// 実行中のプロセスの一覧を名前順に取得（実行中のアプリを選んで登録する用）
+// ランチャーと同じユーザーのプロセスだけを対象にし、カーネルのスレッド・OSのプロセス・ランチャー自身は除く
+#[tauri::command]
+async fn list_system_processes() -> Result<Vec<SystemProcess>, CommandError> {
+    tauri::async_runtime::spawn_blocking(|| {
+        use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_exe(UpdateKind::Always)
+                .with_cmd(UpdateKind::Always)
+                .with_user(UpdateKind::Always),
+        );
+        let own_pid = std::process::id();
+        let own_user = system
+            .process(Pid::from_u32(own_pid))
+            .and_then(|process| process.user_id())
+            .cloned();
+        // WindowsのOSのプロセス（C:\Windows 以下の実行ファイル）は除く
+        let system_root = std::env::var("SystemRoot")
+            .ok()
+            .filter(|_| cfg!(target_os = "windows"))
+            .map(|root| format!("{}\\", path_compare_key(Path::new(&root))));
+
+        let mut processes: Vec<SystemProcess> = system
+            .processes()
+            .iter()
+            .filter(|(pid, process)| {
+                pid.as_u32() != own_pid
+                    && process.thread_kind().is_none()
+                    && (own_user.is_none() || process.user_id() == own_user.as_ref())
+            })
+            .filter_map(|(pid, process)| {
+                // 実行ファイルが分からないもの（カーネルのスレッド・権限のないプロセス）は登録できない
+                let exe = process.exe().filter(|exe| !exe.as_os_str().is_empty())?;
+                if system_root
+                    .as_ref()
+                    .is_some_and(|root| path_compare_key(exe).starts_with(root.as_str()))
+                {
+                    return None;
+                }
+                Some(SystemProcess {
+                    pid: pid.as_u32(),
+                    name: process.name().to_string_lossy().into_owned(),
+                    exe_path: exe.display().to_string(),
+                    cmd: process
+                        .cmd()
+                        .iter()
+                        .map(|arg| arg.to_string_lossy().into_owned())
+                        .collect(),
+                })
+            })
+            .collect();
+        processes.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then(a.pid.cmp(&b.pid))
+        });
+        processes
+    })
+    .await
+    .map_err(CommandError::background_task)
+}
+
+// 実行ファイルのパスが一致する実行中プロセスのPID一覧を取得
+fn find_pids_by_executable_path(path: &str) -> Vec<u32> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+    // ワイルドカードのパスは、一致するどのバージョンの実行ファイルのプロセスも対象にする
+    if is_glob_path(path) {
+        let Ok(pattern) = glob::Pattern::new(&resolve_relative_path(path)) else {
+            return Vec::new();
+        };
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+        );
+        let own_pid = std::process::id();
+        return system
+            .processes()
+            .iter()
+            .filter(|(pid, process)| {
+                pid.as_u32() != own_pid
+                    && process
+                        .exe()
+                        .is_some_and(|exe| pattern.matches_path_with(exe, glob_match_options()))
+            })
+            .map(|(pid, _)| pid.as_u32())
+            .collect();
+    }
+
+    // ショートカットの場合はリンク先の実行ファイルで比較
+    let (path, _) = resolve_launch_target(&resolve_relative_path(path), "");
+    let path = path.as_str();
+    let target = normalize_executable_path(path);
+    let target_name = process_name_from_path(path);
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+
+    let own_pid = std::process::id();
+    system
+        .processes()
+        .iter()
+        .filter(|(pid, process)| {
+            pid.as_u32() != own_pid
+                && match process.exe() {
+                    Some(exe) => path_compare_key(exe) == target,
+                    // 権限不足で実行ファイルのパスが取得できない場合（昇格したプロセスなど）はファイル名で比較
+                    None => {
+                        target_name.is_some()
+                            && process_name_from_path(&process.name().to_string_lossy())
+                                == target_name
+                    }
+                }
+        })
+        .map(|(pid, _)| pid.as_u32())
+        .collect()
+}
+
+// 実行ファイルのパスが一致するプロセスをすべて停止し、停止した数を返す
+fn terminate_processes_by_path(path: &str) -> Result<usize, String> {
+    let pids = find_pids_by_executable_path(path);
+    for pid in &pids {
+        kill_process_tree(*pid)?;
+    }
+    Ok(pids.len())
+}
+
+// 起動に失敗した理由（再試行するかの判断に使い、コマンドからは CommandError に変換して返す）
+#[derive(Debug, Clone, PartialEq)]
+pub enum LaunchError {
+    NotFound(String),
+    AccessDenied(String),
+    Timeout(String),
+    InvalidPath(String),
+    // 直前に同じアプリを起動しようとしたため無視した
+    Debounced(String),
+    Other(String),
+}
+
+impl LaunchError {
+    // PowerShell・OSのエラーメッセージから失敗の理由を分類
+    fn classify(message: String) -> Self {
+        let lower = message.to_lowercase();
+        let contains_any = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+        if contains_any(&["timed out", "timeout"]) {
+            Self::Timeout(message)
+        } else if contains_any(&[
+            "access is denied",
+            "access denied",
+            "permission denied",
+            "canceled by the user",
+            "cancelled by the user",
+            "requires elevation",
+            "no password stored",
+            "user name or password is incorrect",
+            "os error 5)",
+            "os error 13)",
+            "os error 740)",
+        ]) {
+            Self::AccessDenied(message)
+        } else if contains_any(&[
+            "not an executable",
+            "not a valid application",
+            "checksum mismatch",
+            "working directory does not exist",
+            "invalid url",
+            "os error 193)",
+        ]) {
+            Self::InvalidPath(message)
+        } else if contains_any(&[
+            "file not found",
+            "cannot find the file",
+            "cannot find the path",
+            "no such file",
+            "os error 2)",
+            "os error 3)",
+        ]) {
+            Self::NotFound(message)
+        } else {
+            Self::Other(message)
+        }
+    }
+
+    // ファイルのロック・ウイルススキャンなど一時的な原因で失敗した可能性があるか
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::NotFound(_) | Self::InvalidPath(_) | Self::Debounced(_) => false,
+            // UACの確認画面でキャンセルされた場合は再試行しない
+            Self::AccessDenied(message) => {
+                let lower = message.to_lowercase();
+                !lower.contains("canceled by the user") && !lower.contains("cancelled by the user")
+            }
+            Self::Timeout(_) | Self::Other(_) => true,
+        }
+    }
+
+    // フロントエンドへ返すエラーのコード
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::AccessDenied(_) => ErrorCode::PermissionDenied,
+            Self::Timeout(_) => ErrorCode::Timeout,
+            Self::InvalidPath(_) => ErrorCode::InvalidInput,
+            Self::Debounced(_) => ErrorCode::Debounced,
+            Self::Other(_) => ErrorCode::LaunchFailed,
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::AccessDenied(message)
+            | Self::Timeout(message)
+            | Self::InvalidPath(message)
+            | Self::Debounced(message)
+            | Self::Other(message) => message,
+        }
+    }
+}
+
+impl std::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl From<LaunchError> for CommandError {
+    fn from(error: LaunchError) -> Self {
+        CommandError::new(error.code(), error.message())
+    }
+}
+
+// アプリケーションを起動
+#[tauri::command]
+async fn launch_application(
+    app: AppHandle,
+    app_id: String,
+    path: String,
+    arguments: String,
+    context: Option<HashMap<String, String>>,
+    preset_name: Option<String>,
+) -> Result<Option<u32>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+
+    // プリセットが指定された場合は既定の引数の代わりにプリセットの引数で起動する
+    let arguments = match preset_name {
+        Some(preset_name) => {
+            find_arg_preset(&lock_config(&state), &app_id, &preset_name)?.arguments
+        }
+        None => arguments,
+    };
+
+    debounce_launch(&state, &app_id)?;
+
+    // 既に実行中の場合はウィンドウを前面に表示する・停止してから起動し直す
+    let already_running_app = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id && a.on_already_running != OnAlreadyRunning::DoNothing)
+        .cloned();
+    if let Some(registered_app) = already_running_app {
+        if is_app_instance_running(&app, &registered_app).await {
+            match registered_app.on_already_running {
+                OnAlreadyRunning::Focus => {
+                    match focus_app_window(&app, &registered_app) {
+                        Ok(true) => info!("Focused the running window of app_id={}", app_id),
+                        Ok(false) => info!(
+                            "app_id={} is already running but has no window to focus",
+                            app_id
+                        ),
+                        Err(e) => warn!("Failed to focus app_id={}: {}", app_id, e),
+                    }
+                    return Ok(running_tracked_pid(&app, &app_id).ok());
+                }
+                OnAlreadyRunning::Relaunch => {
+                    info!("Relaunching app_id={}: already running", app_id);
+                    // ランチャーの外で起動したプロセスは管理していないため、停止できなくても起動する
+                    match stop_application(app.clone(), app_id.clone()).await {
+                        Err(e)
+                            if !matches!(
+                                e.code,
+                                ErrorCode::ProcessNotRunning | ErrorCode::AppNotFound
+                            ) =>
+                        {
+                            return Err(e);
+                        }
+                        _ => {}
+                    }
+                }
+                OnAlreadyRunning::DoNothing => {}
+            }
+        }
+    }
+
+    // 明示的に起動し直した場合は停止フラグを解除
+    state
+        .intentionally_stopped
+        .lock_or_recover()
+        .remove(&app_id);
+
+    let (
+        name,
+        auto_restart,
+        use_shell,
+        retries,
+        retry_delay_ms,
+        hook_app,
+        placeholders,
+        auto_stop_after_ms,
+    ) = {
+        let config = lock_config(&state);
+        let registered_app = config.registered_apps.iter().find(|a| a.id == app_id);
+        (
+            registered_app.map(|a| a.name.clone()).unwrap_or_else(|| {
+                Path::new(&path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.clone())
+            }),
+            registered_app.is_some_and(|a| a.auto_restart),
+            registered_app.is_some_and(|a| a.use_shell),
+            registered_app.map_or(0, |a| a.launch_retries),
+            registered_app.map_or(0, |a| a.launch_retry_delay_ms),
+            registered_app
+                .filter(|a| a.pre_launch.is_some() || a.post_launch.is_some())
+                .cloned(),
+            registered_app.is_some_and(|a| a.placeholders),
+            registered_app.and_then(|a| a.auto_stop_after_ms),
+        )
+    };
+
+    let is_url = is_url_path(&path);
+
+    // パスと引数の環境変数を展開
+    let path = expand_env(&path);
+    let mut arguments = expand_env(&arguments);
+
+    // 引数のプレースホルダーを置き換える（ファイルの選択をキャンセルした場合などは起動しない）
+    if placeholders {
+        arguments = resolve_placeholders(&app, &arguments, context.unwrap_or_default())
+            .await
+            .map_err(|e| CommandError::new(ErrorCode::LaunchFailed, e))?;
+    }
+
+    // 引数の秘密の値が見つからない場合は、起動前のコマンドも実行せずにエラーにする
+    if let Err(e) = secret::resolve(&arguments) {
+        notify(
+            &app,
+            &tr(&app, "notify.launch_failed", &[("name", &name)]),
+            &e,
+        );
+        record_launch_event(&app, &app_id, &name, Err(&e));
+        return Err(CommandError::new(ErrorCode::LaunchFailed, e));
+    }
+
+    // 起動前のコマンド（失敗をエラーとして扱う場合は起動しない）
+    if let Err(e) = run_hook(&app, hook_app.as_ref(), HookStage::PreLaunch).await {
+        notify(
+            &app,
+            &tr(&app, "notify.launch_failed", &[("name", &name)]),
+            &e,
+        );
+        record_launch_event(&app, &app_id, &name, Err(&e));
+        return Err(CommandError::new(ErrorCode::LaunchFailed, e));
+    }
+
+    // 実行ファイルを確認してから起動し、一時的な失敗の場合は設定された回数まで再試行
+    let mut attempts = 0;
+    let result = loop {
+        attempts += 1;
+        let result = match validate_launch_target(&path, use_shell) {
+            Ok(()) => {
+                spawn_application(app.clone(), app_id.clone(), path.clone(), arguments.clone())
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let error = match result {
+            Ok(pid) => break Ok(pid),
+            Err(e) => LaunchError::classify(e),
+        };
+        if attempts > retries || !error.is_transient() {
+            break Err(error);
+        }
+        warn!(
+            "Launch attempt {} of app_id={} failed, retrying in {}ms: {}",
+            attempts,
+            app_id,
+            retry_delay_ms,
+            error.message()
+        );
+        tokio::time::sleep(Duration::from_millis(retry_delay_ms)).await;
+    };
+    let pid = match result {
+        Ok(pid) => {
+            notify(&app, &tr(&app, "notify.started", &[("name", &name)]), "");
+            record_launch(&app, &app_id);
+            record_launch_event(&app, &app_id, &name, Ok(()));
+            pid
+        }
+        Err(error) => {
+            let message = if attempts > 1 {
+                format!("{} (failed after {} attempts)", error.message(), attempts)
+            } else {
+                error.message().to_string()
+            };
+            notify(
+                &app,
+                &tr(&app, "notify.launch_failed", &[("name", &name)]),
+                &message,
+            );
+            record_launch_event(&app, &app_id, &name, Err(&message));
+            return Err(CommandError::new(error.code(), message));
+        }
+    };
+
+    // URLは終了を検知できないため自動再起動の対象外
+    if auto_restart && !is_url {
+        ensure_restart_monitor(&app, &app_id);
+    }
+
+    // プロセスを追跡できる場合だけ自動停止を予約する（前回の起動の予約は取り消す）
+    if let (Some(after_ms), Some(_)) = (auto_stop_after_ms.filter(|&ms| ms > 0), pid) {
+        schedule_auto_stop(&app, &app_id, Duration::from_millis(after_ms));
+    }
+
+    // 起動後のコマンド（アプリは起動済みのため、失敗しても停止せず起動は成功として返す）
+    if let Err(e) = run_hook(&app, hook_app.as_ref(), HookStage::PostLaunch).await {
+        notify(
+            &app,
+            &tr(&app, "notify.post_launch_failed", &[("name", &name)]),
+            &e,
+        );
+    }
+
+    Ok(pid)
+}
+
+// 今回の起動だけ引数を変えて登録したアプリを起動する（設定は変更しない）
+// replace が true なら登録した引数の代わりに extra_arguments を使い、false なら後ろに追加する
+#[tauri::command]
+async fn launch_with_args(
+    app: AppHandle,
+    app_id: String,
+    extra_arguments: String,
+    replace: bool,
+) -> Result<Option<u32>, CommandError> {
+    reject_control_characters("Arguments", &extra_arguments)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    let registered_app = lock_config(&app.state::<AppState>())
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .cloned()
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+
+    let extra_arguments = extra_arguments.trim();
+    let arguments = if replace || registered_app.arguments.trim().is_empty() {
+        extra_arguments.to_string()
+    } else if extra_arguments.is_empty() {
+        registered_app.arguments.clone()
+    } else {
+        format!(
+            "{} {}",
+            registered_app.arguments.trim_end(),
+            extra_arguments
+        )
+    };
+    info!(
+        "Launching app_id={} with one-off arguments ({})",
+        app_id,
+        if replace { "replaced" } else { "appended" }
+    );
+    launch_application(app, app_id, registered_app.path, arguments, None, None).await
+}
+
+// アプリの引数のプリセットを名前で探す（見つからない場合はエラー）
+fn find_arg_preset(
+    config: &AppConfig,
+    app_id: &str,
+    preset_name: &str,
+) -> Result<ArgPreset, CommandError> {
+    let registered_app = config
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+    registered_app
+        .arg_presets
+        .iter()
+        .find(|p| p.name == preset_name)
+        .cloned()
+        .ok_or_else(|| {
+            CommandError::new(
+                ErrorCode::NotFound,
+                format!(
+                    "Unknown argument preset '{}' for {}",
+                    preset_name, registered_app.name
+                ),
+            )
+        })
+}
+
+// アプリの引数のプリセットの一覧を取得
+#[tauri::command]
+fn get_arg_presets(app: AppHandle, app_id: String) -> Result<Vec<ArgPreset>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    config
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .map(|a| a.arg_presets.clone())
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))
+}
+
+// 同じアプリを短い間隔で続けて起動しようとした場合は拒否する（ダブルクリック・ホットキーの誤作動対策）
+// 既に起動しているインスタンスを扱う prevent_duplicate とは別に、起動の呼び出しそのものを間引く
+fn debounce_launch(state: &AppState, app_id: &str) -> Result<(), LaunchError> {
+    let window = Duration::from_millis(lock_config(state).launch_debounce_ms);
+    if window.is_zero() {
+        return Ok(());
+    }
+    let now = Instant::now();
+    let mut recent_launches = state.recent_launches.lock_or_recover();
+    if let Some(last) = recent_launches.get(app_id) {
+        let elapsed = now.duration_since(*last);
+        if elapsed < window {
+            info!(
+                "Ignored launch of app_id={} ({}ms after the previous launch)",
+                app_id,
+                elapsed.as_millis()
+            );
+            return Err(LaunchError::Debounced(format!(
+                "Launch ignored: the app was launched {}ms ago",
+                elapsed.as_millis()
+            )));
+        }
+    }
+    recent_launches.retain(|_, last| now.duration_since(*last) < window);
+    recent_launches.insert(app_id.to_string(), now);
+    Ok(())
+}
+
+// 引数の {name} を resolve が返す値で置き換える（{{・}} は波かっこそのもの）
+// 値が返されなかったプレースホルダーがある場合はエラー（波かっこのまま起動しないように）
+fn substitute_placeholders(
+    arguments: &str,
+    mut resolve: impl FnMut(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(arguments.len());
+    let mut unresolved = Vec::new();
+    let mut rest = arguments;
+    while let Some(index) = rest.find(['{', '}']) {
+        result.push_str(&rest[..index]);
+        let after = &rest[index + 1..];
+        // {{・}} は波かっこそのもの
+        if after.starts_with(&rest[index..index + 1]) {
+            result.push_str(&rest[index..index + 1]);
+            rest = &after[1..];
+            continue;
+        }
+        // 展開されなかった ${VAR} や名前でない波かっこはそのまま残す
+        let is_open = rest[index..].starts_with('{') && !result.ends_with('$');
+        let name_end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'));
+        let name = match name_end {
+            Some(end) if is_open && end > 0 && after[end..].starts_with('}') => &after[..end],
+            _ => {
+                result.push_str(&rest[index..index + 1]);
+                rest = after;
+                continue;
+            }
+        };
+        match resolve(name) {
+            Some(value) => result.push_str(&value),
+            None => unresolved.push(format!("{{{}}}", name)),
+        }
+        rest = &after[name.len() + 1..];
+    }
+    result.push_str(rest);
+
+    if unresolved.is_empty() {
+        Ok(result)
+    } else {
+        Err(format!(
+            "Unresolved placeholder(s) in arguments: {}",
+            unresolved.join(", ")
+        ))
+    }
+}
+
+// 起動時に指定された値で引数のプレースホルダーを置き換える
+// （{file} が指定されていなければファイル選択ダイアログで選ぶ）
+async fn resolve_placeholders(
+    app: &AppHandle,
+    arguments: &str,
+    mut context: HashMap<String, String>,
+) -> Result<String, String> {
+    let mut names = Vec::new();
+    substitute_placeholders(arguments, |name| {
+        names.push(name.to_string());
+        Some(String::new())
+    })?;
+
+    if names.iter().any(|name| name == "file") && !context.contains_key("file") {
+        let app_handle = app.clone();
+        let file = tauri::async_runtime::spawn_blocking(move || {
+            use tauri_plugin_dialog::DialogExt;
+            app_handle.dialog().file().blocking_pick_file()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "File selection was cancelled".to_string())?;
+        context.insert("file".to_string(), file.to_string());
+    }
+
+    // 値は引数の文字列にそのまま入るため、登録時の引数と同じ確認をする
+    for name in &names {
+        if let Some(value) = context.get(name) {
+            let label = format!("Placeholder {{{}}}", name);
+            reject_control_characters(&label, value)?;
+            if looks_like_command_injection(value) {
+                return Err(format!(
+                    "{} contains characters that could be interpreted as a command: {}",
+                    label, value
+                ));
+            }
+        }
+    }
+    substitute_placeholders(arguments, |name| context.get(name).cloned())
+}
+
+// 起動前・起動後のどちらのコマンドか
+#[derive(Clone, Copy)]
+enum HookStage {
+    PreLaunch,
+    PostLaunch,
+}
+
+// 設定されていれば起動前・起動後のコマンドを実行
+// 失敗をエラーとして扱わない設定の場合は警告を記録して Ok を返す
+async fn run_hook(
+    app: &AppHandle,
+    registered_app: Option<&RegisteredApp>,
+    stage: HookStage,
+) -> Result<(), String> {
+    let Some(registered_app) = registered_app else {
+        return Ok(());
+    };
+    let (label, hook) = match stage {
+        HookStage::PreLaunch => ("pre-launch hook", &registered_app.pre_launch),
+        HookStage::PostLaunch => ("post-launch hook", &registered_app.post_launch),
+    };
+    let Some(hook) = hook.as_ref().filter(|h| !h.trim().is_empty()).cloned() else {
+        return Ok(());
+    };
+
+    let app_handle = app.clone();
+    let hook_app = registered_app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        run_launch_hook(&app_handle, &hook_app, label, &hook)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|result| result);
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if registered_app.hooks_required => {
+            error!("The {} of {} failed: {}", label, registered_app.name, e);
+            Err(format!("The {} failed: {}", label, e))
+        }
+        Err(e) => {
+            warn!(
+                "The {} of {} failed, continuing: {}",
+                label, registered_app.name, e
+            );
+            Ok(())
+        }
+    }
+}
+
+// コマンドを実行して出力を取得（制限時間を超えた場合はプロセスを強制終了してエラーを返す）
+#[cfg(target_os = "windows")]
+fn output_with_timeout(
+    mut command: Command,
+    timeout: Duration,
+) -> Result<std::process::Output, String> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    // パイプが詰まらないよう出力は別スレッドで読み続ける
+    let read_all = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    };
+    let stdout_reader = read_all(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr_reader = read_all(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let started = std::time::Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("timed out after {} seconds", timeout.as_secs()));
+        }
+        std::thread::sleep(LAUNCH_POLL_INTERVAL);
+    };
+
+    Ok(std::process::Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+// 起動回数と最終起動日時を更新して保存
+fn record_launch(app: &AppHandle, app_id: &str) {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    let Some(app_entry) = config.registered_apps.iter_mut().find(|a| a.id == app_id) else {
+        return;
+    };
+    app_entry.launch_count += 1;
+    app_entry.last_launched = Some(chrono::Local::now().to_rfc3339());
+
+    let once_per_boot = app_entry.launch_once_per_boot;
+
+    if let Err(e) = write_config(app, &config, false) {
+        error!("Failed to save launch statistics for {}: {}", app_id, e);
+    }
+    drop(config);
+
+    // OSの起動ごとに1回だけ起動するアプリは、ランチャーを起動し直しても分かるよう記録する
+    if once_per_boot {
+        state
+            .launched_this_boot
+            .lock_or_recover()
+            .insert(app_id.to_string());
+        save_runtime_state(app);
+    }
+}
+
+// 起動の履歴のファイルのパス
+fn launch_history_path(app: &AppHandle) -> PathBuf {
+    get_config_dir(app).join(HISTORY_FILE_NAME)
+}
+
+// 起動の成功・失敗を履歴に記録（記録に失敗しても起動の結果には影響させない）
+fn record_launch_event(app: &AppHandle, app_id: &str, name: &str, result: Result<(), &str>) {
+    let event = LaunchEvent {
+        app_id: app_id.to_string(),
+        name: name.to_string(),
+        timestamp: chrono::Local::now().to_rfc3339(),
+        success: result.is_ok(),
+        error: result.err().map(str::to_string),
+    };
+    if let Err(e) = history::append(&launch_history_path(app), event) {
+        warn!("Failed to record launch history for {}: {}", app_id, e);
+    }
+}
+
+// 起動の履歴を新しい順に最大 limit 件取得
+#[tauri::command]
+fn get_launch_history(app: AppHandle, limit: usize) -> Result<Vec<LaunchEvent>, CommandError> {
+    let mut events = history::read(&launch_history_path(&app));
+    events.reverse();
+    events.truncate(limit);
+    Ok(events)
+}
+
+// 起動の履歴を消去
+#[tauri::command]
+fn clear_launch_history(app: AppHandle) -> Result<(), CommandError> {
+    history::clear(&launch_history_path(&app)).map_err(CommandError::from)
+}
+
+// アプリケーションの起動回数と最終起動日時を取得
+#[tauri::command]
+fn get_app_stats(app: AppHandle, app_id: String) -> Result<(u64, Option<String>), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    config
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .map(|a| (a.launch_count, a.last_launched.clone()))
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))
+}
+
+// 起動するプロセスの優先度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessPriority {
+    Low,
+    Normal,
+    High,
+}
+
+impl ProcessPriority {
+    // 設定の文字列から優先度を求める（未指定ならOSの既定、不正な値は警告して無視）
+    fn from_setting(priority: Option<&str>) -> Option<Self> {
+        let priority = priority.map(str::trim).filter(|p| !p.is_empty())?;
+        match priority.to_lowercase().as_str() {
+            "low" => Some(Self::Low),
+            "normal" => Some(Self::Normal),
+            "high" => Some(Self::High),
+            _ => {
+                warn!("Ignoring invalid priority '{}'", priority);
+                None
+            }
+        }
+    }
+
+    // PowerShell の PriorityClass の値
+    fn priority_class(self) -> &'static str {
+        match self {
+            Self::Low => "BelowNormal",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    // CreateProcess の優先度クラスのフラグ
+    #[cfg(target_os = "windows")]
+    fn creation_flag(self) -> u32 {
+        match self {
+            Self::Low => 0x0000_4000,    // BELOW_NORMAL_PRIORITY_CLASS
+            Self::Normal => 0x0000_0020, // NORMAL_PRIORITY_CLASS
+            Self::High => 0x0000_0080,   // HIGH_PRIORITY_CLASS
+        }
+    }
+
+    // nice 値
+    fn nice_value(self) -> i32 {
+        match self {
+            Self::Low => 10,
+            Self::Normal => 0,
+            Self::High => -10,
+        }
+    }
+}
+
+// 起動したプロセスの nice 値を設定（優先度を上げるには権限が必要なため、失敗しても警告のみ）
+#[cfg(not(target_os = "windows"))]
+fn apply_priority(pid: u32, priority: ProcessPriority) {
+    let result =
+        unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, priority.nice_value()) };
+    if result != 0 {
+        warn!(
+            "Failed to set priority {:?} for pid {}: {}",
+            priority,
+            pid,
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+// 指定されたコアのうち、このマシンに存在するものだけを返す（存在しないコアは警告して無視する）
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn valid_cpu_cores(cores: &[usize]) -> Vec<usize> {
+    let core_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    // Windowsのアフィニティマスクは usize のビット数までしか指定できない
+    #[cfg(target_os = "windows")]
+    let core_count = core_count.min(usize::BITS as usize);
+    let (valid, invalid): (Vec<usize>, Vec<usize>) =
+        cores.iter().partition(|&&core| core < core_count);
+    if !invalid.is_empty() {
+        warn!(
+            "Ignoring CPU cores {:?} (this machine has {} cores)",
+            invalid, core_count
+        );
+    }
+    valid
+}
+
+// 起動したプロセスを指定したコアで実行させる（失敗しても警告のみ）
+#[cfg(target_os = "windows")]
+fn apply_cpu_affinity(pid: u32, cores: &[usize]) {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, SetProcessAffinityMask, PROCESS_QUERY_LIMITED_INFORMATION,
+        PROCESS_SET_INFORMATION,
+    };
+
+    let cores = valid_cpu_cores(cores);
+    if cores.is_empty() {
+        return;
+    }
+    let mask = cores.iter().fold(0usize, |mask, &core| mask | (1 << core));
+    let result = unsafe {
+        OpenProcess(
+            PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION,
+            false,
+            pid,
+        )
+    }
+    .and_then(|process| {
+        let result = unsafe { SetProcessAffinityMask(process, mask) };
+        unsafe {
+            let _ = CloseHandle(process);
+        }
+        result
+    });
+    match result {
+        Ok(()) => info!("Set CPU affinity {:?} for pid {}", cores, pid),
+        Err(e) => warn!("Failed to set CPU affinity for pid {}: {}", pid, e),
+    }
+}
+
+// 起動したプロセスを指定したコアで実行させる（失敗しても警告のみ）
+#[cfg(target_os = "linux")]
+fn apply_cpu_affinity(pid: u32, cores: &[usize]) {
+    let cores = valid_cpu_cores(cores);
+    if cores.is_empty() {
+        return;
+    }
+    let result = unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &core in &cores {
+            libc::CPU_SET(core, &mut set);
+        }
+        libc::sched_setaffinity(
+            pid as libc::pid_t,
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        )
+    };
+    if result == 0 {
+        info!("Set CPU affinity {:?} for pid {}", cores, pid);
+    } else {
+        warn!(
+            "Failed to set CPU affinity for pid {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+// macOSにはプロセスのCPUアフィニティを指定する安定したAPIがないため何もしない
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn apply_cpu_affinity(pid: u32, cores: &[usize]) {
+    info!(
+        "CPU affinity {:?} is not supported on this platform; ignored for pid {}",
+        cores, pid
+    );
+}
+
+// 別のユーザーとして実行する場合にパスワードを PowerShell へ渡す環境変数
+// （コマンドラインは他のプロセスから見えるため、パスワードをコマンドに書かない）
+const RUN_AS_PASSWORD_ENV: &str = "AJIPONZU_RUN_AS_PASSWORD";
+
+// Start-Process で起動するPowerShellコマンドを組み立て（track_pid の場合はプロセスIDを出力する）
+// run_as_user の場合は RUN_AS_PASSWORD_ENV のパスワードで -Credential を指定する
+fn build_powershell_launch_command(spec: &LaunchSpec, track_pid: bool) -> String {
+    // 環境変数はStart-Processの前にPowerShellのプロセス環境へ設定し、子プロセスに継承させる
+    // （キー名に記号を含む場合も扱えるようSetEnvironmentVariableを使う）
+    let mut command: String = spec
+        .env
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "[Environment]::SetEnvironmentVariable({}, {}, 'Process'); ",
+                quote_powershell(key),
+                quote_powershell(value)
+            )
+        })
+        .collect();
+
+    // パスワードは資格情報にした後で環境変数から消し、起動するプロセスに継承させない
+    if let Some(user) = spec.run_as_user.as_deref() {
+        command.push_str(&format!(
+            "$credential = New-Object System.Management.Automation.PSCredential({}, (ConvertTo-SecureString $env:{} -AsPlainText -Force)); Remove-Item Env:{}; ",
+            quote_powershell(user),
+            RUN_AS_PASSWORD_ENV,
+            RUN_AS_PASSWORD_ENV
+        ));
+    }
+
+    // 優先度を設定する場合も起動したプロセスを受け取る
+    let pass_thru = track_pid || spec.priority.is_some();
+    if pass_thru {
+        command.push_str("$process = ");
+    }
+    command.push_str(&format!(
+        "Start-Process -FilePath {}",
+        quote_powershell(&spec.path)
+    ));
+    if let Some(quoted_args) = build_powershell_argument_list(&spec.arguments) {
+        command.push_str(&format!(" -ArgumentList {}", quoted_args));
+    }
+    if pass_thru {
+        command.push_str(" -PassThru");
+    }
+
+    // 作業ディレクトリ・ログ出力などの共通オプション
+    if !spec.working_directory.is_empty() {
+        command.push_str(&format!(
+            " -WorkingDirectory {}",
+            quote_powershell(&spec.working_directory)
+        ));
+    }
+    if spec.run_as_user.is_some() {
+        command.push_str(" -Credential $credential");
+    }
+    if spec.run_as_admin {
+        // 管理者として実行（-Verb RunAs は標準出力のリダイレクトと併用できない）
+        command.push_str(" -Verb RunAs");
+    } else if let Some((log_path, err_log_path)) = &spec.log_paths {
+        command.push_str(&format!(
+            " -RedirectStandardOutput {} -RedirectStandardError {}",
+            quote_powershell(&log_path.to_string_lossy()),
+            quote_powershell(&err_log_path.to_string_lossy())
+        ));
+    }
+
+    if let Some(priority) = spec.priority {
+        // 昇格したプロセスなどで設定できない場合も起動は成功とする
+        command.push_str(&format!(
+            "; try {{ $process.PriorityClass = '{}' }} catch {{ Write-Warning $_ }}",
+            priority.priority_class()
+        ));
+    }
+    if track_pid {
+        command.push_str("; Write-Output $process.Id");
+    }
+    command
+}
+
+// PowerShellを介さずに起動するコマンドを組み立て（標準出力のリダイレクトは呼び出し側で設定する）
+fn build_direct_launch_command(
+    path: &str,
+    arguments: &str,
+    env: &HashMap<String, String>,
+    working_directory: &str,
+) -> Command {
+    let mut cmd = Command::new(path);
+    cmd.args(parse_arguments(arguments));
+    if !working_directory.is_empty() {
+        cmd.current_dir(working_directory);
+    }
+    cmd.envs(env);
+    cmd
+}
+
+// CreateProcessW で直接起動できる実行ファイルか（それ以外はShellExecuteを使うStart-Processで開く）
+#[cfg(target_os = "windows")]
+fn is_native_launchable(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| e == "exe" || e == "com")
+}
+
+// CreateProcessW に渡すコマンドライン（実行ファイルと引数を1つずつクォートして連結）
+#[cfg(target_os = "windows")]
+fn build_windows_command_line(path: &str, arguments: &str) -> String {
+    std::iter::once(path.to_string())
+        .chain(parse_arguments(arguments))
+        .map(|arg| quote_windows_arg(&arg))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// CreateProcessW に渡す環境変数ブロック（ランチャーの環境に env を上書きし、キーの大文字小文字を無視して並べる）
+#[cfg(target_os = "windows")]
+fn build_environment_block(env: &HashMap<String, String>) -> Vec<u16> {
+    use std::os::windows::ffi::OsStrExt;
+
+    let mut vars: Vec<(std::ffi::OsString, std::ffi::OsString)> = std::env::vars_os()
+        .filter(|(key, _)| {
+            !env.keys()
+                .any(|k| k.eq_ignore_ascii_case(&key.to_string_lossy()))
+        })
+        .collect();
+    vars.extend(env.iter().map(|(k, v)| (k.into(), v.into())));
+    vars.sort_by_key(|(key, _)| key.to_string_lossy().to_uppercase());
+
+    let mut block = Vec::new();
+    for (key, value) in vars {
+        block.extend(key.encode_wide());
+        block.push('=' as u16);
+        block.extend(value.encode_wide());
+        block.push(0);
+    }
+    block.push(0);
+    block
+}
+
+// PowerShellを介さずに CreateProcessW で起動し、プロセスIDとプロセスハンドルを返す
+// （Start-Process の起動を待つ数百ミリ秒と、コンソールが一瞬表示される問題を避けるため）
+#[cfg(target_os = "windows")]
+fn spawn_native(
+    path: &str,
+    arguments: &str,
+    env: &HashMap<String, String>,
+    working_directory: &str,
+    log_paths: Option<&(PathBuf, PathBuf)>,
+    priority: Option<ProcessPriority>,
+    detached: bool,
+) -> Result<(u32, std::os::windows::io::OwnedHandle), String> {
+    use std::os::windows::ffi::OsStrExt;
+    use std::os::windows::io::{AsRawHandle, FromRawHandle, OwnedHandle};
+    use windows::core::{PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{
+        CloseHandle, SetHandleInformation, HANDLE, HANDLE_FLAG_INHERIT,
+    };
+    use windows::Win32::System::Threading::{
+        CreateProcessW, CREATE_NEW_PROCESS_GROUP, CREATE_NO_WINDOW, CREATE_UNICODE_ENVIRONMENT,
+        DETACHED_PROCESS, PROCESS_CREATION_FLAGS, PROCESS_INFORMATION, STARTF_USESTDHANDLES,
+        STARTUPINFOW,
+    };
+
+    let to_wide = |value: &str| -> Vec<u16> {
+        std::ffi::OsStr::new(value)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    };
+    let os_error = |e: windows::core::Error| {
+        std::io::Error::from_raw_os_error(e.code().0 & 0xFFFF).to_string()
+    };
+
+    // CreateProcessW はコマンドラインを書き換えることがあるため可変のバッファで渡す
+    let mut command_line = to_wide(&build_windows_command_line(path, arguments));
+    let current_directory = (!working_directory.is_empty()).then(|| to_wide(working_directory));
+    let environment = (!env.is_empty()).then(|| build_environment_block(env));
+
+    let mut flags = CREATE_UNICODE_ENVIRONMENT
+        | PROCESS_CREATION_FLAGS(priority.map_or(0, ProcessPriority::creation_flag));
+    if detached {
+        flags |= DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP;
+    }
+    let mut startup_info = STARTUPINFOW {
+        cb: std::mem::size_of::<STARTUPINFOW>() as u32,
+        ..Default::default()
+    };
+
+    // ログ出力が有効な場合は標準出力・標準エラーをログファイルに継承させる（コンソールは表示しない）
+    let log_files = match log_paths {
+        Some((log_path, err_log_path)) => {
+            let files = (open_app_log(log_path)?, open_app_log(err_log_path)?);
+            for file in [&files.0, &files.1] {
+                unsafe {
+                    SetHandleInformation(
+                        HANDLE(file.as_raw_handle()),
+                        HANDLE_FLAG_INHERIT.0,
+                        HANDLE_FLAG_INHERIT,
+                    )
+                }
+                .map_err(os_error)?;
+            }
+            startup_info.dwFlags = STARTF_USESTDHANDLES;
+            startup_info.hStdOutput = HANDLE(files.0.as_raw_handle());
+            startup_info.hStdError = HANDLE(files.1.as_raw_handle());
+            flags |= CREATE_NO_WINDOW;
+            Some(files)
+        }
+        None => None,
+    };
+
+    let mut process_info = PROCESS_INFORMATION::default();
+    unsafe {
+        CreateProcessW(
+            PCWSTR::null(),
+            Some(PWSTR(command_line.as_mut_ptr())),
+            None,
+            None,
+            log_files.is_some(),
+            flags,
+            environment
+                .as_ref()
+                .map(|block| block.as_ptr() as *const std::ffi::c_void),
+            current_directory
+                .as_ref()
+                .map_or(PCWSTR::null(), |dir| PCWSTR(dir.as_ptr())),
+            &startup_info,
+            &mut process_info,
+        )
+    }
+    .map_err(os_error)?;
+
+    // スレッドのハンドルは使わないので閉じ、プロセスのハンドルは終了の待機に使う
+    unsafe {
+        let _ = CloseHandle(process_info.hThread);
+    }
+    let process = unsafe { OwnedHandle::from_raw_handle(process_info.hProcess.0) };
+    Ok((process_info.dwProcessId, process))
+}
+
+// シェルで実行するコマンドを組み立て（パスと引数をつなげた文字列をそのままシェルに渡す）
+// シェルのメタ文字（| && > % $ など）が解釈されるため、信頼できるコマンドだけを登録すること
+fn build_shell_launch_command(
+    path: &str,
+    arguments: &str,
+    env: &HashMap<String, String>,
+    working_directory: &str,
+) -> Command {
+    let command_line = format!("{} {}", path.trim(), arguments.trim())
+        .trim()
+        .to_string();
+
+    // cmd の引用符の扱いに左右されないよう /S で外側の引用符だけを取り除かせる
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("cmd");
+        cmd.raw_arg(format!("/S /C \"{}\"", command_line));
+        cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(&command_line);
+        cmd
+    };
+
+    if !working_directory.is_empty() {
+        cmd.current_dir(working_directory);
+    }
+    cmd.envs(env);
+    cmd
+}
+
+// 試験起動の結果
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TestResult {
+    // プロセスを起動できたか
+    pub started: bool,
+    pub pid: Option<u32>,
+    // 様子を見ている間に終了したか（終了していなければ試験後に停止する）
+    pub exited: bool,
+    pub exit_code: Option<i32>,
+    // 起動直後の標準エラー
+    pub stderr: String,
+    // 起動できなかった場合のエラー
+    pub error: Option<String>,
+}
+
+// 登録せずに起動して少し様子を見て、結果を返す（プロセス管理テーブル・設定には追加せず、
+// 動作し続けている場合は停止する）
+#[tauri::command]
+async fn test_launch(
+    app: AppHandle,
+    path: String,
+    arguments: String,
+    working_directory: String,
+) -> Result<TestResult, CommandError> {
+    let path = resolve_relative_path(&path);
+    if is_url_path(&path) {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "URLs cannot be test-launched",
+        ));
+    }
+    let arguments = secret::resolve(&expand_env(&arguments))?;
+    let (path, arguments) = resolve_launch_target(&path, &arguments);
+    if let Err(e) = validate_app_path(&path) {
+        return Ok(TestResult {
+            error: Some(e),
+            ..Default::default()
+        });
+    }
+
+    let spec = LaunchSpec {
+        app_id: "test-launch".to_string(),
+        path,
+        arguments,
+        working_directory: expand_env(working_directory.trim()),
+        capture_output: true,
+        ..Default::default()
+    };
+    let launcher = app.state::<AppState>().launcher.clone();
+    tauri::async_runtime::spawn_blocking(move || run_test_launch(launcher.as_ref(), &spec))
+        .await
+        .map_err(|e| CommandError::from(e.to_string()))
+}
+
+fn run_test_launch(launcher: &dyn ProcessLauncher, spec: &LaunchSpec) -> TestResult {
+    info!(
+        "{}",
+        secret::redact(&format!("Test launching {} {}", spec.path, spec.arguments))
+    );
+    let spawned = match launcher.spawn(spec) {
+        Ok(spawned) => spawned,
+        Err(e) => {
+            return TestResult {
+                error: Some(e.message().to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    // 標準出力は読み捨て、標準エラーは先頭だけ残す（パイプが詰まって止まらないように読み続ける）
+    let stderr_buffer = Arc::new(Mutex::new(Vec::new()));
+    if let Some((mut stdout, mut stderr)) = spawned.output {
+        std::thread::spawn(move || {
+            let _ = std::io::copy(&mut stdout, &mut std::io::sink());
+        });
+        let buffer = stderr_buffer.clone();
+        std::thread::spawn(move || {
+            let mut chunk = [0u8; 1024];
+            while let Ok(read) = stderr.read(&mut chunk) {
+                if read == 0 {
+                    break;
+                }
+                let mut buffer = buffer.lock_or_recover();
+                let room = TEST_LAUNCH_OUTPUT_LIMIT.saturating_sub(buffer.len());
+                buffer.extend_from_slice(&chunk[..read.min(room)]);
+            }
+        });
+    }
+
+    // 終了を待つ処理は別スレッドで行い、一定時間内に終了したかを確認する
+    let (tx, rx) = mpsc::channel();
+    if let Some(exit) = spawned.exit {
+        std::thread::spawn(move || {
+            let _ = tx.send(exit());
+        });
+    }
+    let mut result = TestResult {
+        started: true,
+        pid: spawned.pid,
+        ..Default::default()
+    };
+    match rx.recv_timeout(TEST_LAUNCH_WAIT) {
+        Ok(exit_code) => {
+            result.exited = true;
+            result.exit_code = exit_code;
+        }
+        Err(_) => {
+            if let Some(pid) = spawned.pid {
+                if let Err(e) = launcher.kill(KillTarget::ProcessTree(pid)) {
+                    warn!("Failed to stop test-launched process {}: {}", pid, e);
+                }
+            }
+        }
+    }
+
+    // 終了直前の出力を読み切るまで少し待つ
+    std::thread::sleep(Duration::from_millis(100));
+    result.stderr = String::from_utf8_lossy(&stderr_buffer.lock_or_recover()).into_owned();
+    info!(
+        "Test launch of {} finished: pid={:?} exited={} exit_code={:?}",
+        spec.path, result.pid, result.exited, result.exit_code
+    );
+    result
+}
+
+// 実際には起動せず、launch_application が実行するコマンドを文字列で返す
+#[tauri::command]
+fn preview_launch_command(app: AppHandle, app_id: String) -> Result<String, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let registered_app = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .cloned()
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+
+    let path = expand_env(&registered_app.path);
+    let arguments = expand_env(&registered_app.arguments);
+    if is_url_path(&path) {
+        return Ok(format!("Open URL: {}", path.trim()));
+    }
+    let working_directory = registered_app.working_directory.trim();
+    if registered_app.use_shell {
+        let cmd =
+            build_shell_launch_command(&path, &arguments, &registered_app.env, working_directory);
+        return Ok(format!("{:?} (shell, PID tracking is best-effort)", cmd));
+    }
+    let path = registered_executable_path(&registered_app);
+    let (path, arguments) = resolve_launch_target(&path, &arguments);
+    let run_as_admin = runs_as_admin(&registered_app);
+    let run_as_user = runs_as_user(&registered_app).filter(|_| cfg!(target_os = "windows"));
+
+    // ログのローテーションは行わずパスだけを求める
+    let priority = ProcessPriority::from_setting(registered_app.priority.as_deref());
+    let log_paths = if registered_app.log_output {
+        let log_path = app_log_file_path(&app, &app_id)?;
+        let err_log_path = app_err_log_file_path(&log_path);
+        Some((log_path, err_log_path))
+    } else {
+        None
+    };
+
+    // 出力を取得する場合は直接起動する（管理者・別のユーザーとして実行する場合を除く）
+    if registered_app.capture_output
+        && !(cfg!(target_os = "windows") && (run_as_admin || run_as_user.is_some()))
+    {
+        let cmd =
+            build_direct_launch_command(&path, &arguments, &registered_app.env, working_directory);
+        let mut preview = format!("{:?} (capturing output)", cmd);
+        if let Some(priority) = priority {
+            preview.push_str(&format!(" (priority: {:?})", priority));
+        }
+        if let Some((log_path, err_log_path)) = &log_paths {
+            preview.push_str(&format!(
+                " >> {:?} 2>> {:?}",
+                log_path.display().to_string(),
+                err_log_path.display().to_string()
+            ));
+        }
+        return Ok(preview);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if !run_as_admin && run_as_user.is_none() && is_native_launchable(&path) {
+            let mut preview = format!(
+                "CreateProcess: {}",
+                build_windows_command_line(&path, &arguments)
+            );
+            if !registered_app.env.is_empty() {
+                preview.push_str(&format!(" (env: {:?})", registered_app.env));
+            }
+            if !working_directory.is_empty() {
+                preview.push_str(&format!(" (cwd: {})", working_directory));
+            }
+            if let Some(priority) = priority {
+                preview.push_str(&format!(" (priority: {:?})", priority));
+            }
+            if let Some((log_path, err_log_path)) = &log_paths {
+                preview.push_str(&format!(
+                    " >> {:?} 2>> {:?}",
+                    log_path.display().to_string(),
+                    err_log_path.display().to_string()
+                ));
+            }
+            return Ok(preview);
+        }
+
+        let spec = LaunchSpec {
+            app_id,
+            path,
+            arguments,
+            env: registered_app.env.clone(),
+            working_directory: working_directory.to_string(),
+            log_paths,
+            priority,
+            run_as_admin,
+            run_as_user: run_as_user.map(str::to_string),
+            ..Default::default()
+        };
+        let track_pid = !spec.run_as_admin && spec.run_as_user.is_none();
+        let powershell_command = build_powershell_launch_command(&spec, track_pid);
+        Ok(format!(
+            "powershell -WindowStyle Hidden -Command {}",
+            powershell_command
+        ))
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let cmd =
+            build_direct_launch_command(&path, &arguments, &registered_app.env, working_directory);
+        let mut preview = format!("{:?}", cmd);
+        if let Some(priority) = priority {
+            preview.push_str(&format!(" (priority: {:?})", priority));
+        }
+        if let Some((log_path, err_log_path)) = &log_paths {
+            preview.push_str(&format!(
+                " >> {:?} 2>> {:?}",
+                log_path.display().to_string(),
+                err_log_path.display().to_string()
+            ));
+        }
+        Ok(preview)
+    }
+}
+
+// 有効なアプリの起動コマンドを PowerShell・cmd・bash のスクリプトとして返す（読み取りのみ。表示順に起動する）
+// 秘密の値は書き出さないよう、{secret:NAME} は登録したままにする
+#[tauri::command]
+fn export_launch_script(app: AppHandle, shell: String) -> Result<String, CommandError> {
+    let shell = script::ScriptShell::parse(&shell)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    let state: tauri::State<AppState> = app.state();
+    let mut registered_apps: Vec<RegisteredApp> = lock_config(&state)
+        .registered_apps
+        .iter()
+        .filter(|a| a.enabled)
+        .cloned()
+        .collect();
+    registered_apps.sort_by_key(|a| a.sort_order);
+
+    let entries: Vec<script::ScriptEntry> = registered_apps
+        .iter()
+        .map(|registered_app| {
+            let path = expand_env(&registered_app.path);
+            let arguments = expand_env(&registered_app.arguments);
+            let is_url = is_url_path(&path);
+            let (path, arguments) = if is_url || registered_app.use_shell {
+                (path, arguments)
+            } else {
+                resolve_launch_target(&registered_executable_path(registered_app), &arguments)
+            };
+            script::ScriptEntry {
+                name: registered_app.name.clone(),
+                path,
+                arguments,
+                env: registered_app.env.clone(),
+                working_directory: expand_env(registered_app.working_directory.trim()),
+                use_shell: registered_app.use_shell,
+                is_url,
+                run_as_admin: !is_url && runs_as_admin(registered_app),
+                run_as_user: runs_as_user(registered_app)
+                    .filter(|_| !is_url && !registered_app.use_shell)
+                    .map(str::to_string),
+                priority: ProcessPriority::from_setting(registered_app.priority.as_deref()),
+            }
+        })
+        .collect();
+    Ok(script::build(shell, &entries))
+}
+
+// アプリケーションのプロセスを起動し、管理テーブルに記録
+async fn spawn_application(
+    app: AppHandle,
+    app_id: String,
+    path: String,
+    arguments: String,
+) -> Result<Option<u32>, String> {
+    // URL・カスタムプロトコルは既定のハンドラーで開く（プロセスは追跡しない）
+    if is_url_path(&path) {
+        info!("Opening app_id={} url={}", app_id, path.trim());
+        app.opener()
+            .open_url(path.trim(), None::<&str>)
+            .map_err(|e| format!("Failed to open URL: {}", e))?;
+        return Ok(None);
+    }
+
+    // 引数の秘密の値は起動する時点で入れる（設定・起動の記録には残さない）
+    let arguments = secret::resolve(&arguments)?;
+
+    // 登録されたアプリケーションの情報を確認
+    let state: tauri::State<AppState> = app.state();
+    let registered_app = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|app| app.id == app_id)
+        .cloned();
+    let registered_app = registered_app.as_ref();
+    let is_registered_app = registered_app.is_some();
+    let prevent_duplicate = registered_app
+        .map(|app| app.prevent_duplicate)
+        .unwrap_or(false);
+    let log_output = registered_app.map(|app| app.log_output).unwrap_or(false);
+    let log_truncate = registered_app.map(|app| app.log_truncate).unwrap_or(false);
+    let working_directory = registered_app
+        .map(|app| app.working_directory.trim().to_string())
+        .unwrap_or_default();
+    let env = registered_app
+        .map(|app| app.env.clone())
+        .unwrap_or_default();
+    let run_as_admin = registered_app.map(|app| app.run_as_admin).unwrap_or(false);
+    let run_as_user = registered_app.and_then(runs_as_user).map(str::to_string);
+    let capture_output = registered_app
+        .map(|app| app.capture_output)
+        .unwrap_or(false);
+    let priority =
+        registered_app.and_then(|app| ProcessPriority::from_setting(app.priority.as_deref()));
+    let use_shell = registered_app.map(|app| app.use_shell).unwrap_or(false);
+    let cpu_affinity = registered_app.and_then(|app| app.cpu_affinity.clone());
+    let detached = registered_app.is_some_and(|app| app.detached);
+
+    // ワイルドカードのパスは起動する時点で一致するファイルに解決し、相対パスは基準ディレクトリから解決する
+    let path = if use_shell {
+        path
+    } else if is_glob_path(&path) {
+        resolve_path_glob(
+            &path,
+            registered_app.map_or_else(PathGlobPick::default, |app| app.path_glob_pick),
+        )?
+    } else {
+        resolve_relative_path(&path)
+    };
+
+    // ショートカットはリンク先を直接起動（解決できない場合はショートカットのまま起動）
+    // シェルで実行する場合はパスと引数をそのままコマンド文字列として使う
+    let (path, arguments) = if use_shell {
+        (path, arguments)
+    } else {
+        resolve_launch_target(&path, &arguments)
+    };
+    // 実行ファイルが差し替えられていないか確認する（一致しない場合は起動しない）
+    if let Some(expected_sha256) = registered_app
+        .and_then(|app| app.expected_sha256.as_deref())
+        .filter(|h| !h.trim().is_empty())
+    {
+        if use_shell {
+            warn!(
+                "expected_sha256 is ignored for app_id={} because it is launched through the shell",
+                app_id
+            );
+        } else {
+            let hash_path = path.clone();
+            let expected_sha256 = expected_sha256.to_string();
+            tauri::async_runtime::spawn_blocking(move || {
+                verify_sha256(&hash_path, &expected_sha256)
+            })
+            .await
+            .map_err(|e| e.to_string())??;
+        }
+    }
+    // マニフェストが管理者権限を要求する実行ファイルだけ管理者として実行する
+    let auto_elevated = !run_as_admin
+        && run_as_user.is_none()
+        && cfg!(target_os = "windows")
+        && !use_shell
+        && registered_app.is_some_and(|app| app.auto_elevate)
+        && executable::requires_administrator(&path);
+    if auto_elevated {
+        info!(
+            "{} requires administrator privileges; launching app_id={} elevated",
+            path, app_id
+        );
+    }
+    let run_as_admin = run_as_admin || auto_elevated;
+    if use_shell && run_as_admin && cfg!(target_os = "windows") {
+        warn!(
+            "run_as_admin is not supported with use_shell; launching {} through the shell without elevation",
+            app_id
+        );
+    }
+    let run_as_admin = run_as_admin && !use_shell;
+
+    #[cfg(not(target_os = "windows"))]
+    if run_as_admin {
+        warn!(
+            "run_as_admin is only supported on Windows; launching {} normally",
+            app_id
+        );
+    }
+
+    // 別のユーザーとして実行するのは Windows でシェルを使わない場合のみ
+    if run_as_user.is_some() && !cfg!(target_os = "windows") {
+        warn!(
+            "run_as_user is only supported on Windows; launching {} as the current user",
+            app_id
+        );
+    } else if run_as_user.is_some() && use_shell {
+        warn!(
+            "run_as_user is not supported with use_shell; launching {} through the shell as the current user",
+            app_id
+        );
+    }
+    let run_as_user = run_as_user.filter(|_| cfg!(target_os = "windows") && !use_shell);
+
+    // 作業ディレクトリが指定されている場合は存在を確認
+    if !working_directory.is_empty() && !Path::new(&working_directory).is_dir() {
+        return Err(format!(
+            "Working directory does not exist: {}",
+            working_directory
+        ));
+    }
+
+    // ログ出力が有効な場合はログファイルを準備
+    let log_paths = if is_registered_app && log_output {
+        Some(prepare_app_log(&app, &app_id, log_truncate)?)
+    } else {
+        None
+    };
+
+    // 管理者・別のユーザーとして実行する場合は標準出力をパイプで受け取れない
+    if capture_output && cfg!(target_os = "windows") && run_as_admin {
+        warn!(
+            "Output capture is not available when running as administrator: {}",
+            app_id
+        );
+    }
+    if capture_output && run_as_user.is_some() {
+        warn!(
+            "Output capture is not available when running as another user: {}",
+            app_id
+        );
+    }
+    if cfg!(target_os = "windows") && run_as_admin && log_paths.is_some() {
+        warn!(
+            "Output logging is not available when running as administrator: {}",
+            app_id
+        );
+    }
+
+    // 出力を取得する場合はログファイルへの書き込みも転送のスレッドで行う
+    let capture_log_files = match &log_paths {
+        Some((log_path, err_log_path))
+            if capture_output
+                && !(cfg!(target_os = "windows") && run_as_admin)
+                && run_as_user.is_none() =>
+        {
+            Some((open_app_log(log_path)?, open_app_log(err_log_path)?))
+        }
+        _ => None,
+    };
+
+    let spec = LaunchSpec {
+        app_id: app_id.clone(),
+        path,
+        arguments,
+        env,
+        working_directory,
+        log_paths,
+        priority,
+        run_as_admin,
+        run_as_user,
+        capture_output,
+        use_shell,
+        cpu_affinity,
+        detached,
+    };
+
+    // PowerShellの完了待ちなどで処理が止まるため、起動はブロッキング用のスレッドで行う
+    let launcher = state.launcher.clone();
+    let started = Instant::now();
+    let spawned = tauri::async_runtime::spawn_blocking(move || launcher.spawn(&spec))
+        .await
+        .map_err(|e| format!("Failed to launch application: {}", e))?
+        .map_err(|e| e.to_string())?;
+
+    record_spawned_process(&state, &app_id, prevent_duplicate, spawned.pid);
+    save_runtime_state(&app);
+    update_tray_status(&app);
+
+    let Some(pid) = spawned.pid else {
+        info!(
+            "Launched app_id={} (name tracking, no PID tracking) in {:?}",
+            app_id,
+            started.elapsed()
+        );
+        watch_name_marker_exit(&app, app_id);
+        return Ok(None);
+    };
+    info!(
+        "Launched app_id={} pid={}{} in {:?}",
+        app_id,
+        pid,
+        if use_shell {
+            " (shell process, PID tracking is best-effort)"
+        } else {
+            ""
+        },
+        started.elapsed()
+    );
+
+    if let Some((stdout, stderr)) = spawned.output {
+        start_output_capture(&app, &app_id, stdout, stderr, capture_log_files);
+    }
+
+    if let Some(exit) = spawned.exit {
+        watch_exit(&app, app_id, pid, exit);
+    }
+    Ok(Some(pid))
+}
+
+// 起動したプロセスを管理テーブルに記録し、記録したキーを返す
+fn record_spawned_process(
+    state: &AppState,
+    app_id: &str,
+    prevent_duplicate: bool,
+    pid: Option<u32>,
+) -> String {
+    let (process_key, pid) = tracking_entry(app_id, prevent_duplicate, pid);
+    lock_processes(state).insert(process_key.clone(), pid);
+    process_key
+}
+
+// 管理テーブルに記録するキーとPID
+// 重複起動禁止の場合もPIDを記録し、プロセス名管理のキーで区別する
+// PIDが分からない場合（Windowsの管理者・別のユーザーとしての実行）はプロセス名ベース管理のマーカー（PID 0）にする
+fn tracking_entry(app_id: &str, prevent_duplicate: bool, pid: Option<u32>) -> (String, u32) {
+    match pid {
+        Some(pid) if !prevent_duplicate => (app_id.to_string(), pid),
+        Some(pid) => (format!("{}:name", app_id), pid),
+        None => (format!("{}:name", app_id), 0),
+    }
+}
+
+// 起動してもPIDが分からないアプリか（Windowsで管理者・別のユーザーとして実行する。シェル経由の場合はどちらも使わない）
+fn launches_without_pid(registered_app: &RegisteredApp) -> bool {
+    cfg!(target_os = "windows")
+        && !registered_app.use_shell
+        && (runs_as_admin(registered_app) || runs_as_user(registered_app).is_some())
+}
+
+// ログファイルを追記モードで開く
+fn open_app_log(path: &Path) -> Result<std::fs::File, String> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open log file {}: {}", path.display(), e))
+}
+
+// 起動したプロセスの標準出力・標準エラーをフロントエンドへ転送する
+// （ログ出力が有効な場合はログファイルにも書き込む）
+fn start_output_capture(
+    app: &AppHandle,
+    app_id: &str,
+    stdout: Box<dyn std::io::Read + Send>,
+    stderr: Box<dyn std::io::Read + Send>,
+    log_files: Option<(std::fs::File, std::fs::File)>,
+) {
+    let (log_file, err_log_file) = log_files.unzip();
+
+    // 前回の起動の転送は新しい転送に置き換える
+    let active = Arc::new(AtomicBool::new(true));
+    let state: tauri::State<AppState> = app.state();
+    if let Some(previous) = state
+        .output_captures
+        .lock_or_recover()
+        .insert(app_id.to_string(), active.clone())
+    {
+        previous.store(false, Ordering::Relaxed);
+    }
+    forward_output(app, app_id, "stdout", stdout, &active, log_file);
+    forward_output(app, app_id, "stderr", stderr, &active, err_log_file);
+}
+
+// プロセスの出力1行分としてフロントエンドへ送るイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct ProcessOutputPayload {
+    app_id: String,
+    stream: &'static str,
+    line: String,
+}
+
+// 改行までを読み取り、limit を超えた部分は捨てる（読み取ったバイト数を返し、0なら終端）
+fn read_line_limited<R: BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    limit: usize,
+) -> std::io::Result<usize> {
+    let mut total = 0;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(total);
+        }
+        let (chunk, done) = match available.iter().position(|&b| b == b'\n') {
+            Some(i) => (&available[..=i], true),
+            None => (available, false),
+        };
+        let len = chunk.len();
+        let room = limit.saturating_sub(buf.len());
+        buf.extend_from_slice(&chunk[..len.min(room)]);
+        reader.consume(len);
+        total += len;
+        if done {
+            return Ok(total);
+        }
+    }
+}
+
+// パイプから1行ずつ読み取り、process-output イベントとして転送するスレッドを開始
+// - 転送が多すぎる場合は1秒ごとの上限を超えた行を破棄し、破棄した行数だけ通知する
+// - 転送を停止した後もプロセスが書き込みで止まらないよう読み取りは続ける
+fn forward_output<R: std::io::Read + Send + 'static>(
+    app: &AppHandle,
+    app_id: &str,
+    stream: &'static str,
+    reader: R,
+    active: &Arc<AtomicBool>,
+    mut log_file: Option<std::fs::File>,
+) {
+    let app = app.clone();
+    let app_id = app_id.to_string();
+    let active = active.clone();
+    std::thread::spawn(move || {
+        let emit = |line: String| {
+            let payload = ProcessOutputPayload {
+                app_id: app_id.clone(),
+                stream,
+                line,
+            };
+            if let Err(e) = app.emit("process-output", payload) {
+                error!("Failed to emit process-output: {}", e);
+            }
+        };
+
+        let mut reader = std::io::BufReader::new(reader);
+        let mut buf = Vec::new();
+        let mut window_start = Instant::now();
+        let mut sent = 0;
+        let mut dropped = 0;
+        loop {
+            buf.clear();
+            match read_line_limited(&mut reader, &mut buf, OUTPUT_MAX_LINE_BYTES) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Failed to read {} of {}: {}", stream, app_id, e);
+                    break;
+                }
+            }
+            let line = String::from_utf8_lossy(&buf)
+                .trim_end_matches(['\r', '\n'])
+                .to_string();
+
+            if let Some(file) = log_file.as_mut() {
+                if let Err(e) = writeln!(file, "{}", line) {
+                    warn!("Failed to write {} log of {}: {}", stream, app_id, e);
+                    log_file = None;
+                }
+            }
+
+            if !active.load(Ordering::Relaxed) {
+                continue;
+            }
+            if window_start.elapsed() >= Duration::from_secs(1) {
+                if dropped > 0 {
+                    emit(format!("... {} lines dropped", dropped));
+                    dropped = 0;
+                }
+                window_start = Instant::now();
+                sent = 0;
+            }
+            if sent >= OUTPUT_MAX_LINES_PER_SEC {
+                dropped += 1;
+                continue;
+            }
+            sent += 1;
+            emit(line);
+        }
+
+        if active.load(Ordering::Relaxed) && dropped > 0 {
+            emit(format!("... {} lines dropped", dropped));
+        }
+
+        // 終了したプロセスの転送を管理テーブルから外す（再起動後の転送は残す）
+        let state: tauri::State<AppState> = app.state();
+        let mut captures = state.output_captures.lock_or_recover();
+        if captures
+            .get(&app_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &active))
+        {
+            captures.remove(&app_id);
+        }
+    });
+}
+
+// 出力の転送を停止（プロセスは停止しない）
+#[tauri::command]
+fn stop_output_capture(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let active = state
+        .output_captures
+        .lock_or_recover()
+        .remove(&app_id)
+        .ok_or_else(|| {
+            CommandError::new(ErrorCode::ProcessNotRunning, "Output capture is not active")
+        })?;
+    active.store(false, Ordering::Relaxed);
+    info!("Stopped output capture for app_id={}", app_id);
+    Ok(())
+}
+
+// プロセス終了時にフロントエンドへ送るイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct ProcessExitedPayload {
+    app_id: String,
+    pid: u32,
+    exit_code: Option<i32>,
+    timestamp: String,
+}
+
+// プロセスの終了を管理テーブルに反映し、フロントエンドへ通知
+fn handle_process_exit(app: &AppHandle, app_id: &str, pid: u32, exit_code: Option<i32>) {
+    let state: tauri::State<AppState> = app.state();
+    let mut processes = lock_processes(&state);
+    // 既に再起動されて別のPIDになっている場合は残す
+    for key in [app_id.to_string(), format!("{}:name", app_id)] {
+        if processes.get(&key) == Some(&pid) {
+            processes.remove(&key);
+        }
+    }
+    // 終了を待っている呼び出しに終了コードを渡す（管理テーブルと同じロック内で取り出す）
+    let waiters = state.exit_waiters.lock_or_recover().remove(&pid);
+    drop(processes);
+    save_runtime_state(app);
+    update_tray_status(app);
+    for waiter in waiters.into_iter().flatten() {
+        let _ = waiter.send(exit_code);
+    }
+
+    info!(
+        "Process {} for app_id: {} exited with code {:?}",
+        pid, app_id, exit_code
+    );
+    let payload = ProcessExitedPayload {
+        app_id: app_id.to_string(),
+        pid,
+        exit_code,
+        timestamp: chrono::Local::now().to_rfc3339(),
+    };
+    if let Err(e) = app.emit("process-exited", payload) {
+        error!("Failed to emit process-exited: {}", e);
+    }
+}
+
+// 起動したプロセスの終了を待つスレッドを開始
+fn watch_exit(app: &AppHandle, app_id: String, pid: u32, exit: ExitWaiter) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let exit_code = exit();
+        handle_process_exit(&app, &app_id, pid, exit_code);
+    });
+}
+
+// 自分の子プロセスではないプロセスの終了を定期的に確認するスレッドを開始（終了コードは取得できない）
+#[cfg(not(target_os = "windows"))]
+fn watch_pid_exit(app: &AppHandle, app_id: String, pid: u32) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while is_process_alive(pid) {
+            std::thread::sleep(PROCESS_EXIT_POLL_INTERVAL);
+        }
+        handle_process_exit(&app, &app_id, pid, None);
+    });
+}
+
+// PIDが分からないプロセス名のマーカー（Windowsの管理者実行）は、実行ファイルのパスで探して
+// プロセスがなくなったら終了として扱う（外部から終了された場合に、実行中と判断し続けないように）
+fn watch_name_marker_exit(app: &AppHandle, app_id: String) {
+    // 同じアプリのマーカーを監視するスレッドは1つだけにする
+    static WATCHING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    {
+        let mut watching = WATCHING.lock_or_recover();
+        if watching.contains(&app_id) {
+            return;
+        }
+        watching.push(app_id.clone());
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let process_key = format!("{}:name", app_id);
+        let started = Instant::now();
+        let mut found = false;
+        let exited = loop {
+            std::thread::sleep(NAME_MARKER_POLL_INTERVAL);
+            let state: tauri::State<AppState> = app.state();
+            // 停止した・PIDで管理するようになった場合は監視を終える
+            if lock_processes(&state).get(&process_key) != Some(&0) {
+                break false;
+            }
+            if is_tracked_process_alive(&state, &app_id, &process_key, 0) {
+                found = true;
+            } else if found || started.elapsed() >= NAME_MARKER_START_GRACE {
+                break true;
+            }
+        };
+        WATCHING.lock_or_recover().retain(|id| id != &app_id);
+        if exited {
+            info!(
+                "No running process found for name-tracked app_id={}; removing its marker",
+                app_id
+            );
+            handle_process_exit(&app, &app_id, 0, None);
+        }
+    });
+}
+
+// 管理中のプロセスが終了するまで待ち、終了コードを返す
+#[tauri::command]
+async fn await_application_exit(app: AppHandle, app_id: String) -> Result<i32, CommandError> {
+    let receiver = {
+        let state: tauri::State<AppState> = app.state();
+        let processes = lock_processes(&state);
+        let pid = processes
+            .get(&app_id)
+            .or_else(|| processes.get(&format!("{}:name", app_id)))
+            .copied()
+            .ok_or_else(|| {
+                CommandError::new(ErrorCode::ProcessNotRunning, "Application is not running")
+            })?;
+        // Windowsの管理者実行のアプリはPIDが分からない
+        if pid == 0 {
+            return Err(CommandError::new(
+                ErrorCode::Unsupported,
+                "Cannot wait for an application launched as administrator",
+            ));
+        }
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        state
+            .exit_waiters
+            .lock_or_recover()
+            .entry(pid)
+            .or_default()
+            .push(sender);
+        receiver
+    };
+
+    receiver
+        .await
+        .map_err(|_| "Stopped waiting for the application".to_string())?
+        .ok_or_else(|| "Exit code is not available".into())
+}
+
+// 自分の子プロセスではないプロセスの終了を待つスレッドを開始
+#[cfg(target_os = "windows")]
+fn watch_process_exit(app: &AppHandle, app_id: String, pid: u32) {
+    watch_exit(app, app_id, pid, Box::new(move || wait_for_pid_exit(pid)));
+}
+
+// 自分の子プロセスではないプロセスの終了をPowerShellで待ち、終了コードを返す
+#[cfg(target_os = "windows")]
+fn wait_for_pid_exit(pid: u32) -> Option<i32> {
+    // PowerShellが使えない場合は終了するまで確認し続ける（終了コードは取得できない）
+    if !powershell_available() {
+        while is_process_alive(pid) {
+            std::thread::sleep(PROCESS_EXIT_POLL_INTERVAL);
+        }
+        return None;
+    }
+    let output = Command::new("powershell")
+        .args([
+            "-WindowStyle",
+            "Hidden",
+            "-Command",
+            &format!(
+                "$p = Get-Process -Id {} -ErrorAction Stop; $p.WaitForExit(); Write-Output $p.ExitCode",
+                pid
+            ),
+        ])
+        .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
+        .output();
+    output.ok().and_then(|o| {
+        String::from_utf8_lossy(&o.stdout)
+            .trim()
+            .parse::<i32>()
+            .ok()
+    })
+}
+
+// アプリケーションを停止（停止コマンドの終了を待つ場合があるためメインスレッドを止めないようにする。
+// 成功を返すのはプロセスの終了を確認できた場合のみ）
+#[tauri::command]
+async fn stop_application(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let settle_ms = lock_config(&app.state::<AppState>())
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .map_or(0, |a| a.stop_settle_ms);
+
+    tauri::async_runtime::spawn_blocking(move || stop_application_blocking(app, app_id))
+        .await
+        .map_err(CommandError::background_task)??;
+
+    // 終了を確認した後も、ポート・ロックファイルなどが解放されるまで待ってから返す
+    if settle_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(settle_ms)).await;
+    }
+    Ok(())
+}
+
+// 実行中のアプリのプロセスを一時停止（終了はせず、resume_application で再開する）
+#[tauri::command]
+fn suspend_application(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let pid = running_tracked_pid(&app, &app_id)?;
+    if state.suspended_apps.lock_or_recover().get(&app_id) == Some(&pid) {
+        return Err(CommandError::new(
+            ErrorCode::AlreadyExists,
+            format!("Application is already suspended: {}", app_id),
+        ));
+    }
+    set_process_tree_suspended(pid, true).map_err(|e| {
+        CommandError::new(
+            ErrorCode::LaunchFailed,
+            format!("Failed to suspend {}: {}", app_id, e),
+        )
+    })?;
+    state
+        .suspended_apps
+        .lock_or_recover()
+        .insert(app_id.clone(), pid);
+    info!("Suspended app_id={} pid={}", app_id, pid);
+    Ok(())
+}
+
+// 一時停止したアプリのプロセスを再開
+#[tauri::command]
+fn resume_application(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let pid = running_tracked_pid(&app, &app_id)?;
+    if state.suspended_apps.lock_or_recover().get(&app_id) != Some(&pid) {
+        return Err(CommandError::new(
+            ErrorCode::ProcessNotRunning,
+            format!("Application is not suspended: {}", app_id),
+        ));
+    }
+    set_process_tree_suspended(pid, false).map_err(|e| {
+        CommandError::new(
+            ErrorCode::LaunchFailed,
+            format!("Failed to resume {}: {}", app_id, e),
+        )
+    })?;
+    state.suspended_apps.lock_or_recover().remove(&app_id);
+    info!("Resumed app_id={} pid={}", app_id, pid);
+    Ok(())
+}
+
+// 管理中で動作しているアプリのプロセスのPIDを取得（実行中でない・PIDが分からない場合はエラー）
+fn running_tracked_pid(app: &AppHandle, app_id: &str) -> Result<u32, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let not_running = || {
+        CommandError::new(
+            ErrorCode::ProcessNotRunning,
+            format!("Application is not running: {}", app_id),
+        )
+    };
+    let (process_key, pid) = find_tracked_process(&state, app_id).ok_or_else(not_running)?;
+    if pid == 0 {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            format!("Process ID of {} is unknown", app_id),
+        ));
+    }
+    if !is_tracked_process_alive(&state, app_id, &process_key, pid) {
+        remove_stale_process_entry(app, app_id, &process_key, pid);
+        return Err(not_running());
+    }
+    Ok(pid)
+}
+
+// 停止コマンドを実行し、制限時間内に終了するまで待つ
+fn run_stop_command(
+    registered_app: &RegisteredApp,
+    stop_command: &str,
+    timeout: Duration,
+) -> Result<(), String> {
+    run_app_shell_command(registered_app, "stop command", stop_command, timeout, None)
+}
+
+// 起動前・起動後のコマンドを実行（出力はアプリのログに追記する）
+fn run_launch_hook(
+    app: &AppHandle,
+    registered_app: &RegisteredApp,
+    label: &str,
+    hook: &str,
+) -> Result<(), String> {
+    let log_path = app_log_file_path(app, &registered_app.id)?;
+    let err_log_path = app_err_log_file_path(&log_path);
+    let log = (open_app_log(&log_path)?, open_app_log(&err_log_path)?);
+    run_app_shell_command(registered_app, label, hook, LAUNCH_HOOK_TIMEOUT, Some(log))
+}
+
+// アプリの環境変数・作業ディレクトリでコマンドをシェルで実行し、制限時間内に終了するまで待つ
+// （log を指定した場合は標準出力・標準エラーをそのファイルに書き込み、それ以外は破棄する）
+fn run_app_shell_command(
+    registered_app: &RegisteredApp,
+    label: &str,
+    command_text: &str,
+    timeout: Duration,
+    log: Option<(std::fs::File, std::fs::File)>,
+) -> Result<(), String> {
+    let mut command = build_shell_launch_command(
+        &expand_env(command_text),
+        "",
+        &registered_app.env,
+        &expand_env(&registered_app.working_directory),
+    );
+    use_new_process_group(&mut command);
+    let (stdout, stderr) = match log {
+        Some((stdout, stderr)) => (stdout.into(), stderr.into()),
+        None => (std::process::Stdio::null(), std::process::Stdio::null()),
+    };
+    command
+        .stdin(std::process::Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr);
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW); // コンソールウィンドウを表示しない
+    info!(
+        "Running {} for app_id={}: {:?}",
+        label, registered_app.id, command
+    );
+
+    let mut child = command.spawn().map_err(|e| e.to_string())?;
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait().map_err(|e| e.to_string())? {
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(format!("{} exited with {}", label, status))
+            };
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!(
+                "{} timed out after {} seconds",
+                label,
+                timeout.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+fn stop_application_blocking(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+
+    // 自動再起動の監視に停止を伝える
+    state
+        .intentionally_stopped
+        .lock_or_recover()
+        .insert(app_id.clone());
+
+    // 停止したため自動停止の予約を取り消す
+    state.auto_stops.lock_or_recover().remove(&app_id);
+
+    // 一時停止中のプロセスは終了の要求を処理できないため、先に再開する
+    let suspended_pid = state.suspended_apps.lock_or_recover().remove(&app_id);
+    if let Some(suspended_pid) = suspended_pid {
+        if let Err(e) = set_process_tree_suspended(suspended_pid, false) {
+            warn!("Failed to resume {} before stopping: {}", app_id, e);
+        }
+    }
+
+    // 登録されたアプリケーションの情報を取得
+    let registered_app = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|app| app.id == app_id)
+        .cloned();
+    let app_path = registered_app.as_ref().map(|app| app.path.clone());
+
+    // 起動時に記録したキーで管理テーブルから取り除く（重複起動禁止・PIDが分からない起動はプロセス名で管理されている）
+    if let Some((process_key, pid)) = take_tracked_process(&state, &app_id) {
+        let name_tracked = process_key.ends_with(":name");
+        save_runtime_state(&app);
+        update_tray_status(&app);
+
+        // 停止コマンドがあれば先に実行し、終了しなければ強制終了する
+        let stop_command = registered_app
+            .as_ref()
+            .and_then(|a| a.stop_command.as_deref())
+            .filter(|c| !c.trim().is_empty());
+        if let (Some(registered_app), Some(stop_command)) = (&registered_app, stop_command) {
+            let deadline = Instant::now() + STOP_COMMAND_TIMEOUT;
+            match run_stop_command(registered_app, stop_command, STOP_COMMAND_TIMEOUT) {
+                Ok(()) => {
+                    while is_tracked_process_alive(&state, &app_id, &process_key, pid)
+                        && Instant::now() < deadline
+                    {
+                        std::thread::sleep(Duration::from_millis(200));
+                    }
+                }
+                Err(e) => warn!("Stop command for app_id={} failed: {}", app_id, e),
+            }
+            if !is_tracked_process_alive(&state, &app_id, &process_key, pid) {
+                info!("Stopped app_id={} gracefully with stop command", app_id);
+                return Ok(());
+            }
+            warn!(
+                "app_id={} is still running after stop command; forcing stop",
+                app_id
+            );
+        }
+
+        return stop_tracked_process(&state, &app_id, pid, name_tracked, app_path)
+            .map_err(|e| CommandError::new(ErrorCode::LaunchFailed, e));
+    }
+
+    Err(CommandError::new(
+        ErrorCode::ProcessNotRunning,
+        "Application not found or not running",
+    ))
+}
+
+// 管理テーブルから取り除いたプロセスを停止する（終了を要求し、猶予時間内に終了しなければ強制終了する）
+// プロセス名で管理しているアプリは、起動したプロセスが終了していれば実行ファイルのパスで停止する
+fn stop_tracked_process(
+    state: &AppState,
+    app_id: &str,
+    pid: u32,
+    name_tracked: bool,
+    app_path: Option<String>,
+) -> Result<(), String> {
+    let grace_period = Duration::from_millis(lock_config(state).stop_grace_period_ms);
+
+    if name_tracked && pid != 0 && state.launcher.is_alive(pid) {
+        // 起動したプロセスが動作中ならそのプロセスだけを停止（同名の他のプロセスは巻き込まない）
+        info!("Stopping app_id={} (name tracking) pid={}", app_id, pid);
+        if request_exit_and_wait(state, &[pid], grace_period) {
+            info!("Stopped app_id={} pid={} gracefully", app_id, pid);
+            return Ok(());
+        }
+        info!("Force stopping app_id={} pid={}", app_id, pid);
+        kill_and_verify(state, pid)
+    } else if name_tracked {
+        // PIDが分からない（管理者実行）か、起動したプロセスが既存のインスタンスに処理を渡して
+        // 終了している場合は、実行ファイルのパスが一致するプロセスを停止
+        if let Some(app_path) = app_path {
+            info!(
+                "Stopping app_id={} (name tracking) by executable path {}",
+                app_id, app_path
+            );
+            let pids = state.launcher.find_by_path(&app_path);
+            if request_exit_and_wait(state, &pids, grace_period) {
+                info!(
+                    "Stopped {} process(es) gracefully for: {}",
+                    pids.len(),
+                    app_path
+                );
+                return Ok(());
+            }
+
+            info!("Force stopping processes for: {}", app_path);
+            match state
+                .launcher
+                .kill(KillTarget::ExecutablePath(app_path.clone()))
+            {
+                Ok(0) => Err(format!("No running process found for '{}'", app_path)),
+                Ok(count) => {
+                    kill_path_and_verify(state, &app_path)?;
+                    info!(
+                        "Successfully stopped {} process(es) for: {}",
+                        count, app_path
+                    );
+                    Ok(())
+                }
+                Err(e) => Err(format!("Failed to stop process '{}': {}", app_path, e)),
+            }
+        } else {
+            Err("Application path not found".to_string())
+        }
+    } else {
+        // 通常のアプリの場合はPIDで子プロセスも含めて停止
+        info!("Stopping app_id={} pid={}", app_id, pid);
+        if request_exit_and_wait(state, &[pid], grace_period) {
+            info!("Stopped app_id={} pid={} gracefully", app_id, pid);
+            return Ok(());
+        }
+        info!("Force stopping app_id={} pid={}", app_id, pid);
+
+        match kill_and_verify(state, pid) {
+            Ok(()) => {
+                info!("Stopped app_id={} pid={} (process tree)", app_id, pid);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to stop app_id={} pid={}: {}", app_id, pid, e);
+                Err(e)
+            }
+        }
+    }
+}
+
+// プロセスに終了を要求し、猶予時間内にすべて終了したら true を返す
+// （猶予時間が0・終了を要求できなかった場合は待たずに false を返す）
+fn request_exit_and_wait(state: &AppState, pids: &[u32], grace_period: Duration) -> bool {
+    if grace_period.is_zero() || pids.is_empty() {
+        return false;
+    }
+    let mut requested = false;
+    for &pid in pids {
+        match state.launcher.terminate(pid) {
+            Ok(()) => requested = true,
+            Err(e) => warn!("Failed to request exit of process {}: {}", pid, e),
+        }
+    }
+    if !requested {
+        return false;
+    }
+    wait_until_exited(
+        || pids.iter().all(|&pid| !state.launcher.is_alive(pid)),
+        grace_period,
+    )
+}
+
+// 終了したかを時間内に繰り返し確認し、終了したら true を返す
+fn wait_until_exited(exited: impl Fn() -> bool, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if exited() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+// PIDを指定してプロセスを子プロセスも含めて強制終了（アプリとの対応に関係なく停止する、トラブルシューティング用）
+// 管理テーブルに同じPIDがあれば取り除き、そのアプリは自動再起動しない
+#[tauri::command]
+async fn kill_pid(app: AppHandle, pid: u32) -> Result<(), CommandError> {
+    if pid == 0 {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Refusing to kill PID 0",
+        ));
+    }
+    if pid == std::process::id() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Refusing to kill the launcher itself",
+        ));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let state: tauri::State<AppState> = app.state();
+        let removed: Vec<String> = {
+            let mut processes = lock_processes(&state);
+            let keys: Vec<String> = processes
+                .iter()
+                .filter(|&(_, &p)| p == pid)
+                .map(|(process_key, _)| process_key.clone())
+                .collect();
+            for process_key in &keys {
+                processes.remove(process_key);
+            }
+            keys
+        };
+        if !removed.is_empty() {
+            let mut intentionally_stopped = state.intentionally_stopped.lock_or_recover();
+            for process_key in &removed {
+                let app_id = process_key.strip_suffix(":name").unwrap_or(process_key);
+                intentionally_stopped.insert(app_id.to_string());
+            }
+            drop(intentionally_stopped);
+            save_runtime_state(&app);
+            update_tray_status(&app);
+        }
+
+        info!("Killing pid={} (managed entries: {:?})", pid, removed);
+        kill_and_verify(&state, pid)
+    })
+    .await
+    .map_err(CommandError::background_task)?
+    .map_err(CommandError::from)
+}
+
+// プロセスを子プロセスも含めて強制終了し、終了したことを確認する
+// （終了しなければもう一度強制終了し、それでも残っていればエラー）
+fn kill_and_verify(state: &AppState, pid: u32) -> Result<(), String> {
+    for attempt in 0..2 {
+        state
+            .launcher
+            .kill(KillTarget::ProcessTree(pid))
+            .map_err(|e| format!("Failed to stop process {}: {}", pid, e))?;
+        if wait_until_exited(|| !state.launcher.is_alive(pid), KILL_VERIFY_TIMEOUT) {
+            return Ok(());
+        }
+        if attempt == 0 {
+            warn!(
+                "Process {} is still running after being killed; killing again",
+                pid
+            );
+        }
+    }
+    Err(format!(
+        "Process {} is still running after being killed",
+        pid
+    ))
+}
+
+// 実行ファイルのパスが一致するプロセスが強制終了後に残っていないか確認する
+// （残っていればもう一度強制終了し、それでも残っていればエラー）
+fn kill_path_and_verify(state: &AppState, app_path: &str) -> Result<(), String> {
+    let exited = || state.launcher.find_by_path(app_path).is_empty();
+    if wait_until_exited(exited, KILL_VERIFY_TIMEOUT) {
+        return Ok(());
+    }
+    warn!(
+        "Processes for {} are still running after being killed; killing again",
+        app_path
+    );
+    state
+        .launcher
+        .kill(KillTarget::ExecutablePath(app_path.to_string()))
+        .map_err(|e| format!("Failed to stop process '{}': {}", app_path, e))?;
+    if wait_until_exited(exited, KILL_VERIFY_TIMEOUT) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Processes for '{}' are still running after being killed",
+            app_path
+        ))
+    }
+}
+
+// プロセスを子プロセスも含めて強制終了（既に終了している場合は成功として扱う）
+#[cfg(target_os = "windows")]
+fn kill_process_tree(pid: u32) -> Result<(), String> {
+    signal_process_tree(pid, true)
+}
+
+// プロセスに子プロセスも含めて終了を要求（ウィンドウに WM_CLOSE を送る。ウィンドウのないプロセスは失敗する）
+#[cfg(target_os = "windows")]
+fn request_process_tree_exit(pid: u32) -> Result<(), String> {
+    signal_process_tree(pid, false)
+}
+
+// taskkill でプロセスツリーを終了（force でない場合はアプリに終了を要求するだけ）
+#[cfg(target_os = "windows")]
+fn signal_process_tree(pid: u32, force: bool) -> Result<(), String> {
+    // taskkill の終了コード128はプロセスが見つからない（終了済み）
+    const TASKKILL_NOT_FOUND: i32 = 128;
+
+    let pid_arg = pid.to_string();
+    let mut args = vec!["/PID", pid_arg.as_str(), "/T"];
+    if force {
+        args.push("/F");
+    }
+    info!("Executing taskkill {}", args.join(" "));
+    let output = Command::new("taskkill")
+        .args(&args)
+        .creation_flags(CREATE_NO_WINDOW) // コンソールウィンドウを表示しない
+        .output()
+        .map_err(|e| e.to_string())?;
+    match output.status.code() {
+        Some(0) | Some(TASKKILL_NOT_FOUND) => Ok(()),
+        _ => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+// プロセスを子プロセスも含めて強制終了（既に終了している場合は成功として扱う）
+#[cfg(not(target_os = "windows"))]
+fn kill_process_tree(pid: u32) -> Result<(), String> {
+    signal_process_tree(pid, libc::SIGKILL)
+}
+
+// プロセスに子プロセスも含めて終了を要求（SIGTERM）
+#[cfg(not(target_os = "windows"))]
+fn request_process_tree_exit(pid: u32) -> Result<(), String> {
+    signal_process_tree(pid, libc::SIGTERM)
+}
+
+// プロセスにシグナルを送る（既に終了している場合は成功として扱う）
+// ランチャーから起動したアプリは新しいプロセスグループで起動しているため、グループごとに送る
+#[cfg(not(target_os = "windows"))]
+fn signal_process_tree(pid: u32, signal: libc::c_int) -> Result<(), String> {
+    let kill = |target: libc::pid_t| -> Result<(), std::io::Error> {
+        if unsafe { libc::kill(target, signal) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    };
+    let is_gone = |e: &std::io::Error| e.raw_os_error() == Some(libc::ESRCH);
+
+    // プロセスグループがない（ランチャー以外から起動された）場合はプロセス単体を終了
+    match kill(-(pid as libc::pid_t)) {
+        Ok(()) => Ok(()),
+        Err(e) if is_gone(&e) => match kill(pid as libc::pid_t) {
+            Ok(()) => Ok(()),
+            Err(e) if is_gone(&e) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        },
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// プロセスを子プロセスも含めて一時停止・再開する（NtSuspendProcess・NtResumeProcess）
+// 子プロセスの一時停止・再開に失敗しても警告のみとする
+#[cfg(target_os = "windows")]
+fn set_process_tree_suspended(pid: u32, suspend: bool) -> Result<(), String> {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE, NTSTATUS};
+    use windows::Win32::System::Threading::{OpenProcess, PROCESS_SUSPEND_RESUME};
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        fn NtSuspendProcess(process: HANDLE) -> NTSTATUS;
+        fn NtResumeProcess(process: HANDLE) -> NTSTATUS;
+    }
+
+    for target in process_tree_pids(pid) {
+        let result = unsafe { OpenProcess(PROCESS_SUSPEND_RESUME, false, target) }
+            .map_err(|e| e.to_string())
+            .and_then(|process| {
+                let status = unsafe {
+                    if suspend {
+                        NtSuspendProcess(process)
+                    } else {
+                        NtResumeProcess(process)
+                    }
+                };
+                unsafe {
+                    let _ = CloseHandle(process);
+                }
+                if status.is_ok() {
+                    Ok(())
+                } else {
+                    Err(format!("NTSTATUS 0x{:08X}", status.0))
+                }
+            });
+        match result {
+            Ok(()) => {}
+            Err(e) if target == pid => return Err(e),
+            Err(e) => warn!("Failed to suspend/resume child process {}: {}", target, e),
+        }
+    }
+    Ok(())
+}
+
+// プロセスと子孫のプロセスのPID一覧（指定したプロセスが先頭）
+fn process_tree_pids(pid: u32) -> Vec<u32> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    let mut pids = vec![pid];
+    let mut index = 0;
+    while index < pids.len() {
+        let parent = pids[index];
+        let children: Vec<u32> = system
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.parent().map(|p| p.as_u32()) == Some(parent))
+            .map(|(child, _)| child.as_u32())
+            .filter(|child| !pids.contains(child))
+            .collect();
+        pids.extend(children);
+        index += 1;
+    }
+    pids
+}
+
+// 実行中のアプリのウィンドウを前面に表示する（表示できるウィンドウがなければ false）
+// 管理しているプロセスと子孫のプロセスを優先し、次に同じ実行ファイルのプロセスから探す
+fn focus_app_window(app: &AppHandle, registered_app: &RegisteredApp) -> Result<bool, String> {
+    let mut pids = running_tracked_pid(app, &registered_app.id)
+        .map(process_tree_pids)
+        .unwrap_or_default();
+    for pid in find_pids_by_executable_path(&registered_app.path) {
+        if !pids.contains(&pid) {
+            pids.push(pid);
+        }
+    }
+    if pids.is_empty() {
+        return Ok(false);
+    }
+    focus_process_window(&pids)
+}
+
+// 実行中のアプリのウィンドウを前面に表示する（表示できたか）
+#[tauri::command]
+async fn focus_application(app: AppHandle, app_id: String) -> Result<bool, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state: tauri::State<AppState> = app.state();
+        let registered_app = lock_config(&state)
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .cloned()
+            .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+        focus_app_window(&app, &registered_app).map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::background_task)?
+}
+
+// PIDの一覧の先頭から順に、表示されているメインのウィンドウを探して前面に表示する
+#[cfg(target_os = "windows")]
+fn focus_process_window(pids: &[u32]) -> Result<bool, String> {
+    use windows::core::BOOL;
+    use windows::Win32::Foundation::{HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindow, GetWindowThreadProcessId, IsIconic, IsWindowVisible,
+        SetForegroundWindow, ShowWindow, GW_OWNER, SW_RESTORE,
+    };
+
+    // 表示されていて所有者のない（ダイアログなどでない）ウィンドウを (ウィンドウ, PID) で集める
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam.0 as *mut Vec<(HWND, u32)>);
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        if IsWindowVisible(hwnd).as_bool() && GetWindow(hwnd, GW_OWNER).is_err() {
+            windows.push((hwnd, pid));
+        }
+        BOOL(1)
+    }
+
+    let mut windows: Vec<(HWND, u32)> = Vec::new();
+    unsafe { EnumWindows(Some(collect), LPARAM(&mut windows as *mut _ as isize)) }
+        .map_err(|e| format!("Failed to enumerate windows: {}", e))?;
+    let Some(hwnd) = pids.iter().find_map(|pid| {
+        windows
+            .iter()
+            .find(|(_, owner)| owner == pid)
+            .map(|(hwnd, _)| *hwnd)
+    }) else {
+        return Ok(false);
+    };
+
+    unsafe {
+        // 最小化されている場合は元に戻してから前面に表示する
+        if IsIconic(hwnd).as_bool() {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+        }
+        if !SetForegroundWindow(hwnd).as_bool() {
+            return Err("Failed to bring the window to the foreground".to_string());
+        }
+    }
+    Ok(true)
+}
+
+// macOSでは System Events でプロセスを最前面にする
+#[cfg(target_os = "macos")]
+fn focus_process_window(pids: &[u32]) -> Result<bool, String> {
+    for pid in pids {
+        let script = format!(
+            "tell application \"System Events\" to set frontmost of (first process whose unix id is {}) to true",
+            pid
+        );
+        match Command::new("osascript").args(["-e", &script]).output() {
+            Ok(output) if output.status.success() => return Ok(true),
+            Ok(_) => {}
+            Err(e) => return Err(format!("Failed to run osascript: {}", e)),
+        }
+    }
+    Ok(false)
+}
+
+// Linuxなどでは wmctrl でウィンドウを探して前面に表示する（wmctrl がなければ何もしない）
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+fn focus_process_window(pids: &[u32]) -> Result<bool, String> {
+    // 「ウィンドウID デスクトップ PID ホスト名 タイトル」の一覧
+    let output = match Command::new("wmctrl").arg("-lp").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            info!("wmctrl is not available; cannot focus the window");
+            return Ok(false);
+        }
+    };
+    let list = String::from_utf8_lossy(&output.stdout);
+    let windows: Vec<(&str, u32)> = list
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let id = columns.next()?;
+            let pid = columns.nth(1)?.parse().ok()?;
+            Some((id, pid))
+        })
+        .collect();
+    let Some(id) = pids.iter().find_map(|pid| {
+        windows
+            .iter()
+            .find(|(_, owner)| owner == pid)
+            .map(|(id, _)| *id)
+    }) else {
+        return Ok(false);
+    };
+    let status = Command::new("wmctrl")
+        .args(["-i", "-a", id])
+        .status()
+        .map_err(|e| format!("Failed to run wmctrl: {}", e))?;
+    if !status.success() {
+        return Err(format!("wmctrl failed to activate window {}", id));
+    }
+    Ok(true)
+}
+
+// プロセスを子プロセスも含めて一時停止・再開する（SIGSTOP・SIGCONT をプロセスグループに送る）
+#[cfg(not(target_os = "windows"))]
+fn set_process_tree_suspended(pid: u32, suspend: bool) -> Result<(), String> {
+    signal_process_tree(
+        pid,
+        if suspend {
+            libc::SIGSTOP
+        } else {
+            libc::SIGCONT
+        },
+    )
+}
+
+// 停止時にプロセスグループごと終了できるよう、新しいプロセスグループで起動する
+#[cfg(not(target_os = "windows"))]
+fn use_new_process_group(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    cmd.process_group(0);
+}
+
+// Windowsでは taskkill /T で子プロセスをたどるため何もしない
+#[cfg(target_os = "windows")]
+fn use_new_process_group(_cmd: &mut Command) {}
+
+// ランチャーから切り離して起動する（新しいセッションで起動し、端末・ランチャーの終了の影響を受けない）
+// セッションのリーダーは新しいプロセスグループのリーダーでもあるため、停止はこれまで通りグループごと行える
+#[cfg(not(target_os = "windows"))]
+fn detach_process(cmd: &mut Command) {
+    use std::os::unix::process::CommandExt;
+    unsafe {
+        cmd.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+// Windowsでは作成フラグ（DETACHED_PROCESS・CREATE_NEW_PROCESS_GROUP）で切り離すため何もしない
+#[cfg(target_os = "windows")]
+fn detach_process(_cmd: &mut Command) {}
+
+// 管理中のすべてのアプリを停止（一定時間内に終了しないものは強制終了。切り離して起動したアプリは除く）
+fn stop_all_applications(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    // 切り離して起動したアプリはランチャーより長く動かすためのものなので停止しない
+    let detached: HashSet<String> = lock_config(&state)
+        .registered_apps
+        .iter()
+        .filter(|a| a.detached)
+        .map(|a| a.id.clone())
+        .collect();
+    let tracked: Vec<(String, u32)> = lock_processes(&state)
+        .iter()
+        .filter(|(key, _)| !detached.contains(key.trim_end_matches(":name")))
+        .map(|(key, &pid)| (key.clone(), pid))
+        .collect();
+    if tracked.is_empty() {
+        return;
+    }
+
+    info!("Stopping {} managed process(es) before quit", tracked.len());
+    for (process_key, _) in &tracked {
+        let app_id = process_key.trim_end_matches(":name").to_string();
+        if let Err(e) = stop_application_blocking(app.clone(), app_id.clone()) {
+            error!("Failed to stop {} on quit: {}", app_id, e);
+        }
+    }
+
+    // 終了を待ち、残っているプロセスはPIDで強制終了
+    let deadline = std::time::Instant::now() + QUIT_STOP_TIMEOUT;
+    let mut remaining: Vec<u32> = tracked
+        .iter()
+        .map(|&(_, pid)| pid)
+        .filter(|&pid| pid != 0)
+        .collect();
+    while !remaining.is_empty() && std::time::Instant::now() < deadline {
+        remaining.retain(|&pid| is_process_alive(pid));
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    for pid in remaining {
+        info!("Force killing process {} on quit", pid);
+        if let Err(e) = state.launcher.kill(KillTarget::ProcessTree(pid)) {
+            error!("Failed to force kill process {}: {}", pid, e);
+        }
+    }
+}
+
+// ランチャー終了時にアプリも停止するかを設定
+#[tauri::command]
+fn set_stop_apps_on_quit(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.stop_apps_on_quit = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 停止時に強制終了するまでの猶予時間を設定
+#[tauri::command]
+fn set_stop_grace_period_ms(app: AppHandle, value: u64) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.stop_grace_period_ms = value;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 自動再起動の通知をまとめる間隔を設定
+#[tauri::command]
+fn set_restart_notification_interval_ms(app: AppHandle, value: u64) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.restart_notification_interval_ms = value;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// メンテナンスの期間を設定（None の場合は解除する）
+#[tauri::command]
+fn set_maintenance_window(
+    app: AppHandle,
+    window: Option<MaintenanceWindow>,
+) -> Result<(), CommandError> {
+    if let Some(window) = &window {
+        validate_maintenance_window(window)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    }
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.maintenance_window = window;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 次回の自動起動を1回だけスキップするかを設定（false で解除する）
+#[tauri::command]
+fn set_suppress_next_startup(app: AppHandle, value: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.suppress_next_startup = value;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 同じアプリの続けての起動を無視する間隔を設定
+#[tauri::command]
+fn set_launch_debounce_ms(app: AppHandle, value: u64) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.launch_debounce_ms = value;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// トレイの「Quit」で終了の確認を省略するかを設定
+#[tauri::command]
+fn set_skip_quit_confirmation(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.skip_quit_confirmation = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// トレイメニュー・通知の表示言語を設定し、トレイを作り直す
+#[tauri::command]
+fn set_language(app: AppHandle, language: String) -> Result<(), CommandError> {
+    if !i18n::is_supported(&language) {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            format!("Unsupported language: {}", language),
+        ));
+    }
+    let state: tauri::State<AppState> = app.state();
+    {
+        let mut config = lock_config(&state);
+        config.language = language;
+        save_config(&app, &config)?;
+    }
+    rebuild_tray_menu(&app);
+    update_tray_status(&app);
+    Ok(())
+}
+
+// 設定に合わせてウィンドウを最前面に固定・解除
+fn apply_always_on_top(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let state: tauri::State<AppState> = app.state();
+    let always_on_top = lock_config(&state).always_on_top;
+    if let Err(e) = window.set_always_on_top(always_on_top) {
+        error!("Failed to set always on top: {}", e);
+    }
+}
+
+// 起動時にウィンドウを表示しないかを設定
+#[tauri::command]
+fn set_start_hidden(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.start_hidden = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// ウィンドウを常に最前面に表示するかを切り替えて保存
+#[tauri::command]
+fn set_always_on_top(app: AppHandle, value: bool) -> Result<(), CommandError> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| CommandError::new(ErrorCode::NotFound, "Main window not found"))?;
+    window.set_always_on_top(value).map_err(|e| e.to_string())?;
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.always_on_top = value;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 終了の確認をフロントエンドへ依頼する際のイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct QuitRequestedPayload {
+    running_count: usize,
+    stop_apps_on_quit: bool,
+}
+
+// 終了を要求（実行中のアプリがある場合はフロントエンドで確認してから confirm_quit で終了する）
+fn request_quit(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let (skip_confirmation, stop_apps_on_quit) = {
+        let config = lock_config(&state);
+        (config.skip_quit_confirmation, config.stop_apps_on_quit)
+    };
+    let running_count = running_app_count(&state);
+    if skip_confirmation || running_count == 0 {
+        app.exit(0);
+        return;
+    }
+
+    // ウィンドウが非表示のままでは確認できないため表示してから依頼する（できなければそのまま終了）
+    let Some(window) = app.get_webview_window("main") else {
+        warn!("Main window not found; quitting without confirmation");
+        app.exit(0);
+        return;
+    };
+    if let Err(e) = window.show().and_then(|()| window.set_focus()) {
+        warn!("Failed to show window for quit confirmation: {}", e);
+    }
+    let payload = QuitRequestedPayload {
+        running_count,
+        stop_apps_on_quit,
+    };
+    if let Err(e) = app.emit("quit-requested", payload) {
+        error!("Failed to emit quit-requested; quitting: {}", e);
+        app.exit(0);
+    }
+}
+
+// フロントエンドで終了が確認されたらランチャーを終了
+#[tauri::command]
+fn confirm_quit(app: AppHandle) {
+    app.exit(0);
+}
+
+// 自動起動アプリを順番に起動するか（無効な場合は同時に起動し、遅延はそれぞれ独立して待つ）を設定
+#[tauri::command]
+fn set_sequential_startup(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.sequential_startup = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 相対パスの実行ファイルの基準ディレクトリを設定（空の場合は設定ディレクトリ）
+#[tauri::command]
+fn set_base_directory(app: AppHandle, path: String) -> Result<(), CommandError> {
+    reject_control_characters("Base directory", &path)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    let path = path.trim().to_string();
+    if !path.is_empty() {
+        if let Some(base) = base_directory_path(&app, &path).filter(|base| !base.is_dir()) {
+            return Err(CommandError::new(
+                ErrorCode::NotFound,
+                format!("Base directory not found: {}", base.display()),
+            ));
+        }
+    }
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.base_directory = path;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 自動起動の結果のまとめを通知するかを設定
+#[tauri::command]
+fn set_show_startup_summary(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.show_startup_summary = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// 起動の成否のデスクトップ通知を有効・無効にする
+#[tauri::command]
+fn set_notifications_enabled(app: AppHandle, enabled: bool) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    config.notifications_enabled = enabled;
+    save_config(&app, &config).map_err(|e| CommandError::new(ErrorCode::Io, e))
+}
+
+// デスクトップ通知を表示（設定で無効な場合は何もしない）
+// 設定された言語の表示文字列を取得
+fn tr<R: Runtime>(app: &AppHandle<R>, key: &str, args: &[(&str, &str)]) -> String {
+    let language = lock_config(&app.state::<AppState>()).language.clone();
+    i18n::translate(&language, key, args)
+}
+
+fn notify(app: &AppHandle, title: &str, body: &str) {
+    let state: tauri::State<AppState> = app.state();
+    if !lock_config(&state).notifications_enabled {
+        return;
+    }
+
+    let mut builder = app.notification().builder().title(title);
+    if !body.is_empty() {
+        builder = builder.body(body);
+    }
+    if let Err(e) = builder.show() {
+        error!("Failed to show notification: {}", e);
+    }
+}
+
+// プロセスIDのプロセスが生存しているか確認（停止の待機などで繰り返し呼ぶため、プロセスを起動せずに確認する）
+#[cfg(target_os = "windows")]
+fn is_process_alive(pid: u32) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_ACCESS_DENIED, STILL_ACTIVE};
+    use windows::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    if pid == 0 {
+        return false;
+    }
+    let process = match unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) } {
+        Ok(process) => process,
+        // 保護されたプロセスなど、開けなくても存在はしている
+        Err(e) => return e.code() == ERROR_ACCESS_DENIED.to_hresult(),
+    };
+    let mut exit_code = 0u32;
+    let result = unsafe { GetExitCodeProcess(process, &mut exit_code) };
+    unsafe {
+        let _ = CloseHandle(process);
+    }
+    result.is_ok() && exit_code == STILL_ACTIVE.0 as u32
+}
+
+// プロセスIDのプロセスが生存しているか確認（シグナル0を送る）
+#[cfg(not(target_os = "windows"))]
+fn is_process_alive(pid: u32) -> bool {
+    if pid == 0 {
+        return false;
+    }
+    // 自分が起動した子プロセスが終了済みならゾンビを回収する（回収できれば終了済み）
+    let mut status = 0;
+    if unsafe { libc::waitpid(pid as libc::pid_t, &mut status, libc::WNOHANG) } == pid as i32 {
+        return false;
+    }
+    // EPERM は権限がないだけでプロセスは存在している
+    let result = unsafe { libc::kill(pid as libc::pid_t, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+// プロセス名で管理しているアプリのプロセスが存在するか確認（実行ファイルのパスで照合）
+fn is_process_name_running(state: &AppState, registered_app: &RegisteredApp) -> bool {
+    !state.launcher.find_by_path(&registered_app.path).is_empty()
+}
+
+// 管理テーブルからアプリのエントリを取り除き、プロセスキーとPIDを返す（find_tracked_process と同じ順に探す）
+fn take_tracked_process(state: &AppState, app_id: &str) -> Option<(String, u32)> {
+    let mut processes = lock_processes(state);
+    [app_id.to_string(), format!("{}:name", app_id)]
+        .into_iter()
+        .find_map(|process_key| processes.remove(&process_key).map(|pid| (process_key, pid)))
+}
+
+// 管理テーブルからアプリのプロセスキーとPIDを取得
+fn find_tracked_process(state: &AppState, app_id: &str) -> Option<(String, u32)> {
+    let name_key = format!("{}:name", app_id);
+    let processes = lock_processes(state);
+    processes
+        .get(app_id)
+        .map(|&pid| (app_id.to_string(), pid))
+        .or_else(|| processes.get(&name_key).map(|&pid| (name_key, pid)))
+}
+
+// 起動したプロセスのリソース使用量
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessStats {
+    pub pid: u32,
+    // CPU使用率（全コアに対する割合、0〜100。前回の取得からの平均のため初回は 0）
+    pub cpu_percent: f32,
+    // 物理メモリの使用量（バイト）
+    pub memory_bytes: u64,
+    // 起動してからの経過時間（秒）
+    pub uptime_secs: u64,
+}
+
+// 実行中のアプリのCPU・メモリの使用量と起動してからの時間を取得（実行中でなければエラー）
+#[tauri::command]
+fn get_process_stats(app: AppHandle, app_id: String) -> Result<ProcessStats, CommandError> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate};
+
+    let state: tauri::State<AppState> = app.state();
+    let not_running =
+        || CommandError::new(ErrorCode::ProcessNotRunning, "Application is not running");
+    let (process_key, pid) = find_tracked_process(&state, &app_id).ok_or_else(not_running)?;
+    // プロセス名で管理しているアプリは、起動したプロセスが終了していれば実行ファイルのパスで探す
+    let pid = if pid != 0 && is_process_alive(pid) {
+        pid
+    } else if process_key.ends_with(":name") {
+        let path = lock_config(&state)
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .map(|a| a.path.clone())
+            .ok_or_else(not_running)?;
+        find_pids_by_executable_path(&path)
+            .first()
+            .copied()
+            .ok_or_else(not_running)?
+    } else {
+        return Err(not_running());
+    };
+
+    let mut system = state.process_stats.lock_or_recover();
+    let sys_pid = Pid::from_u32(pid);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[sys_pid]),
+        true,
+        ProcessRefreshKind::nothing().with_cpu().with_memory(),
+    );
+    let process = system.process(sys_pid).ok_or_else(not_running)?;
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    Ok(ProcessStats {
+        pid,
+        cpu_percent: process.cpu_usage() / cores as f32,
+        memory_bytes: process.memory(),
+        uptime_secs: process.run_time(),
+    })
+}
+
+// 管理中のプロセスが生存しているか確認
+fn is_tracked_process_alive(state: &AppState, app_id: &str, process_key: &str, pid: u32) -> bool {
+    if process_key.ends_with(":name") {
+        // 起動したプロセスが動作中なら実行ファイルのパスで探すまでもない
+        if pid != 0 && state.launcher.is_alive(pid) {
+            return true;
+        }
+        let config = lock_config(state);
+        let registered_app = config
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .cloned();
+        drop(config);
+        registered_app.is_some_and(|a| is_process_name_running(state, &a))
+    } else {
+        state.launcher.is_alive(pid)
+    }
+}
+
+// ランチャーの再起動前から実行中の登録アプリを探し、管理テーブルに登録し直す（URLは対象外）
+// （記録から管理し直せなかったアプリだけを実行ファイルのパスで探す）
+fn reconcile_running_processes(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let registered_apps = lock_config(&state).registered_apps.clone();
+
+    for registered_app in registered_apps.iter().filter(|a| !is_url_path(&a.path)) {
+        if find_tracked_process(&state, &registered_app.id).is_some() {
+            continue;
+        }
+        let Some(&pid) = find_pids_by_executable_path(&registered_app.path).first() else {
+            continue;
+        };
+
+        // 起動時と同じ方法（PID・プロセス名）で管理する（PIDが分からない起動のアプリはPIDを記録しない）
+        let (process_key, tracked_pid) = tracking_entry(
+            &registered_app.id,
+            registered_app.prevent_duplicate,
+            (!launches_without_pid(registered_app)).then_some(pid),
+        );
+        attach_running_process(app, &process_key, tracked_pid);
+
+        info!(
+            "Found running process pid={} for app_id={} ({})",
+            pid, registered_app.id, registered_app.name
+        );
+    }
+    save_runtime_state(app);
+    update_tray_status(app);
+}
+
+// 起動済みのプロセスを管理テーブルに登録し、終了を監視する（PIDが0の場合はプロセス名で管理する）
+fn attach_running_process(app: &AppHandle, process_key: &str, pid: u32) {
+    let state: tauri::State<AppState> = app.state();
+    lock_processes(&state).insert(process_key.to_string(), pid);
+    let app_id = process_key.trim_end_matches(":name").to_string();
+    if pid == 0 {
+        watch_name_marker_exit(app, app_id);
+        return;
+    }
+    #[cfg(target_os = "windows")]
+    watch_process_exit(app, app_id, pid);
+    #[cfg(not(target_os = "windows"))]
+    watch_pid_exit(app, app_id, pid);
+}
+
+// ランチャーが異常終了・再起動しても引き継ぐ実行中の状態
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct RuntimeState {
+    // 記録したときのOSの起動時刻（UNIX時間の秒）
+    #[serde(default)]
+    boot_time: u64,
+    // プロセスのキー -> 実行中のプロセス
+    #[serde(default)]
+    processes: HashMap<String, RuntimeProcess>,
+    // boot_time の起動後に起動した launch_once_per_boot のアプリのID
+    #[serde(default)]
+    launched_this_boot: HashSet<String>,
+}
+
+// 記録した実行中のプロセス（PIDの再利用を見分けるためプロセスの開始時刻も記録する）
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+struct RuntimeProcess {
+    pid: u32,
+    // プロセスの開始時刻（UNIX時間の秒。取得できない場合は0）
+    #[serde(default)]
+    started_at: u64,
+}
+
+// 実行中のプロセスを記録するファイルのパス
+fn runtime_state_path(app: &AppHandle) -> PathBuf {
+    get_config_dir(app).join(RUNTIME_STATE_FILE_NAME)
+}
+
+// OSの起動時刻（UNIX時間の秒）
+fn system_boot_time() -> u64 {
+    sysinfo::System::boot_time()
+}
+
+// 同じOSの起動か（起動時刻は稼働時間から求めるため、数秒の誤差は同じ起動とみなす）
+fn is_same_boot(boot_time: u64) -> bool {
+    boot_time != 0 && boot_time.abs_diff(system_boot_time()) <= BOOT_TIME_TOLERANCE_SECS
+}
+
+// 前回のランチャーが記録した実行中の状態を読み込む（ない・読み込めない場合は空）
+fn read_runtime_state(app: &AppHandle) -> RuntimeState {
+    let path = runtime_state_path(app);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return RuntimeState::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        warn!("Ignoring invalid runtime state {}: {}", path.display(), e);
+        RuntimeState::default()
+    })
+}
+
+// 同じOSの起動中に起動済みの launch_once_per_boot のアプリを引き継ぐ
+fn restore_launched_this_boot(app: &AppHandle, runtime_state: &RuntimeState) {
+    if !is_same_boot(runtime_state.boot_time) {
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    state
+        .launched_this_boot
+        .lock_or_recover()
+        .extend(runtime_state.launched_this_boot.iter().cloned());
+}
+
+// プロセスの開始時刻（UNIX時間の秒）を取得
+fn process_start_times(pids: &[u32]) -> HashMap<u32, u64> {
+    use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let sys_pids: Vec<Pid> = pids
+        .iter()
+        .filter(|&&pid| pid != 0)
+        .map(|&pid| Pid::from_u32(pid))
+        .collect();
+    if sys_pids.is_empty() {
+        return HashMap::new();
+    }
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&sys_pids),
+        true,
+        ProcessRefreshKind::nothing(),
+    );
+    sys_pids
+        .iter()
+        .filter_map(|pid| {
+            system
+                .process(*pid)
+                .map(|process| (pid.as_u32(), process.start_time()))
+        })
+        .collect()
+}
+
+// 管理テーブルなどを実行中の状態として保存（管理テーブルを変更するたびに呼ぶ）
+fn save_runtime_state(app: &AppHandle) {
+    // 古い内容で上書きしないよう、管理テーブルの読み取りから書き込みまでを1つずつ行う
+    static WRITE_LOCK: Mutex<()> = Mutex::new(());
+    let _write = WRITE_LOCK.lock_or_recover();
+
+    let state: tauri::State<AppState> = app.state();
+    let entries: Vec<(String, u32)> = lock_processes(&state)
+        .iter()
+        .map(|(process_key, &pid)| (process_key.clone(), pid))
+        .collect();
+    let pids: Vec<u32> = entries.iter().map(|&(_, pid)| pid).collect();
+    let start_times = process_start_times(&pids);
+    let processes = entries
+        .into_iter()
+        .map(|(process_key, pid)| {
+            let started_at = start_times.get(&pid).copied().unwrap_or(0);
+            (process_key, RuntimeProcess { pid, started_at })
+        })
+        .collect();
+    let runtime_state = RuntimeState {
+        boot_time: system_boot_time(),
+        processes,
+        launched_this_boot: state.launched_this_boot.lock_or_recover().clone(),
+    };
+
+    let result = serde_json::to_string_pretty(&runtime_state)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| write_file_atomically(&runtime_state_path(app), &contents));
+    if let Err(e) = result {
+        warn!("Failed to save runtime state: {}", e);
+    }
+}
+
+// 前回のランチャーが記録した実行中のプロセスを管理し直す
+// （PIDが生存していて、開始時刻と実行ファイルが記録と一致するものだけ。それ以外の記録は捨てる）
+fn restore_runtime_processes(app: &AppHandle, processes: HashMap<String, RuntimeProcess>) {
+    if processes.is_empty() {
+        return;
+    }
+    let state: tauri::State<AppState> = app.state();
+    let registered_apps = lock_config(&state).registered_apps.clone();
+    let pids: Vec<u32> = processes.values().map(|entry| entry.pid).collect();
+    let start_times = process_start_times(&pids);
+
+    for (process_key, entry) in processes {
+        let app_id = process_key.trim_end_matches(":name");
+        let Some(registered_app) = registered_apps
+            .iter()
+            .find(|a| a.id == app_id && !is_url_path(&a.path))
+        else {
+            info!("Dropped runtime state of unknown app_id={}", app_id);
+            continue;
+        };
+
+        // PIDが分からないアプリ（Windowsの管理者実行）は実行ファイルのパスで確認する
+        let verified = if entry.pid == 0 {
+            is_process_name_running(&state, registered_app)
+        } else {
+            let same_process = match start_times.get(&entry.pid) {
+                Some(&started_at) => entry.started_at == 0 || started_at == entry.started_at,
+                None => false,
+            };
+            // シェル経由のアプリのPIDはシェルのプロセスのため実行ファイルは比較できない
+            same_process
+                && is_process_alive(entry.pid)
+                && (registered_app.use_shell
+                    || find_pids_by_executable_path(&registered_app.path).contains(&entry.pid))
+        };
+        if !verified {
+            info!(
+                "Dropped stale runtime state pid={} for app_id={}",
+                entry.pid, app_id
+            );
+            continue;
+        }
+
+        attach_running_process(app, &process_key, entry.pid);
+        info!(
+            "Re-attached to process pid={} for app_id={} ({})",
+            entry.pid, app_id, registered_app.name
+        );
+    }
+    save_runtime_state(app);
+    update_tray_status(app);
+}
+
+// アプリケーションの実行状態を確認（終了済みのプロセスは管理テーブルから削除）
+#[tauri::command]
+async fn is_application_running(app: AppHandle, app_id: String) -> bool {
+    let state: tauri::State<AppState> = app.state();
+
+    // URL・カスタムプロトコルはプロセスを追跡できない
+    let (is_url, detached_path) = {
+        let config = lock_config(&state);
+        let registered_app = config.registered_apps.iter().find(|a| a.id == app_id);
+        (
+            registered_app.is_some_and(|a| is_url_path(&a.path)),
+            registered_app
+                .filter(|a| a.detached)
+                .map(|a| a.path.clone()),
+        )
+    };
+    if is_url {
+        return false;
+    }
+
+    let Some((process_key, pid)) = find_tracked_process(&state, &app_id) else {
+        // 切り離して起動したアプリはランチャーを再起動した後も動いていることがあるため実行ファイルのパスで探す
+        return detached_path.is_some_and(|path| !find_pids_by_executable_path(&path).is_empty());
+    };
+
+    // プロセスの確認には時間がかかるためロックを外して行う
+    let alive = is_tracked_process_alive(&state, &app_id, &process_key, pid);
+
+    if !alive {
+        remove_stale_process_entry(&app, &app_id, &process_key, pid);
+    }
+    alive
+}
+
+// アプリの実行状態（停止中・実行中・一時停止中）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ApplicationState {
+    Stopped,
+    Running,
+    Suspended,
+}
+
+// アプリの実行状態を取得（is_application_running と異なり、一時停止中を区別する）
+#[tauri::command]
+async fn get_application_state(app: AppHandle, app_id: String) -> ApplicationState {
+    if !is_application_running(app.clone(), app_id.clone()).await {
+        return ApplicationState::Stopped;
+    }
+    let state: tauri::State<AppState> = app.state();
+    let suspended_pid = state.suspended_apps.lock_or_recover().get(&app_id).copied();
+    match (suspended_pid, find_tracked_process(&state, &app_id)) {
+        (Some(suspended_pid), Some((_, pid))) if suspended_pid == pid => {
+            ApplicationState::Suspended
+        }
+        _ => ApplicationState::Running,
+    }
+}
+
+// 終了済みのプロセスのエントリを削除（確認中に再起動されていれば残す）
+fn remove_stale_process_entry(app: &AppHandle, app_id: &str, process_key: &str, pid: u32) {
+    if remove_process_entry_if_unchanged(&app.state(), process_key, pid) {
+        save_runtime_state(app);
+        info!("Removed stale process entry for app_id: {}", app_id);
+    }
+}
+
+// 管理テーブルのエントリが確認した時のPIDのままなら削除し、削除したかを返す
+fn remove_process_entry_if_unchanged(state: &AppState, process_key: &str, pid: u32) -> bool {
+    let mut processes = lock_processes(state);
+    if processes.get(process_key) != Some(&pid) {
+        return false;
+    }
+    processes.remove(process_key);
+    true
+}
+
+// 管理中のすべてのアプリのIDとPIDを取得（終了済みのエントリは削除してから返す）
+// プロセス名で管理しているアプリも「:name」を除いたIDで返す（Windowsの管理者実行はPIDが分からないため0）
+#[tauri::command]
+async fn get_running_processes(app: AppHandle) -> Result<Vec<(String, u32)>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let entries: Vec<(String, u32)> = lock_processes(&state)
+        .iter()
+        .map(|(process_key, &pid)| (process_key.clone(), pid))
+        .collect();
+
+    // プロセスの確認には時間がかかるためロックを外して行う
+    let mut running = Vec::new();
+    for (process_key, pid) in entries {
+        let app_id = process_key
+            .strip_suffix(":name")
+            .unwrap_or(&process_key)
+            .to_string();
+        if is_tracked_process_alive(&state, &app_id, &process_key, pid) {
+            running.push((app_id, pid));
+        } else {
+            remove_stale_process_entry(&app, &app_id, &process_key, pid);
+        }
+    }
+    update_tray_status(&app);
+    running.sort();
+    Ok(running)
+}
+
+// 管理テーブルの1つのエントリ（reconcile_running_state の結果）
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessEntry {
+    pub app_id: String,
+    // 管理テーブルのキー（プロセス名で管理している場合は「:name」が付く）
+    pub process_key: String,
+    pub pid: u32,
+}
+
+// 管理テーブルを確かめ直した結果
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ReconcileReport {
+    // 終了していたため取り除いたエントリ
+    pub removed: Vec<ProcessEntry>,
+    // 動作していたため残したエントリ
+    pub remaining: Vec<ProcessEntry>,
+}
+
+// 管理テーブルのすべてのエントリの生存を確かめ、終了していたものを取り除く
+// （終了の監視・起動時の確認で取りこぼしたエントリを手動で片付ける。ロックは確認ごとに短く取る）
+#[tauri::command]
+async fn reconcile_running_state(app: AppHandle) -> Result<ReconcileReport, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || reconcile_process_entries(&app, |_| true))
+        .await
+        .map_err(CommandError::background_task)
+}
+
+// 管理テーブルのプロセス名のマーカー（「:name」）だけを確かめ、プロセスが見つからないものを取り除く
+// （プロセスは実行ファイルのパスで探す。取り除いたエントリを返す）
+#[tauri::command]
+async fn clean_name_markers(app: AppHandle) -> Result<Vec<ProcessEntry>, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        reconcile_process_entries(&app, |process_key| process_key.ends_with(":name")).removed
+    })
+    .await
+    .map_err(CommandError::background_task)
+}
+
+// 管理テーブルのエントリ（include で選んだもの）の生存を確かめ、終了していたものを取り除く
+fn reconcile_process_entries(app: &AppHandle, include: impl Fn(&str) -> bool) -> ReconcileReport {
+    let report = prune_process_entries(&app.state(), include);
+    if !report.removed.is_empty() {
+        save_runtime_state(app);
+    }
+    update_tray_status(app);
+    report
+}
+
+// 管理テーブルのエントリ（include で選んだもの）のうち、終了していたものを取り除く（保存・トレイの更新はしない）
+fn prune_process_entries(state: &AppState, include: impl Fn(&str) -> bool) -> ReconcileReport {
+    let entries: Vec<(String, u32)> = lock_processes(state)
+        .iter()
+        .filter(|(process_key, _)| include(process_key))
+        .map(|(process_key, &pid)| (process_key.clone(), pid))
+        .collect();
+
+    let mut report = ReconcileReport::default();
+    for (process_key, pid) in entries {
+        let entry = ProcessEntry {
+            app_id: process_key.trim_end_matches(":name").to_string(),
+            process_key,
+            pid,
+        };
+        if is_tracked_process_alive(state, &entry.app_id, &entry.process_key, pid) {
+            report.remaining.push(entry);
+            continue;
+        }
+        // 確認中に起動し直された場合は残す
+        if !remove_process_entry_if_unchanged(state, &entry.process_key, pid) {
+            continue;
+        }
+        let mut suspended_apps = state.suspended_apps.lock_or_recover();
+        if suspended_apps.get(&entry.app_id) == Some(&pid) {
+            suspended_apps.remove(&entry.app_id);
+        }
+        drop(suspended_apps);
+        info!(
+            "Removed stale process entry {} (pid={})",
+            entry.process_key, pid
+        );
+        report.removed.push(entry);
+    }
+
+    report
+        .remaining
+        .sort_by(|a, b| a.process_key.cmp(&b.process_key));
+    report
+        .removed
+        .sort_by(|a, b| a.process_key.cmp(&b.process_key));
+    report
+}
+
+// 自動再起動の監視タスクを開始（既に監視中なら何もしない）
+fn ensure_restart_monitor(app: &AppHandle, app_id: &str) {
+    let state: tauri::State<AppState> = app.state();
+    if !state
+        .restart_monitors
+        .lock_or_recover()
+        .insert(app_id.to_string())
+    {
+        return;
+    }
+
+    let app = app.clone();
+    let app_id = app_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        monitor_and_restart(&app, &app_id).await;
+        let state: tauri::State<AppState> = app.state();
+        state.restart_monitors.lock_or_recover().remove(&app_id);
+    });
+}
+
+// 自動停止したとしてフロントエンドへ送るイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct AutoStoppedPayload {
+    app_id: String,
+    // 起動してから停止するまでの時間（auto_stop_after_ms）
+    after_ms: u64,
+    // 停止に失敗した場合のエラー
+    error: Option<String>,
+}
+
+// 起動してから一定時間後にアプリを停止する
+// 起動し直した・停止した場合は予約が置き換わる・消えるため何もしない（自動再起動で起動し直した場合は停止する）
+fn schedule_auto_stop(app: &AppHandle, app_id: &str, after: Duration) {
+    let state: tauri::State<AppState> = app.state();
+    let scheduled_at = Instant::now();
+    state
+        .auto_stops
+        .lock_or_recover()
+        .insert(app_id.to_string(), scheduled_at);
+    info!("Scheduled auto-stop of app_id={} in {:?}", app_id, after);
+
+    let app = app.clone();
+    let app_id = app_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(after).await;
+        let state: tauri::State<AppState> = app.state();
+        {
+            let mut auto_stops = state.auto_stops.lock_or_recover();
+            if auto_stops.get(&app_id) != Some(&scheduled_at) {
+                return;
+            }
+            auto_stops.remove(&app_id);
+        }
+        if !is_application_running(app.clone(), app_id.clone()).await {
+            return;
+        }
+
+        // stop_application で停止したものとして扱うため、自動再起動はされない
+        info!("Auto-stopping app_id={} after {:?}", app_id, after);
+        let result = stop_application(app.clone(), app_id.clone()).await;
+        if let Err(e) = &result {
+            error!("Failed to auto-stop {}: {}", app_id, e);
+        }
+        let payload = AutoStoppedPayload {
+            app_id,
+            after_ms: after.as_millis() as u64,
+            error: result.err().map(String::from),
+        };
+        if let Err(e) = app.emit("app-auto-stopped", payload) {
+            error!("Failed to emit app-auto-stopped: {}", e);
+        }
+    });
+}
+
+// 起動直後の終了を繰り返しているとしてフロントエンドへ送るイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct FlappingDetectedPayload {
+    app_id: String,
+    // 終了するまでに動作していた時間（ミリ秒）
+    runtime_ms: u64,
+    attempts: u32,
+    // 再起動を諦めた
+    gave_up: bool,
+}
+
+// 自動再起動の通知をまとめる（終了と再起動を繰り返すアプリで通知が続かないよう、1つのアプリにつき
+// restart_notification_interval_ms に1回まで。その間の再起動は回数だけを次の通知で知らせる）
+#[derive(Default)]
+struct RestartNotifier {
+    name: String,
+    // まだ通知していない再起動の回数
+    pending: u32,
+    last_notified: Option<tokio::time::Instant>,
+}
+
+impl RestartNotifier {
+    fn record(&mut self, app: &AppHandle, name: &str) {
+        self.name = name.to_string();
+        self.pending += 1;
+        self.flush(app, false);
+    }
+
+    // 前回の通知から間隔が空いていれば（force の場合は常に）まとめて通知する
+    fn flush(&mut self, app: &AppHandle, force: bool) {
+        if self.pending == 0 {
+            return;
+        }
+        let interval = Duration::from_millis(
+            lock_config(&app.state::<AppState>()).restart_notification_interval_ms,
+        );
+        let now = tokio::time::Instant::now();
+        let due = self
+            .last_notified
+            .is_none_or(|last| now.duration_since(last) >= interval);
+        if !force && !due {
+            return;
+        }
+        let title = if self.pending == 1 {
+            tr(app, "notify.restarted", &[("name", &self.name)])
+        } else {
+            let window = self
+                .last_notified
+                .map_or(interval, |last| now.duration_since(last));
+            tr(
+                app,
+                "notify.restarted_many",
+                &[
+                    ("name", &self.name),
+                    ("count", &self.pending.to_string()),
+                    ("seconds", &window.as_secs().max(1).to_string()),
+                ],
+            )
+        };
+        notify(app, &title, "");
+        self.pending = 0;
+        self.last_notified = Some(now);
+    }
+}
+
+// 自動再起動の監視（監視を終える前に、まとめて待っていた再起動の通知を出す）
+async fn monitor_and_restart(app: &AppHandle, app_id: &str) {
+    let mut notifier = RestartNotifier::default();
+    watch_and_restart(app, app_id, &mut notifier).await;
+    notifier.flush(app, true);
+}
+
+// プロセスの終了を監視し、意図しない終了であればバックオフしながら再起動
+// （min_healthy_runtime_ms より早く終了した場合は、バックオフを速く伸ばして少ない回数で諦める）
+async fn watch_and_restart(app: &AppHandle, app_id: &str, notifier: &mut RestartNotifier) {
+    let mut attempts: u32 = 0;
+    let mut alive_since = tokio::time::Instant::now();
+
+    loop {
+        tokio::time::sleep(AUTO_RESTART_POLL_INTERVAL).await;
+        notifier.flush(app, false);
+
+        let state: tauri::State<AppState> = app.state();
+        if state
+            .intentionally_stopped
+            .lock_or_recover()
+            .contains(app_id)
+        {
+            return;
+        }
+        let Some(registered_app) = lock_config(&state)
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id && a.auto_restart)
+            .cloned()
+        else {
+            // 削除された・自動再起動が無効になった場合は監視を終了
+            return;
+        };
+
+        let alive = match find_tracked_process(&state, app_id) {
+            Some((process_key, pid)) => is_tracked_process_alive(&state, app_id, &process_key, pid),
+            None => false,
+        };
+        if alive {
+            // 一定時間安定して動作していれば再試行回数をリセット
+            if attempts > 0 && alive_since.elapsed() >= AUTO_RESTART_STABLE_PERIOD {
+                attempts = 0;
+            }
+            continue;
+        }
+
+        {
+            let mut processes = lock_processes(&state);
+            processes.remove(app_id);
+            processes.remove(&format!("{}:name", app_id));
+        }
+        save_runtime_state(app);
+
+        // 監視の間隔があるため、動作していた時間は最大で監視の間隔だけ長めになる
+        let runtime = alive_since.elapsed();
+        let min_runtime = Duration::from_millis(registered_app.min_healthy_runtime_ms);
+        let flapping = !min_runtime.is_zero() && runtime < min_runtime;
+        let max_retries = if flapping {
+            AUTO_RESTART_FLAPPING_MAX_RETRIES
+        } else {
+            AUTO_RESTART_MAX_RETRIES
+        };
+        if flapping {
+            warn!(
+                "{} exited after {:?}, before min_healthy_runtime_ms ({:?})",
+                registered_app.name, runtime, min_runtime
+            );
+            let payload = FlappingDetectedPayload {
+                app_id: app_id.to_string(),
+                runtime_ms: runtime.as_millis() as u64,
+                attempts,
+                gave_up: attempts >= max_retries,
+            };
+            if let Err(e) = app.emit("flapping-detected", payload) {
+                error!("Failed to emit flapping-detected: {}", e);
+            }
+        }
+
+        if attempts >= max_retries {
+            warn!(
+                "Giving up auto-restart of {} after {} attempts",
+                registered_app.name, attempts
+            );
+            notifier.flush(app, true);
+            notify(
+                app,
+                &tr(
+                    app,
+                    "notify.restart_gave_up",
+                    &[("name", &registered_app.name)],
+                ),
+                "",
+            );
+            return;
+        }
+
+        // 起動直後の失敗は 1, 4, 16... 秒と速く伸ばす
+        let backoff_secs = if flapping {
+            1 << (attempts * 2)
+        } else {
+            1 << attempts
+        };
+        let backoff = AUTO_RESTART_MAX_BACKOFF.min(Duration::from_secs(backoff_secs));
+        attempts += 1;
+        info!(
+            "{} exited unexpectedly; restarting in {:?} (attempt {}/{})",
+            registered_app.name, backoff, attempts, max_retries
+        );
+        tokio::time::sleep(backoff).await;
+
+        if state
+            .intentionally_stopped
+            .lock_or_recover()
+            .contains(app_id)
+        {
+            return;
+        }
+        let result = spawn_application(
+            app.clone(),
+            registered_app.id.clone(),
+            registered_app.path.clone(),
+            registered_app.arguments.clone(),
+        )
+        .await;
+        match result {
+            Ok(_) => {
+                info!("Restarted {}", registered_app.name);
+                alive_since = tokio::time::Instant::now();
+                notifier.record(app, &registered_app.name);
+            }
+            Err(e) => error!("Failed to restart {}: {}", registered_app.name, e),
+        }
+    }
+}
+
+// アプリケーションを再起動（停止を確認してから起動し直す）
+#[tauri::command]
+async fn restart_application(app: AppHandle, app_id: String) -> Result<(), CommandError> {
+    let registered_app = {
+        let state: tauri::State<AppState> = app.state();
+        let config = lock_config(&state);
+        config
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .cloned()
+            .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?
+    };
+
+    if is_application_running(app.clone(), app_id.clone()).await {
+        let tracked = find_tracked_process(&app.state::<AppState>(), &app_id);
+        stop_application(app.clone(), app_id.clone()).await?;
+
+        // プロセスが実際に終了するまで待機
+        if let Some((process_key, pid)) = tracked {
+            let deadline = tokio::time::Instant::now() + RESTART_STOP_TIMEOUT;
+            while is_tracked_process_alive(&app.state::<AppState>(), &app_id, &process_key, pid) {
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(CommandError::new(
+                        ErrorCode::Timeout,
+                        format!("Timed out waiting for {} to stop", registered_app.name),
+                    ));
+                }
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+        }
+
+        let delay = launch_delay(&registered_app);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    launch_application(
+        app,
+        app_id,
+        registered_app.path,
+        registered_app.arguments,
+        None,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+// アプリごとのログファイルのパスを取得
+#[tauri::command]
+fn get_app_log_path(app: AppHandle, id: String) -> Result<String, CommandError> {
+    let log_path = app_log_file_path(&app, &id)?;
+    Ok(log_path.to_string_lossy().to_string())
+}
+
+// ランチャー自身のログファイルを開く
+#[tauri::command]
+fn open_log_file(app: AppHandle) -> Result<(), CommandError> {
+    let log_dir = match portable_log_dir() {
+        Some(log_dir) => log_dir,
+        None => app
+            .path()
+            .app_log_dir()
+            .map_err(|e| format!("Failed to get app log dir: {}", e))?,
+    };
+    let log_path = log_dir.join(format!("{}.log", LAUNCHER_LOG_FILE_NAME));
+    if !log_path.exists() {
+        return Err(CommandError::new(
+            ErrorCode::NotFound,
+            format!("Log file not found: {}", log_path.display()),
+        ));
+    }
+    app.opener()
+        .open_path(log_path.to_string_lossy(), None::<&str>)
+        .map_err(|e| CommandError::new(ErrorCode::Io, format!("Failed to open log file: {}", e)))
+}
+
+// アプリのインスタンスが実行中か確認（管理中のプロセスに加え、ランチャー以外から起動されたプロセスも探す）
+async fn is_app_instance_running(app: &AppHandle, registered_app: &RegisteredApp) -> bool {
+    if is_url_path(&registered_app.path) {
+        return false;
+    }
+    is_application_running(app.clone(), registered_app.id.clone()).await
+        || is_process_name_running(&app.state(), registered_app)
+}
+
+// 起動前の待機時間（delay_ms があれば delay より優先し、0..delay_jitter_ms のランダムな時間を加える）
+fn launch_delay(registered_app: &RegisteredApp) -> Duration {
+    let base = base_launch_delay(registered_app);
+    let jitter = match registered_app.delay_jitter_ms {
+        0 => 0,
+        max => rand::random_range(0..max),
+    };
+    base + Duration::from_millis(jitter)
+}
+
+// ランダムな時間を含まない起動までの遅延（delay_ms が指定されていれば優先）
+fn base_launch_delay(registered_app: &RegisteredApp) -> Duration {
+    registered_app
+        .delay_ms
+        .map(Duration::from_millis)
+        .unwrap_or_else(|| Duration::from_secs(registered_app.delay))
+}
+
+// 登録された全アプリケーションを起動（自動起動用）
+#[tauri::command]
+async fn launch_startup_apps(app: AppHandle) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+
+    // 次回の自動起動をスキップする設定・メンテナンスの期間中の場合は、どのアプリも起動しない
+    let suppressed = {
+        let mut config = lock_config(&state);
+        if config.suppress_next_startup {
+            config.suppress_next_startup = false;
+            if let Err(e) = save_config(&app, &config) {
+                error!("Failed to clear the startup suppression: {}", e);
+            }
+            Some("Startup launches were suppressed once".to_string())
+        } else {
+            active_maintenance_window(&config, chrono::Local::now().naive_local())
+                .map(|end| format!("Maintenance window is active until {}", end))
+        }
+    };
+    if let Some(reason) = suppressed {
+        info!("Skipped launching startup apps: {}", reason);
+        notify(&app, &tr(&app, "notify.startup_suppressed", &[]), &reason);
+        return Ok(());
+    }
+
+    let config = lock_config(&state).clone();
+
+    // 依存関係を考慮した起動順を決定（循環があればエラー）
+    let launch_order = match resolve_startup_order(&config.registered_apps) {
+        Ok(order) => order,
+        Err(e) => {
+            notify(&app, &tr(&app, "notify.startup_failed", &[]), &e);
+            return Err(e.into());
+        }
+    };
+
+    let launch_order: Vec<RegisteredApp> = launch_order.into_iter().cloned().collect();
+    let config = Arc::new(config);
+    let total = launch_order.len();
+
+    let mut summary = Vec::with_capacity(total);
+    if config.sequential_startup {
+        for (index, registered_app) in launch_order.into_iter().enumerate() {
+            summary.push(
+                launch_startup_app(app.clone(), registered_app, config.clone(), (index, total))
+                    .await,
+            );
+        }
+    } else {
+        // アプリごとのタスクで起動し、遅延を重ねて待つ（依存関係は wait_for_dependencies で待つ）
+        let tasks: Vec<_> = launch_order
+            .into_iter()
+            .enumerate()
+            .map(|(index, registered_app)| {
+                let (app_id, name) = (registered_app.id.clone(), registered_app.name.clone());
+                let task = tauri::async_runtime::spawn(launch_startup_app(
+                    app.clone(),
+                    registered_app,
+                    config.clone(),
+                    (index, total),
+                ));
+                (app_id, name, task)
+            })
+            .collect();
+        for (app_id, name, task) in tasks {
+            match task.await {
+                Ok(entry) => summary.push(entry),
+                Err(e) => {
+                    error!("Startup task for {} failed: {}", name, e);
+                    summary.push(StartupSummaryEntry {
+                        app_id,
+                        name,
+                        status: StartupSummaryStatus::Failed,
+                        detail: Some(e.to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    if config.show_startup_summary {
+        if let Err(e) = app.emit("startup-summary", summary) {
+            error!("Failed to emit startup-summary: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+// 自動起動の結果（startup-summary イベントでアプリごとに送る）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum StartupSummaryStatus {
+    Launched,
+    Skipped,
+    Failed,
+}
+
+// 自動起動したアプリ1つの結果
+#[derive(Debug, Serialize, Clone)]
+struct StartupSummaryEntry {
+    app_id: String,
+    name: String,
+    status: StartupSummaryStatus,
+    // スキップした理由・失敗したエラー（起動した場合は None）
+    detail: Option<String>,
+}
+
+// 自動起動の進み具合（待機中・起動中・起動した・失敗した・スキップした）
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum StartupPhase {
+    Waiting,
+    Launching,
+    Done,
+    Failed,
+    Skipped,
+}
+
+// 自動起動の進み具合としてフロントエンドへ送るイベントの内容
+#[derive(Debug, Serialize, Clone)]
+struct StartupProgressPayload {
+    // 起動順での位置（0から）と自動起動するアプリの数
+    index: usize,
+    total: usize,
+    app_id: String,
+    phase: StartupPhase,
+    // 起動までの残りの遅延（ミリ秒。待機中以外は0）
+    remaining_delay_ms: u64,
+}
+
+// 自動起動の進み具合をフロントエンドへ通知
+fn emit_startup_progress(
+    app: &AppHandle,
+    (index, total): (usize, usize),
+    app_id: &str,
+    phase: StartupPhase,
+    remaining_delay: Duration,
+) {
+    let payload = StartupProgressPayload {
+        index,
+        total,
+        app_id: app_id.to_string(),
+        phase,
+        remaining_delay_ms: remaining_delay.as_millis() as u64,
+    };
+    if let Err(e) = app.emit("startup-progress", payload) {
+        error!("Failed to emit startup-progress: {}", e);
+    }
+}
+
+// 自動起動アプリを1つ起動（依存アプリの起動と遅延を待ってから起動し、失敗はログに記録する）
+// position は起動順での位置と自動起動するアプリの数（進み具合の通知用）
+async fn launch_startup_app(
+    app: AppHandle,
+    registered_app: RegisteredApp,
+    config: Arc<AppConfig>,
+    position: (usize, usize),
+) -> StartupSummaryEntry {
+    let delay = launch_delay(&registered_app);
+    let progress = |phase, remaining_delay| {
+        emit_startup_progress(&app, position, &registered_app.id, phase, remaining_delay)
+    };
+    let entry = |status, detail: Option<String>| StartupSummaryEntry {
+        app_id: registered_app.id.clone(),
+        name: registered_app.name.clone(),
+        status,
+        detail,
+    };
+    progress(StartupPhase::Waiting, delay);
+
+    // 依存アプリが起動済みになるまで待機
+    if let Err(e) = wait_for_dependencies(&app, &registered_app, &config).await {
+        progress(StartupPhase::Skipped, Duration::ZERO);
+        warn!("Skipped launching {}: {}", registered_app.name, e);
+        notify(
+            &app,
+            &tr(
+                &app,
+                "notify.startup_skipped",
+                &[("name", &registered_app.name)],
+            ),
+            &e,
+        );
+        return entry(StartupSummaryStatus::Skipped, Some(e));
+    }
+
+    // OSの起動後に既に起動している場合はスキップ（ランチャーを起動し直した場合など）
+    if registered_app.launch_once_per_boot
+        && app
+            .state::<AppState>()
+            .launched_this_boot
+            .lock_or_recover()
+            .contains(&registered_app.id)
+    {
+        info!(
+            "Skipped launching {}: already launched since the current boot",
+            registered_app.name
+        );
+        progress(StartupPhase::Skipped, Duration::ZERO);
+        return entry(
+            StartupSummaryStatus::Skipped,
+            Some("Already launched since the current boot".to_string()),
+        );
+    }
+
+    // 起動の条件を満たさない場合はスキップ（エラーにはしない）
+    if let Some(launch_condition) = &registered_app.launch_condition {
+        if let Err(reason) = condition::evaluate(launch_condition).await {
+            info!(
+                "Skipped launching {}: launch condition not met ({})",
+                registered_app.name, reason
+            );
+            progress(StartupPhase::Skipped, Duration::ZERO);
+            return entry(
+                StartupSummaryStatus::Skipped,
+                Some(format!("Launch condition not met: {}", reason)),
+            );
+        }
+    }
+
+    // 既に実行中なら起動しない設定の場合はスキップ
+    if registered_app.skip_if_running && is_app_instance_running(&app, &registered_app).await {
+        info!(
+            "Skipped launching {}: already running (skip_if_running)",
+            registered_app.name
+        );
+        progress(StartupPhase::Skipped, Duration::ZERO);
+        return entry(
+            StartupSummaryStatus::Skipped,
+            Some("Already running".to_string()),
+        );
+    }
+
+    let app_id = registered_app.id.clone();
+    let path = registered_app.path.clone();
+    let arguments = registered_app.arguments.clone();
+    let prevent_duplicate = registered_app.prevent_duplicate;
+    let app_handle_clone = app.clone();
+
+    // 重複起動禁止が有効な場合、既存プロセスを停止（URLは対象外）
+    if prevent_duplicate && !is_url_path(&path) {
+        info!("Preventing duplicate launch for: {}", registered_app.name);
+
+        stop_existing_instances(&app.state(), &path);
+    }
+
+    // 遅延がある場合は待機（依存アプリなどを待った後なので、残りの遅延を改めて通知する）
+    if !delay.is_zero() {
+        progress(StartupPhase::Waiting, delay);
+        tokio::time::sleep(delay).await;
+    }
+
+    // アプリケーションを起動
+    progress(StartupPhase::Launching, Duration::ZERO);
+    let result = launch_application(app_handle_clone, app_id, path, arguments, None, None).await;
+    match result {
+        Ok(_) => {
+            progress(StartupPhase::Done, Duration::ZERO);
+            entry(StartupSummaryStatus::Launched, None)
+        }
+        Err(e) => {
+            error!("Failed to launch {}: {}", registered_app.name, e);
+            progress(StartupPhase::Failed, Duration::ZERO);
+            entry(StartupSummaryStatus::Failed, Some(e.to_string()))
+        }
+    }
+}
+
+// 重複起動を防ぐため、同じ実行ファイルの既存のプロセスを停止（同名の別プロセスは対象外）
+fn stop_existing_instances(state: &AppState, path: &str) {
+    match state
+        .launcher
+        .kill(KillTarget::ExecutablePath(path.to_string()))
+    {
+        Ok(count) if count > 0 => {
+            info!("Stopped {} existing process(es) for: {}", count, path)
+        }
+        Ok(_) => {}
+        Err(e) => error!("Failed to stop existing {}: {}", path, e),
+    }
+}
+
+// 自動起動アプリの起動順を依存関係から決定（依存先は自動起動でなくても先に起動する）
+fn resolve_startup_order(apps: &[RegisteredApp]) -> Result<Vec<&RegisteredApp>, String> {
+    resolve_dependency_order(apps, apps.iter().filter(|a| a.auto_start && a.enabled))
+}
+
+// roots のアプリとその依存先の起動順を決定（依存関係が循環している場合はエラー）
+fn resolve_dependency_order<'a>(
+    apps: &'a [RegisteredApp],
+    roots: impl Iterator<Item = &'a RegisteredApp>,
+) -> Result<Vec<&'a RegisteredApp>, String> {
+    fn visit<'a>(
+        app: &'a RegisteredApp,
+        apps: &'a [RegisteredApp],
+        visiting: &mut Vec<&'a str>,
+        visited: &mut HashSet<&'a str>,
+        order: &mut Vec<&'a RegisteredApp>,
+    ) -> Result<(), String> {
+        if visited.contains(app.id.as_str()) {
+            return Ok(());
+        }
+        if let Some(start) = visiting.iter().position(|id| *id == app.id) {
+            let names: Vec<&str> = visiting[start..]
+                .iter()
+                .chain(std::iter::once(&app.id.as_str()))
+                .map(|id| {
+                    apps.iter()
+                        .find(|a| a.id == *id)
+                        .map(|a| a.name.as_str())
+                        .unwrap_or(id)
+                })
+                .collect();
+            return Err(format!("Dependency cycle detected: {}", names.join(" -> ")));
+        }
+
+        visiting.push(&app.id);
+        for dependency_id in &app.depends_on {
+            match apps.iter().find(|a| a.id == *dependency_id) {
+                Some(dependency) => visit(dependency, apps, visiting, visited, order)?,
+                None => warn!(
+                    "Unknown dependency {} for {}, ignoring",
+                    dependency_id, app.name
+                ),
+            }
+        }
+        visiting.pop();
+
+        visited.insert(&app.id);
+        order.push(app);
+        Ok(())
+    }
+
+    let mut visiting = Vec::new();
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for app in roots {
+        visit(app, apps, &mut visiting, &mut visited, &mut order)?;
+    }
+    Ok(order)
+}
+
+// 依存アプリがすべて起動中になるまで待機
+async fn wait_for_dependencies(
+    app: &AppHandle,
+    registered_app: &RegisteredApp,
+    config: &AppConfig,
+) -> Result<(), String> {
+    for dependency_id in &registered_app.depends_on {
+        let Some(dependency) = config
+            .registered_apps
+            .iter()
+            .find(|a| a.id == *dependency_id)
+        else {
+            continue;
+        };
+        // URLは起動状態を確認できないため開いた時点で起動済みとみなす
+        if !is_url_path(&dependency.path) {
+            // 同時に起動する場合は依存アプリの遅延の分も長く待つ
+            let timeout = DEPENDENCY_WAIT_TIMEOUT
+                + base_launch_delay(dependency)
+                + Duration::from_millis(dependency.delay_jitter_ms);
+            let started = std::time::Instant::now();
+            while !is_application_running(app.clone(), dependency_id.clone()).await {
+                if started.elapsed() >= timeout {
+                    return Err(format!(
+                        "dependency {} did not start within {} seconds",
+                        dependency.name,
+                        timeout.as_secs()
+                    ));
+                }
+                tokio::time::sleep(DEPENDENCY_POLL_INTERVAL).await;
+            }
+        }
+
+        // ヘルスチェックがある場合は使える状態になるまで待つ
+        if let Some(health_check) = &dependency.health_check {
+            health::check(health_check)
+                .await
+                .map_err(|e| format!("dependency {} is not healthy: {}", dependency.name, e))?;
+        }
+    }
+    Ok(())
+}
+
+// アプリのヘルスチェックを実行（設定されていない場合は Unknown）
+#[tauri::command]
+async fn check_app_health(app: AppHandle, app_id: String) -> Result<HealthStatus, CommandError> {
+    let (name, health_check) = {
+        let state: tauri::State<AppState> = app.state();
+        let config = lock_config(&state);
+        let registered_app = config
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+        (
+            registered_app.name.clone(),
+            registered_app.health_check.clone(),
+        )
+    };
+    let Some(health_check) = health_check else {
+        return Ok(HealthStatus::Unknown);
+    };
+    match health::check(&health_check).await {
+        Ok(()) => Ok(HealthStatus::Healthy),
+        Err(e) => {
+            warn!("Health check for {} failed: {}", name, e);
+            Ok(HealthStatus::Unhealthy)
+        }
+    }
+}
+
+// プロファイル一覧を取得（既定のプロファイルが先頭）
+#[tauri::command]
+fn list_profiles(app: AppHandle) -> Result<Vec<String>, CommandError> {
+    let mut profiles: Vec<String> =
+        match std::fs::read_dir(get_config_dir(&app).join(PROFILES_DIR_NAME)) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().into_owned()))
+                .filter(|name| name != DEFAULT_PROFILE && validate_profile_name(name).is_ok())
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read profiles: {}", e).into()),
+        };
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(profiles)
+}
+
+// 使用中のプロファイル名を取得
+#[tauri::command]
+fn get_active_profile(app: AppHandle) -> String {
+    let state: tauri::State<AppState> = app.state();
+    let profile = state.active_profile.lock_or_recover().clone();
+    profile
+}
+
+// PowerShellが使えるか（ポリシーで無効にされた環境向け。起動後に1回だけ確認して結果を使い回す）
+// 使えない場合、起動は CreateProcess、停止は taskkill だけで行う
+#[cfg(target_os = "windows")]
+fn powershell_available() -> bool {
+    static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        let mut command = Command::new("powershell");
+        command
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-WindowStyle",
+                "Hidden",
+                "-Command",
+                "exit 0",
+            ])
+            .creation_flags(CREATE_NO_WINDOW); // コンソールウィンドウを表示しない
+        let available = output_with_timeout(command, LAUNCH_TIMEOUT)
+            .is_ok_and(|output| output.status.success());
+        if !available {
+            warn!(
+                "PowerShell is unavailable, falling back to CreateProcess for launches and taskkill for stops"
+            );
+        }
+        available
+    })
+}
+
+// PowerShellを使えない場合に制限される機能
+#[derive(Debug, Serialize, Clone)]
+pub struct PowerShellStatus {
+    pub available: bool,
+    // PowerShellが必要なOS（Windows）で使えず、一部の機能が制限されている
+    pub degraded: bool,
+    pub limitations: Vec<String>,
+}
+
+// PowerShellを使えるかと、使えない場合に制限される機能を取得
+#[tauri::command]
+async fn get_powershell_status() -> Result<PowerShellStatus, CommandError> {
+    // Windows以外ではPowerShellを使わない
+    #[cfg(not(target_os = "windows"))]
+    return Ok(PowerShellStatus {
+        available: false,
+        degraded: false,
+        limitations: Vec::new(),
+    });
+
+    #[cfg(target_os = "windows")]
+    {
+        let available = tauri::async_runtime::spawn_blocking(powershell_available)
+            .await
+            .map_err(|e| e.to_string())?;
+        let limitations = if available {
+            Vec::new()
+        } else {
+            [
+                "Run as administrator is not available",
+                "Shortcuts (.lnk) and non-executable files cannot be launched",
+                "Exit codes of processes not started by the launcher are not available",
+                "Importing from the Start menu is not available",
+            ]
+            .map(str::to_string)
+            .to_vec()
+        };
+        Ok(PowerShellStatus {
+            available,
+            degraded: !available,
+            limitations,
+        })
+    }
+}
+
+// 使用中の設定ファイルの情報
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigInfo {
+    pub path: String,
+    pub exists: bool,
+    pub size_bytes: u64,
+    // 最終更新日時（RFC 3339。ファイルがない場合は None）
+    pub modified: Option<String>,
+    // 使用中のプロファイル名（既定のプロファイル以外がない場合は None）
+    pub profile: Option<String>,
+}
+
+// 使用中の設定ファイルのパス・サイズ・最終更新日時を取得（ポータブルモードなどで保存場所が変わるため）
+#[tauri::command]
+fn get_config_info(app: AppHandle) -> Result<ConfigInfo, CommandError> {
+    let path = get_config_path(&app);
+    let metadata = match std::fs::metadata(&path) {
+        Ok(metadata) => Some(metadata),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e).into()),
+    };
+    let modified = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.modified().ok())
+        .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339());
+
+    let active_profile = get_active_profile(app.clone());
+    let profile = (active_profile != DEFAULT_PROFILE || list_profiles(app)?.len() > 1)
+        .then_some(active_profile);
+
+    Ok(ConfigInfo {
+        path: path.to_string_lossy().into_owned(),
+        exists: metadata.is_some(),
+        size_bytes: metadata.as_ref().map_or(0, |metadata| metadata.len()),
+        modified,
+        profile,
+    })
+}
+
+// 不具合の報告用の環境・状態のまとめ（run_diagnostics の結果）
+#[derive(Debug, Serialize, Clone)]
+pub struct Diagnostics {
+    pub launcher_version: String,
+    // "windows"・"macos"・"linux" など
+    pub os: String,
+    // OSの名前とバージョン（取得できない場合は None）
+    pub os_version: Option<String>,
+    pub arch: String,
+    pub powershell: PowerShellStatus,
+    pub config: ConfigInfo,
+    // 設定ファイル（ない場合は設定ディレクトリ）が読み取り専用になっていないか
+    pub config_writable: bool,
+    pub safe_mode: bool,
+    pub registered_apps: usize,
+    pub running_apps: usize,
+    pub autostart: AutostartStatus,
+    pub validation_issues: Vec<ValidationIssue>,
+}
+
+// 環境と状態をまとめて取得（不具合の報告に貼り付ける用）
+// 設定・管理テーブルは変更せず、書き込めるかもファイルの属性だけで判断する
+#[tauri::command]
+async fn run_diagnostics(app: AppHandle) -> Result<Diagnostics, CommandError> {
+    let powershell = get_powershell_status().await?;
+    let config = get_config_info(app.clone())?;
+    let config_path = get_config_path(&app);
+    let config_writable = std::fs::metadata(&config_path)
+        .or_else(|_| std::fs::metadata(get_config_dir(&app)))
+        .is_ok_and(|metadata| !metadata.permissions().readonly());
+
+    let state: tauri::State<AppState> = app.state();
+    let registered_apps = lock_config(&state).registered_apps.len();
+    let running_apps = running_app_count(&state);
+
+    Ok(Diagnostics {
+        launcher_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_version: sysinfo::System::long_os_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        powershell,
+        config,
+        config_writable,
+        safe_mode: get_safe_mode(app.clone()),
+        registered_apps,
+        running_apps,
+        autostart: get_autostart_status(app.clone())?,
+        validation_issues: validate_config(app)?,
+    })
+}
+
+// 空のプロファイルを作成
+#[tauri::command]
+fn create_profile(app: AppHandle, name: String) -> Result<(), CommandError> {
+    let name = name.trim();
+    validate_profile_name(name).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    let config_path = profile_config_path(&app, name);
+    if name == DEFAULT_PROFILE || config_path.exists() {
+        return Err(CommandError::new(
+            ErrorCode::AlreadyExists,
+            format!("Profile '{}' already exists", name),
+        ));
+    }
+
+    std::fs::create_dir_all(get_config_dir(&app).join(PROFILES_DIR_NAME))
+        .map_err(|e| format!("Failed to create profiles dir: {}", e))?;
+    let config_str =
+        serde_json::to_string_pretty(&AppConfig::default()).map_err(|e| e.to_string())?;
+    write_file_atomically(&config_path, &config_str)?;
+
+    info!("Created profile {}", name);
+    Ok(())
+}
+
+// プロファイルを切り替え（stop_running が true なら実行中のアプリを停止してから）
+#[tauri::command]
+async fn switch_profile(
+    app: AppHandle,
+    name: String,
+    stop_running: bool,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    let name = name.trim().to_string();
+    validate_profile_name(&name).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    if !profile_config_path(&app, &name).exists() && name != DEFAULT_PROFILE {
+        return Err(CommandError::new(
+            ErrorCode::ConfigNotFound,
+            format!("Profile '{}' not found", name),
+        ));
+    }
+
+    // 停止は終了を待つため、非同期のワーカーを止めないよう別のスレッドで行う
+    if stop_running {
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || stop_all_applications(&app_handle))
+            .await
+            .map_err(CommandError::background_task)?;
+    }
+
+    // 切り替え中に古い設定が新しいプロファイルへ保存されないよう設定をロックしておく
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    *state.active_profile.lock_or_recover() = name.clone();
+    let (loaded, warning) = load_config(&app);
+    *config = loaded;
+    sync_autostart_if_changed(&app, &config);
+    let registered_apps = config.registered_apps.clone();
+    drop(config);
+    // 履歴は切り替え前のプロファイルの内容のため破棄する
+    state.config_history.lock_or_recover().clear();
+
+    if let Some(warning) = warning {
+        notify(&app, &tr(&app, "notify.config_load_failed", &[]), &warning);
+    }
+
+    if let Err(e) =
+        write_file_atomically(&get_config_dir(&app).join(ACTIVE_PROFILE_FILE_NAME), &name)
+    {
+        error!("Failed to save active profile: {}", e);
+    }
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!("Switched to profile {}", name);
+    Ok(registered_apps)
+}
+
+// プロファイルを削除（既定のプロファイルと使用中のプロファイルは削除できない）
+#[tauri::command]
+fn delete_profile(app: AppHandle, name: String) -> Result<(), CommandError> {
+    let name = name.trim();
+    validate_profile_name(name).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    if name == DEFAULT_PROFILE {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "The default profile cannot be deleted",
+        ));
+    }
+    let state: tauri::State<AppState> = app.state();
+    if *state.active_profile.lock_or_recover() == name {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "The active profile cannot be deleted",
+        ));
+    }
+
+    let config_path = profile_config_path(&app, name);
+    if !config_path.exists() {
+        return Err(CommandError::new(
+            ErrorCode::ConfigNotFound,
+            format!("Profile '{}' not found", name),
+        ));
+    }
+    std::fs::remove_file(&config_path).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Io,
+            format!("Failed to delete profile '{}': {}", name, e),
+        )
+    })?;
+
+    info!("Deleted profile {}", name);
+    Ok(())
+}
+
+// 使用中のプロファイルのアプリを別のプロファイルへ移動する（copy が true の場合は新しいIDで複製し、元のアプリは残す）
+// 実行中のアプリを移動した場合、プロセスは移動前のプロファイルで起動したものとして管理し続ける
+#[tauri::command]
+fn move_app_to_profile(
+    app: AppHandle,
+    app_id: String,
+    target_profile: String,
+    copy: bool,
+) -> Result<(), CommandError> {
+    let target_profile = target_profile.trim().to_string();
+    validate_profile_name(&target_profile)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    let state: tauri::State<AppState> = app.state();
+    let active_profile = state.active_profile.lock_or_recover().clone();
+    if target_profile == active_profile {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("The app is already in profile '{}'", target_profile),
+        ));
+    }
+    let target_path = profile_config_path(&app, &target_profile);
+    if !target_path.exists() && target_profile != DEFAULT_PROFILE {
+        return Err(CommandError::new(
+            ErrorCode::ConfigNotFound,
+            format!("Profile '{}' not found", target_profile),
+        ));
+    }
+
+    let mut config = lock_config(&state);
+    let mut moved_app = config
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .cloned()
+        .ok_or_else(|| CommandError::new(ErrorCode::AppNotFound, "Application not found"))?;
+
+    // 移動先の設定ファイルが壊れている場合は上書きしない
+    let mut target_config: AppConfig = if target_path.exists() {
+        let contents = std::fs::read_to_string(&target_path)
+            .map_err(|e| format!("Failed to read profile '{}': {}", target_profile, e))?;
+        parse_config(&contents)
+            .map_err(|e| format!("Invalid config in profile '{}': {}", target_profile, e))?
+    } else {
+        AppConfig::default()
+    };
+
+    if copy {
+        moved_app.id = uuid::Uuid::new_v4().to_string();
+        moved_app.launch_count = 0;
+        moved_app.last_launched = None;
+    } else if target_config.registered_apps.iter().any(|a| a.id == app_id) {
+        return Err(CommandError::new(
+            ErrorCode::AlreadyExists,
+            format!(
+                "An app with id {} already exists in profile '{}'",
+                app_id, target_profile
+            ),
+        ));
+    }
+    check_duplicate_name(&target_config, &moved_app.name, None)
+        .map_err(|e| CommandError::new(ErrorCode::DuplicateName, e))?;
+
+    // 移動先にない依存先は起動の順番を決められないため外す
+    moved_app.depends_on.retain(|dependency_id| {
+        let exists = target_config
+            .registered_apps
+            .iter()
+            .any(|a| &a.id == dependency_id);
+        if !exists {
+            warn!(
+                "Dropping dependency {} of {}: not in profile '{}'",
+                dependency_id, moved_app.name, target_profile
+            );
+        }
+        exists
+    });
+    moved_app.sort_order = target_config
+        .registered_apps
+        .iter()
+        .map(|a| a.sort_order + 1)
+        .max()
+        .unwrap_or(0);
+    let name = moved_app.name.clone();
+    target_config.registered_apps.push(moved_app);
+
+    if let Some(dir) = target_path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create profiles dir: {}", e))?;
+    }
+    let target_str = serde_json::to_string_pretty(&target_config).map_err(|e| e.to_string())?;
+    write_file_atomically(&target_path, &target_str)?;
+
+    if copy {
+        info!("Copied {} to profile {}", name, target_profile);
+        return Ok(());
+    }
+
+    config.registered_apps.retain(|a| a.id != app_id);
+    save_config(&app, &config)?;
+    drop(config);
+
+    if lock_processes(&state).contains_key(&app_id) {
+        warn!(
+            "{} was moved to profile {} while running; it stays tracked under profile {}",
+            name, target_profile, active_profile
+        );
+    }
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!("Moved {} to profile {}", name, target_profile);
+    Ok(())
+}
+
+// 起動グループ一覧を取得
+#[tauri::command]
+fn get_launch_groups(app: AppHandle) -> Result<Vec<LaunchGroup>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    Ok(config.launch_groups.clone())
+}
+
+// 起動グループを追加
+#[tauri::command]
+fn add_launch_group(
+    app: AppHandle,
+    name: String,
+    app_ids: Vec<String>,
+    max_concurrent: Option<usize>,
+) -> Result<LaunchGroup, CommandError> {
+    if max_concurrent == Some(0) {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "max_concurrent must be at least 1",
+        ));
+    }
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    let new_group = LaunchGroup {
+        id: uuid::Uuid::new_v4().to_string(),
+        name,
+        app_ids,
+        max_concurrent,
+    };
+
+    config.launch_groups.push(new_group.clone());
+    save_config(&app, &config)?;
+
+    Ok(new_group)
+}
+
+// 起動グループを削除
+#[tauri::command]
+fn remove_launch_group(app: AppHandle, id: String) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+
+    config.launch_groups.retain(|g| g.id != id);
+    save_config(&app, &config)?;
+
+    Ok(())
+}
+
+// 起動グループのアプリを起動（各アプリの遅延時間を反映し、ヘルスチェックがあれば通るまで待つ）
+// max_concurrent がない場合は1つずつ順番に、ある場合はその数まで同時に起動する
+#[tauri::command]
+async fn launch_group(app: AppHandle, group_id: String) -> Result<(), CommandError> {
+    let (group, registered_apps) = {
+        let state: tauri::State<AppState> = app.state();
+        let config = lock_config(&state);
+        let group = config
+            .launch_groups
+            .iter()
+            .find(|g| g.id == group_id)
+            .cloned()
+            .ok_or_else(|| CommandError::new(ErrorCode::GroupNotFound, "Launch group not found"))?;
+        (group, config.registered_apps.clone())
+    };
+
+    let mut targets = Vec::new();
+    for app_id in &group.app_ids {
+        // 削除済みのアプリはスキップ
+        let Some(registered_app) = registered_apps.iter().find(|a| &a.id == app_id) else {
+            warn!(
+                "Skipping missing app {} in launch group {}",
+                app_id, group.name
+            );
+            continue;
+        };
+        if !registered_app.enabled {
+            info!(
+                "Skipping disabled app {} in launch group {}",
+                registered_app.name, group.name
+            );
+            continue;
+        }
+        targets.push(registered_app.clone());
+    }
+
+    let mut failed = Vec::new();
+    match group.max_concurrent {
+        None => {
+            for registered_app in &targets {
+                let delay = launch_delay(registered_app);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                if let Err(e) = launch_group_app(&app, registered_app).await {
+                    failed.push(e);
+                }
+            }
+        }
+        Some(limit) => {
+            // 遅延はグループの起動から数え、待ち終わった順に枠が空くのを待って起動する
+            let semaphore = Arc::new(tokio::sync::Semaphore::new(limit.max(1)));
+            let tasks: Vec<_> = targets
+                .into_iter()
+                .map(|registered_app| {
+                    let app = app.clone();
+                    let semaphore = semaphore.clone();
+                    let name = registered_app.name.clone();
+                    let task = tauri::async_runtime::spawn(async move {
+                        let delay = launch_delay(&registered_app);
+                        if !delay.is_zero() {
+                            tokio::time::sleep(delay).await;
+                        }
+                        let _permit = semaphore
+                            .acquire()
+                            .await
+                            .map_err(|e| format!("{} ({})", registered_app.name, e))?;
+                        launch_group_app(&app, &registered_app).await
+                    });
+                    (name, task)
+                })
+                .collect();
+            for (name, task) in tasks {
+                match task.await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => failed.push(e),
+                    Err(e) => {
+                        error!("Launch task for {} failed: {}", name, e);
+                        failed.push(name);
+                    }
+                }
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CommandError::new(
+            ErrorCode::LaunchFailed,
+            format!("Failed to launch: {}", failed.join(", ")),
+        ))
+    }
+}
+
+// 起動グループのアプリを1つ起動し、ヘルスチェックがあれば通るまで待つ（失敗した場合は表示用の名前を返す）
+async fn launch_group_app(app: &AppHandle, registered_app: &RegisteredApp) -> Result<(), String> {
+    let result = launch_application(
+        app.clone(),
+        registered_app.id.clone(),
+        registered_app.path.clone(),
+        registered_app.arguments.clone(),
+        None,
+        None,
+    )
+    .await;
+    if let Err(e) = result {
+        error!("Failed to launch {}: {}", registered_app.name, e);
+        return Err(registered_app.name.clone());
+    }
+
+    // ヘルスチェックがある場合は使える状態になるまで起動中として扱う
+    if let Some(health_check) = &registered_app.health_check {
+        if let Err(e) = health::check(health_check).await {
+            error!("{} did not become healthy: {}", registered_app.name, e);
+            return Err(format!("{} (not healthy)", registered_app.name));
+        }
+    }
+    Ok(())
+}
+
+// 監視パスの変更を検知したアプリを起動
+fn handle_watch_trigger(app: &AppHandle, app_id: &str) {
+    let state: tauri::State<AppState> = app.state();
+    if state.watchers_paused.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let config = lock_config(&state);
+    let Some(registered_app) = config
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .cloned()
+    else {
+        return;
+    };
+    drop(config);
+
+    // 前回の起動がまだ実行中なら、設定で許可されていない限り再起動しない
+    if !registered_app.watch_relaunch_while_running {
+        let processes = lock_processes(&state);
+        if processes.contains_key(app_id) || processes.contains_key(&format!("{}:name", app_id)) {
+            info!(
+                "Skipping watch-triggered launch of {}: still running",
+                registered_app.name
+            );
+            return;
+        }
+    }
+
+    info!("Watch path changed, launching: {}", registered_app.name);
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = launch_application(
+            app_handle,
+            registered_app.id.clone(),
+            registered_app.path.clone(),
+            registered_app.arguments.clone(),
+            None,
+            None,
+        )
+        .await;
+        if let Err(e) = result {
+            error!("Failed to launch {}: {}", registered_app.name, e);
+        }
+    });
+}
+
+// 監視パス用のウォッチャーを作成（イベントはデバウンスしてから起動）
+fn create_file_watcher(
+    app: &AppHandle,
+    app_id: &str,
+    watch_path: &str,
+) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::Watcher;
+
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(Path::new(watch_path), notify::RecursiveMode::Recursive)?;
+
+    // ウォッチャーが破棄されると送信側も破棄され、スレッドは終了する
+    let app_handle = app.clone();
+    let app_id = app_id.to_string();
+    std::thread::spawn(move || {
+        while let Ok(event) = rx.recv() {
+            if event.is_err() {
+                continue;
+            }
+            // 一定時間イベントが途切れるまで待つ
+            loop {
+                match rx.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(_) => continue,
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            handle_watch_trigger(&app_handle, &app_id);
+        }
+    });
+
+    Ok(watcher)
+}
+
+// 2つのパスが同じファイルを指しているか確認（シンボリックリンクなどで表記が異なる場合も含む）
+fn is_same_path(a: &Path, b: &Path) -> bool {
+    if a == b {
+        return true;
+    }
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// 設定ディレクトリを監視し、使用中の設定ファイルが外部で変更されたら読み込み直す
+// （保存は一時ファイルからの置き換えで行うため、ファイルではなくディレクトリを監視する）
+fn create_config_watcher(app: &AppHandle) -> notify::Result<notify::RecommendedWatcher> {
+    use notify::Watcher;
+
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&get_config_dir(app), notify::RecursiveMode::Recursive)?;
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        while let Ok(event) = rx.recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            let config_path = get_config_path(&app_handle);
+            if !event.paths.iter().any(|p| is_same_path(p, &config_path)) {
+                continue;
+            }
+            // 一定時間イベントが途切れるまで待つ
+            loop {
+                match rx.recv_timeout(WATCH_DEBOUNCE) {
+                    Ok(_) => continue,
+                    Err(mpsc::RecvTimeoutError::Timeout) => break,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            reload_config_from_disk(&app_handle);
+        }
+    });
+
+    Ok(watcher)
+}
+
+// 設定ファイルが外部で変更されていれば読み込み直し、フロントエンドへ通知
+fn reload_config_from_disk(app: &AppHandle) {
+    if let Err(e) = flush_config(app) {
+        error!("Failed to save config before reloading it: {}", e);
+    }
+    let config_path = get_config_path(app);
+    let Ok(contents) = std::fs::read_to_string(&config_path) else {
+        return;
+    };
+
+    // 自分で保存した内容と同じ・書き込み待ちの新しい内容がある場合は何もしない
+    // （保存による再読み込みの繰り返しや、古い内容での上書きを防ぐ）
+    let state: tauri::State<AppState> = app.state();
+    if state.last_saved_config.lock_or_recover().as_deref() == Some(contents.as_str())
+        || state.pending_config_write.lock_or_recover().is_some()
+    {
+        return;
+    }
+
+    // 編集途中などで読み込めない場合は現在の設定を維持する
+    let new_config = match parse_config(&contents) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!(
+                "Ignoring invalid config change in {}: {}",
+                config_path.display(),
+                e
+            );
+            return;
+        }
+    };
+
+    apply_reloaded_config(app, new_config, Some(contents));
+    info!("Reloaded config from {}", config_path.display());
+}
+
+// 読み込み直した設定を反映（実行中のプロセスの管理テーブルには触れない）
+fn apply_reloaded_config(app: &AppHandle, new_config: AppConfig, contents: Option<String>) {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    *config = new_config;
+    sync_autostart_if_changed(app, &config);
+    drop(config);
+    *state.last_saved_config.lock_or_recover() = contents;
+
+    sync_file_watchers(app);
+    sync_hotkeys(app);
+    rebuild_tray_menu(app);
+    apply_always_on_top(app);
+
+    if let Err(e) = app.emit("config-reloaded", ()) {
+        error!("Failed to emit config-reloaded: {}", e);
+    }
+}
+
+// 設定ファイルを読み込み直し、登録されたアプリの一覧を返す
+// （読み込めない場合は現在の設定を維持してエラーを返す。壊れたファイルは .bak に退避される）
+#[tauri::command]
+fn reload_config(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    let (new_config, warning) = load_config(&app);
+    if let Some(warning) = warning {
+        return Err(warning.into());
+    }
+    let contents = std::fs::read_to_string(get_config_path(&app)).ok();
+    let mut registered_apps = new_config.registered_apps.clone();
+    registered_apps.sort_by_key(|a| a.sort_order);
+    apply_reloaded_config(&app, new_config, contents);
+    info!("Reloaded config on request");
+    Ok(registered_apps)
+}
+
+// 直前の設定の変更を元に戻し、戻した後のアプリの一覧を返す
+#[tauri::command]
+fn undo_last_change(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    restore_config_history(&app, true).map_err(CommandError::from)
+}
+
+// 元に戻した変更をやり直し、やり直した後のアプリの一覧を返す
+#[tauri::command]
+fn redo_last_change(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    restore_config_history(&app, false).map_err(CommandError::from)
+}
+
+// 履歴の内容に設定を戻す（undo が false ならやり直す）
+// 起動回数・最終起動日時は履歴ではなく現在の値を残す
+fn restore_config_history(app: &AppHandle, undo: bool) -> Result<Vec<RegisteredApp>, String> {
+    let state: tauri::State<AppState> = app.state();
+    // 保存時は設定 → 履歴の順にロックするため、設定のロックを外してから履歴をロックする
+    let current = lock_config(&state).clone();
+    let mut history = state.config_history.lock_or_recover();
+    let snapshot = if undo {
+        history.undo.pop_back()
+    } else {
+        history.redo.pop()
+    }
+    .ok_or_else(|| {
+        if undo {
+            "Nothing to undo".to_string()
+        } else {
+            "Nothing to redo".to_string()
+        }
+    })?;
+
+    let mut restored: AppConfig = serde_json::from_str(&snapshot)
+        .map_err(|e| format!("Failed to restore the previous config: {}", e))?;
+    for registered_app in &mut restored.registered_apps {
+        if let Some(current_app) = current
+            .registered_apps
+            .iter()
+            .find(|a| a.id == registered_app.id)
+        {
+            registered_app.launch_count = current_app.launch_count;
+            registered_app.last_launched = current_app.last_launched.clone();
+        }
+    }
+
+    let current_str = serde_json::to_string_pretty(&current).map_err(|e| e.to_string())?;
+    write_config(app, &restored, false)?;
+    if undo {
+        history.redo.push(current_str);
+    } else {
+        history.undo.push_back(current_str);
+    }
+    drop(history);
+
+    let contents = state.last_saved_config.lock_or_recover().clone();
+    let mut registered_apps = restored.registered_apps.clone();
+    registered_apps.sort_by_key(|a| a.sort_order);
+    apply_reloaded_config(app, restored, contents);
+    info!(
+        "{} the last config change",
+        if undo { "Undid" } else { "Redid" }
+    );
+    Ok(registered_apps)
+}
+
+// 設定の保存場所
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigLocation {
+    // OSのユーザーごとの設定ディレクトリ
+    User,
+    // 実行ファイルと同じ場所の portable ディレクトリ
+    Portable,
+}
+
+// 移行先に設定ファイルが既にある場合の扱い
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigConflict {
+    // 何もせずにエラーを返す（フロントエンドで確認してから再送する）
+    #[default]
+    Abort,
+    // 移行元の設定で上書きする
+    Overwrite,
+    // 移行元の設定に、移行先にだけあるアプリ・グループを加える
+    Merge,
+}
+
+// ディレクトリ内のファイルを再帰的に列挙（ディレクトリからの相対パス。存在しない場合は空）
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+    files
+}
+
+// 移行元の設定に、移行先にだけあるアプリ・グループを加えた設定を作る
+fn merge_configs(mut base: AppConfig, existing: AppConfig) -> Result<AppConfig, String> {
+    let known_ids: HashSet<String> = base.registered_apps.iter().map(|a| a.id.clone()).collect();
+    let mut added: Vec<RegisteredApp> = existing
+        .registered_apps
+        .into_iter()
+        .filter(|a| !known_ids.contains(&a.id))
+        .collect();
+    validate_imported_apps(&mut added, known_ids)?;
+    let next_order = base
+        .registered_apps
+        .iter()
+        .map(|a| a.sort_order + 1)
+        .max()
+        .unwrap_or(0);
+    for (offset, registered_app) in added.iter_mut().enumerate() {
+        registered_app.sort_order = next_order + offset as i64;
+    }
+    base.registered_apps.extend(added);
+
+    let group_ids: HashSet<String> = base.launch_groups.iter().map(|g| g.id.clone()).collect();
+    base.launch_groups.extend(
+        existing
+            .launch_groups
+            .into_iter()
+            .filter(|g| !group_ids.contains(&g.id)),
+    );
+    Ok(base)
+}
+
+// 設定（プロファイル・ウィンドウの状態を含む）とアプリのログを別の保存場所へ移し、移行先の設定を読み込み直す
+// 移行元のファイルはコピーした内容を確認してから削除する
+// --config-dir・AJIPONZU_CONFIG_DIR で指定されている場合は、次回の起動でも指定が優先されるため移行しない
+#[tauri::command]
+fn migrate_config_location(
+    app: AppHandle,
+    target: ConfigLocation,
+    on_conflict: Option<ConfigConflict>,
+) -> Result<PathBuf, CommandError> {
+    if let Some(dir) = explicit_config_dir() {
+        return Err(CommandError::new(
+            ErrorCode::Unsupported,
+            format!(
+                "The config dir is set by {} or {} ({}); remove it before moving the config",
+                CONFIG_DIR_ARG,
+                CONFIG_DIR_ENV,
+                dir.display()
+            ),
+        ));
+    }
+
+    // 書き込みを待っている内容を移行元に書き込んでから移す
+    flush_config(&app)?;
+    let source_dir = get_config_dir(&app);
+    let source_log_dir = app_log_dir(&app)?;
+    let (target_dir, target_log_dir) = match target {
+        ConfigLocation::User => (
+            app.path()
+                .app_config_dir()
+                .map_err(|e| format!("Failed to get app config dir: {}", e))?,
+            user_app_log_dir(&app)?,
+        ),
+        ConfigLocation::Portable => {
+            let dir = portable_dir_path()
+                .ok_or_else(|| "Failed to get the executable's directory".to_string())?;
+            let log_dir = dir.join("logs");
+            (dir, log_dir)
+        }
+    };
+    if is_same_path(&source_dir, &target_dir) {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("The config is already stored in {}", target_dir.display()),
+        ));
+    }
+
+    let target_config_path = target_dir.join("config.json");
+    let on_conflict = on_conflict.unwrap_or_default();
+    if target_config_path.exists() && on_conflict == ConfigConflict::Abort {
+        // フロントエンドは ALREADY_EXISTS で上書き・マージを確認する
+        return Err(CommandError::new(
+            ErrorCode::AlreadyExists,
+            format!("{}: {}", CONFIG_EXISTS_ERROR, target_config_path.display()),
+        ));
+    }
+    check_dir_writable(&target_dir)
+        .map_err(|e| CommandError::new(ErrorCode::PermissionDenied, e))?;
+
+    // 移行するファイル（移行元, 移行先）。ポータブルモードのディレクトリにあるログ・アイコンは設定としては移さない
+    let mut files: Vec<(PathBuf, PathBuf)> = [
+        ACTIVE_PROFILE_FILE_NAME,
+        HISTORY_FILE_NAME,
+        tauri_plugin_window_state::DEFAULT_FILENAME,
+    ]
+    .iter()
+    .map(|name| (source_dir.join(name), target_dir.join(name)))
+    .chain(
+        list_files(&source_dir.join(PROFILES_DIR_NAME))
+            .into_iter()
+            .map(|relative| {
+                (
+                    source_dir.join(PROFILES_DIR_NAME).join(&relative),
+                    target_dir.join(PROFILES_DIR_NAME).join(relative),
+                )
+            }),
+    )
+    .chain(list_files(&source_log_dir).into_iter().map(|relative| {
+        (
+            source_log_dir.join(&relative),
+            target_log_dir.join(relative),
+        )
+    }))
+    .filter(|(source, _)| source.is_file())
+    .collect();
+
+    // config.json は使用中の設定から書き出す（マージする場合は移行先の設定と合わせる）
+    let state: tauri::State<AppState> = app.state();
+    let source_config_path = source_dir.join("config.json");
+    let mut config = match std::fs::read_to_string(&source_config_path) {
+        Ok(contents) => parse_config(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", source_config_path.display(), e))?,
+        Err(_) => AppConfig::default(),
+    };
+    if target_config_path.exists() && on_conflict == ConfigConflict::Merge {
+        let existing: AppConfig = std::fs::read_to_string(&target_config_path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| parse_config(&contents).map_err(|e| e.to_string()))
+            .map_err(|e| format!("Failed to read {}: {}", target_config_path.display(), e))?;
+        config = merge_configs(config, existing)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+        // 移行先にあるプロファイルは残す
+        files.retain(|(_, target)| {
+            !(target.starts_with(target_dir.join(PROFILES_DIR_NAME)) && target.exists())
+        });
+    }
+    let config_contents = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    // コピーして内容が一致するか確認する（失敗した場合は移行元を残したまま中断する）
+    for (source, target) in &files {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::copy(source, target)
+            .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+        let verified = matches!(
+            (std::fs::read(source), std::fs::read(target)),
+            (Ok(a), Ok(b)) if a == b
+        );
+        if !verified {
+            return Err(format!(
+                "Copied file does not match the original: {}",
+                target.display()
+            )
+            .into());
+        }
+    }
+    // 書き込み途中で失敗しても移行先に壊れた config.json を残さないよう、一時ファイルから置き換える
+    write_file_atomically(&target_config_path, &config_contents).map_err(|e| {
+        CommandError::new(
+            ErrorCode::Io,
+            format!("Failed to write {}: {}", target_config_path.display(), e),
+        )
+    })?;
+    if std::fs::read_to_string(&target_config_path).ok().as_deref()
+        != Some(config_contents.as_str())
+    {
+        return Err(format!(
+            "Written config does not match: {}",
+            target_config_path.display()
+        )
+        .into());
+    }
+
+    // 保存場所を切り替えて読み込み直す
+    *PORTABLE_DIR.lock_or_recover() = match target {
+        ConfigLocation::User => None,
+        ConfigLocation::Portable => Some(target_dir.clone()),
+    };
+    let (new_config, warning) = load_config(&app);
+    if let Some(warning) = warning {
+        warn!("Config moved with a warning: {}", warning);
+    }
+    let contents = std::fs::read_to_string(get_config_path(&app)).ok();
+    apply_reloaded_config(&app, new_config, contents);
+    match create_config_watcher(&app) {
+        Ok(watcher) => *state.config_watcher.lock_or_recover() = Some(watcher),
+        Err(e) => error!("Failed to watch config dir: {}", e),
+    }
+
+    // 確認が済んだので移行元を削除する（使用中のログは削除できない場合がある）
+    for source in files
+        .iter()
+        .map(|(source, _)| source)
+        .chain(std::iter::once(&source_config_path))
+    {
+        if let Err(e) = std::fs::remove_file(source) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Failed to remove {}: {}", source.display(), e);
+            }
+        }
+    }
+    for dir in [
+        source_dir.join(PROFILES_DIR_NAME),
+        source_log_dir,
+        source_dir.clone(),
+    ] {
+        // 空になったディレクトリだけ削除する
+        let _ = std::fs::remove_dir(dir);
+    }
+
+    info!(
+        "Moved config from {} to {}",
+        source_dir.display(),
+        target_dir.display()
+    );
+    Ok(target_dir)
+}
+
+// 設定に合わせてファイル監視を開始・停止
+fn sync_file_watchers(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state);
+    let wanted: HashMap<String, String> = config
+        .registered_apps
+        .iter()
+        .filter_map(|a| {
+            a.watch_path
+                .as_ref()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| (a.id.clone(), p.clone()))
+        })
+        .collect();
+    drop(config);
+
+    let mut watchers = state.file_watchers.lock_or_recover();
+
+    // 不要になった・パスが変わった監視を停止
+    watchers.retain(|app_id, (watch_path, _)| wanted.get(app_id) == Some(watch_path));
+
+    for (app_id, watch_path) in wanted {
+        if watchers.contains_key(&app_id) {
+            continue;
+        }
+        match create_file_watcher(app, &app_id, &watch_path) {
+            Ok(watcher) => {
+                info!("Watching {} for app_id: {}", watch_path, app_id);
+                watchers.insert(app_id, (watch_path, watcher));
+            }
+            Err(e) => error!("Failed to watch {}: {}", watch_path, e),
+        }
+    }
+}
+
+// スケジュール文字列を解析（"HH:MM" は毎日その時刻、それ以外は5項目の cron 式）
+fn parse_schedule(schedule: &str) -> Result<Cron, String> {
+    let schedule = schedule.trim();
+    let daily_time = schedule.split_once(':').and_then(|(hour, minute)| {
+        let hour = hour.parse::<u32>().ok().filter(|h| *h < 24)?;
+        let minute = (minute.len() == 2)
+            .then(|| minute.parse::<u32>().ok())
+            .flatten()
+            .filter(|m| *m < 60)?;
+        Some((hour, minute))
+    });
+    let pattern = match daily_time {
+        Some((hour, minute)) => format!("{} {} * * *", minute, hour),
+        None => schedule.to_string(),
+    };
+    Cron::from_str(&pattern).map_err(|e| format!("Invalid schedule '{}': {}", schedule, e))
+}
+
+// メンテナンスの期間の日時を解析（日付だけの場合はその日の 00:00）
+fn parse_maintenance_time(value: &str) -> Result<chrono::NaiveDateTime, String> {
+    let value = value.trim();
+    ["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| {
+            format!(
+                "Invalid maintenance time '{}' (expected YYYY-MM-DD HH:MM or YYYY-MM-DD)",
+                value
+            )
+        })
+}
+
+// メンテナンスの期間の確認（開始が終了より前であること）
+fn validate_maintenance_window(window: &MaintenanceWindow) -> Result<(), String> {
+    if parse_maintenance_time(&window.start)? >= parse_maintenance_time(&window.end)? {
+        return Err("Maintenance window start must be before its end".to_string());
+    }
+    Ok(())
+}
+
+// メンテナンスの期間中であれば終了日時を返す（解析できない期間は無視する）
+fn active_maintenance_window(config: &AppConfig, now: chrono::NaiveDateTime) -> Option<String> {
+    let window = config.maintenance_window.as_ref()?;
+    let start = parse_maintenance_time(&window.start).ok()?;
+    let end = parse_maintenance_time(&window.end).ok()?;
+    (start <= now && now < end).then(|| window.end.trim().to_string())
+}
+
+// 空でないスケジュールが解析できるか確認
+fn validate_schedule(schedule: &Option<String>) -> Result<(), String> {
+    match schedule.as_deref().map(str::trim) {
+        Some(schedule) if !schedule.is_empty() => parse_schedule(schedule).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+// 毎分スケジュールを確認し、該当するアプリを起動する
+// - 設定は毎回読み直すため、設定の変更はそのまま次の確認から反映される
+// - 現地時刻で判定するため、夏時間の開始で飛ばされた時刻は起動しない
+// - 同じ「日付・時:分」では一度しか起動しない（夏時間の終了で同じ時刻が繰り返される場合も含む）
+async fn run_scheduler(app: AppHandle) {
+    let mut last_launched: HashMap<String, String> = HashMap::new();
+
+    loop {
+        // 次の分の境目まで待機
+        let now = chrono::Local::now();
+        let until_next_minute = SCHEDULER_TICK
+            .saturating_sub(Duration::from_secs(now.second() as u64))
+            .saturating_sub(Duration::from_nanos(now.nanosecond() as u64));
+        tokio::time::sleep(until_next_minute.max(Duration::from_millis(1))).await;
+
+        let Some(now) = chrono::Local::now()
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+        else {
+            continue;
+        };
+        let minute_key = now.format("%Y-%m-%dT%H:%M").to_string();
+
+        let state: tauri::State<AppState> = app.state();
+        let (registered_apps, maintenance_end) = {
+            let config = lock_config(&state);
+            (
+                config.registered_apps.clone(),
+                active_maintenance_window(&config, now.naive_local()),
+            )
+        };
+        last_launched.retain(|app_id, _| registered_apps.iter().any(|a| &a.id == app_id));
+
+        for registered_app in registered_apps.iter().filter(|a| a.enabled) {
+            let Some(schedule) = registered_app
+                .schedule
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+            else {
+                continue;
+            };
+            let matches = match parse_schedule(schedule) {
+                Ok(cron) => cron.is_time_matching(&now).unwrap_or(false),
+                Err(e) => {
+                    warn!("Skipping schedule for {}: {}", registered_app.name, e);
+                    false
+                }
+            };
+            if !matches || last_launched.get(&registered_app.id) == Some(&minute_key) {
+                continue;
+            }
+            last_launched.insert(registered_app.id.clone(), minute_key.clone());
+
+            if let Some(end) = &maintenance_end {
+                info!(
+                    "Maintenance window: skipped scheduled launch of app_id={} (until {})",
+                    registered_app.id, end
+                );
+                continue;
+            }
+            info!(
+                "Scheduled launch for app_id={} ({})",
+                registered_app.id, schedule
+            );
+            spawn_launch_by_id(&app, &registered_app.id);
+        }
+    }
+}
+
+// 設定に合わせてグローバルホットキーを登録し直す
+fn sync_hotkeys(app: &AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    let registered_apps = lock_config(&state).registered_apps.clone();
+
+    let global_shortcut = app.global_shortcut();
+    if let Err(e) = global_shortcut.unregister_all() {
+        error!("Failed to unregister hotkeys: {}", e);
+    }
+
+    let mut hotkeys = state.hotkeys.lock_or_recover();
+    hotkeys.clear();
+
+    for registered_app in &registered_apps {
+        let Some(hotkey) = registered_app
+            .hotkey
+            .as_deref()
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+        else {
+            continue;
+        };
+
+        let shortcut: Shortcut = match hotkey.parse() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                warn!(
+                    "Invalid hotkey {} for {}: {}",
+                    hotkey, registered_app.name, e
+                );
+                continue;
+            }
+        };
+
+        // 同じ組み合わせは最初に登録したアプリを優先
+        if let Some(owner_id) = hotkeys.get(&shortcut.id()) {
+            let owner = registered_apps
+                .iter()
+                .find(|a| &a.id == owner_id)
+                .map(|a| a.name.as_str())
+                .unwrap_or(owner_id);
+            warn!(
+                "Hotkey {} for {} is already used by {}, skipping",
+                hotkey, registered_app.name, owner
+            );
+            continue;
+        }
+
+        match global_shortcut.register(shortcut) {
+            Ok(()) => {
+                info!("Registered hotkey {} for {}", hotkey, registered_app.name);
+                hotkeys.insert(shortcut.id(), registered_app.id.clone());
+            }
+            Err(e) => error!(
+                "Failed to register hotkey {} for {}: {}",
+                hotkey, registered_app.name, e
+            ),
+        }
+    }
+}
+
+// ホットキーを設定に保存せずに確かめる（編集画面で入力中に確認する用。保存すると sync_hotkeys で登録し直す）
+// 書式の誤り、他のアプリとの重複、他のアプリケーションが登録済みの場合にエラーを返す
+#[tauri::command]
+fn validate_hotkey(
+    app: AppHandle,
+    accelerator: String,
+    for_app_id: Option<String>,
+) -> Result<(), CommandError> {
+    let accelerator = accelerator.trim();
+    if accelerator.is_empty() {
+        return Ok(());
+    }
+
+    let shortcut: Shortcut = accelerator.parse().map_err(|e| {
+        CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid hotkey {}: {}", accelerator, e),
+        )
+    })?;
+
+    let state: tauri::State<AppState> = app.state();
+    {
+        let config = lock_config(&state);
+        let owner = config.registered_apps.iter().find(|a| {
+            Some(&a.id) != for_app_id.as_ref()
+                && a.hotkey
+                    .as_deref()
+                    .and_then(|h| h.trim().parse::<Shortcut>().ok())
+                    .is_some_and(|s| s.id() == shortcut.id())
+        });
+        if let Some(owner) = owner {
+            return Err(CommandError::new(
+                ErrorCode::AlreadyExists,
+                format!(
+                    "Hotkey {} is already assigned to {}",
+                    accelerator, owner.name
+                ),
+            ));
+        }
+    }
+
+    // ランチャーが登録していない組み合わせは、一時的に登録して他のアプリケーションが使っていないか確かめる
+    // （sync_hotkeys と重ならないよう、ホットキーの一覧をロックしたまま行う）
+    let hotkeys = state.hotkeys.lock_or_recover();
+    if hotkeys.contains_key(&shortcut.id()) {
+        return Ok(());
+    }
+    let global_shortcut = app.global_shortcut();
+    global_shortcut.register(shortcut).map_err(|e| {
+        format!(
+            "Hotkey {} cannot be registered (it may be used by another application): {}",
+            accelerator, e
+        )
+    })?;
+    if let Err(e) = global_shortcut.unregister(shortcut) {
+        warn!("Failed to unregister test hotkey {}: {}", accelerator, e);
+    }
+    drop(hotkeys);
+    Ok(())
+}
+
+// 2つのアプリの割り当てを入れ替えて保存し、ホットキーを登録し直す
+// （1つずつ更新すると途中で同じホットキーが2つのアプリに割り当てられた状態になるため、まとめて入れ替える）
+// kind は "hotkey" のみ対応（クイックスロットはアプリの項目にないため "slot" はエラー）
+#[tauri::command]
+fn swap_assignments(
+    app: AppHandle,
+    id_a: String,
+    id_b: String,
+    kind: String,
+) -> Result<(), CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    swap_app_assignments(&mut config, &id_a, &id_b, &kind)?;
+    save_config(&app, &config)?;
+    drop(config);
+
+    sync_hotkeys(&app);
+    Ok(())
+}
+
+// 設定上で2つのアプリの割り当てを入れ替える
+fn swap_app_assignments(
+    config: &mut AppConfig,
+    id_a: &str,
+    id_b: &str,
+    kind: &str,
+) -> Result<(), CommandError> {
+    if kind != "hotkey" {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "Unsupported assignment kind: {} (only \"hotkey\" can be swapped)",
+                kind
+            ),
+        ));
+    }
+    if id_a == id_b {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Cannot swap an application with itself",
+        ));
+    }
+    let position = |id: &str| {
+        config
+            .registered_apps
+            .iter()
+            .position(|a| a.id == id)
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::AppNotFound,
+                    format!("Application not found: {}", id),
+                )
+            })
+    };
+    let (a, b) = (position(id_a)?, position(id_b)?);
+    let hotkey_a = config.registered_apps[a].hotkey.take();
+    config.registered_apps[a].hotkey =
+        std::mem::replace(&mut config.registered_apps[b].hotkey, hotkey_a);
+    Ok(())
+}
+
+// ホットキーが押されたら対応するアプリを起動
+fn handle_hotkey_pressed(app: &AppHandle, shortcut: &Shortcut) {
+    let state: tauri::State<AppState> = app.state();
+    let app_id = state.hotkeys.lock_or_recover().get(&shortcut.id()).cloned();
+    if let Some(app_id) = app_id {
+        spawn_launch_by_id(app, &app_id);
+    }
+}
+
+// 登録アプリを ID で探してバックグラウンドで起動
+fn spawn_launch_by_id(app: &AppHandle, app_id: &str) {
+    let state: tauri::State<AppState> = app.state();
+    let Some(registered_app) = lock_config(&state)
+        .registered_apps
+        .iter()
+        .find(|a| a.id == app_id)
+        .cloned()
+    else {
+        warn!("Application not found: {}", app_id);
+        return;
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = launch_application(
+            app,
+            registered_app.id,
+            registered_app.path,
+            registered_app.arguments,
+            None,
+            None,
+        )
+        .await;
+        if let Err(e) = result {
+            error!("Failed to launch {}: {}", registered_app.name, e);
+        }
+    });
+}
+
+// ファイル監視による起動を一時停止
+#[tauri::command]
+fn pause_file_watchers(app: AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    state.watchers_paused.store(true, Ordering::SeqCst);
+}
+
+// ファイル監視による起動を再開
+#[tauri::command]
+fn resume_file_watchers(app: AppHandle) {
+    let state: tauri::State<AppState> = app.state();
+    state.watchers_paused.store(false, Ordering::SeqCst);
+}
+
+// ファイル選択ダイアログを開く
+#[tauri::command]
+fn open_file_dialog(app: AppHandle) -> Result<Option<String>, CommandError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let file_path = app
+        .dialog()
         .file()
         .add_filter("実行ファイル", &["exe"])
         .add_filter("ショートカット", &["lnk"])
         .add_filter("すべてのファイル", &["*"])
         .blocking_pick_file();
 
-    Ok(file_path.map(|p| p.to_string()))
+    Ok(file_path.map(|p| p.to_string()))
+}
+
+// 設定の確認で見つかった問題の重大度
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    // 起動に失敗する
+    Error,
+    // 起動はできるが意図しない動作になる可能性がある
+    Warning,
+}
+
+// 設定の確認で見つかった問題
+#[derive(Debug, Serialize, Clone)]
+pub struct ValidationIssue {
+    pub app_id: String,
+    // 問題のある項目（RegisteredApp のフィールド名）
+    pub field: String,
+    pub severity: ValidationSeverity,
+    pub message: String,
+}
+
+// 登録されているすべてのアプリの設定を確認し、見つかった問題をまとめて返す（設定は変更しない）
+#[tauri::command]
+fn validate_config(app: AppHandle) -> Result<Vec<ValidationIssue>, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state).clone();
+
+    let mut issues = Vec::new();
+    let mut hotkey_owners: HashMap<u32, &str> = HashMap::new();
+    for registered_app in &config.registered_apps {
+        let mut report = |field: &str, severity: ValidationSeverity, message: String| {
+            issues.push(ValidationIssue {
+                app_id: registered_app.id.clone(),
+                field: field.to_string(),
+                severity,
+                message,
+            })
+        };
+
+        if registered_app.name.trim().is_empty() {
+            report(
+                "name",
+                ValidationSeverity::Error,
+                "Name must not be empty".to_string(),
+            );
+        } else if let Err(e) =
+            check_duplicate_name(&config, &registered_app.name, Some(&registered_app.id))
+        {
+            report("name", ValidationSeverity::Warning, e);
+        }
+
+        if let Err(e) = validate_launch_target(&registered_app.path, registered_app.use_shell) {
+            report("path", ValidationSeverity::Error, e);
+        } else if !registered_app.use_shell && is_glob_path(&registered_app.path) {
+            // ワイルドカードは登録できても、今一致するファイルがなければ起動に失敗する
+            if let Err(e) = resolve_path_glob(&registered_app.path, registered_app.path_glob_pick) {
+                report("path", ValidationSeverity::Warning, e);
+            }
+        }
+        if let Err(e) = reject_control_characters("Arguments", &registered_app.arguments) {
+            report("arguments", ValidationSeverity::Error, e);
+        }
+        if let Err(e) =
+            reject_control_characters("Working directory", &registered_app.working_directory)
+        {
+            report("working_directory", ValidationSeverity::Error, e);
+        } else if !registered_app.working_directory.trim().is_empty()
+            && !Path::new(expand_env(&registered_app.working_directory).trim()).is_dir()
+        {
+            report(
+                "working_directory",
+                ValidationSeverity::Error,
+                format!(
+                    "Working directory not found: {}",
+                    registered_app.working_directory
+                ),
+            );
+        }
+
+        // 未定義の環境変数はそのまま渡されるため警告とする
+        let env_fields = [
+            ("path", registered_app.path.as_str()),
+            ("arguments", registered_app.arguments.as_str()),
+            (
+                "working_directory",
+                registered_app.working_directory.as_str(),
+            ),
+        ];
+        let env_values = registered_app
+            .env
+            .values()
+            .map(|value| ("env", value.as_str()));
+        for (field, value) in env_fields.into_iter().chain(env_values) {
+            for name in unresolved_env_vars(value) {
+                report(
+                    field,
+                    ValidationSeverity::Warning,
+                    format!("Environment variable not found: {}", name),
+                );
+            }
+        }
+
+        if let Some(hotkey) = registered_app
+            .hotkey
+            .as_deref()
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+        {
+            match hotkey.parse::<Shortcut>() {
+                Ok(shortcut) => {
+                    if let Some(owner) = hotkey_owners.insert(shortcut.id(), &registered_app.name) {
+                        report(
+                            "hotkey",
+                            ValidationSeverity::Warning,
+                            format!("Hotkey {} is already assigned to {}", hotkey, owner),
+                        );
+                    }
+                }
+                Err(e) => report(
+                    "hotkey",
+                    ValidationSeverity::Error,
+                    format!("Invalid hotkey {}: {}", hotkey, e),
+                ),
+            }
+        }
+
+        for dependency_id in &registered_app.depends_on {
+            if !config
+                .registered_apps
+                .iter()
+                .any(|a| a.id == *dependency_id)
+            {
+                report(
+                    "depends_on",
+                    ValidationSeverity::Warning,
+                    format!("Unknown dependency: {}", dependency_id),
+                );
+            }
+        }
+        if let Err(e) =
+            resolve_dependency_order(&config.registered_apps, std::iter::once(registered_app))
+        {
+            report("depends_on", ValidationSeverity::Error, e);
+        }
+
+        if let Err(e) = validate_schedule(&registered_app.schedule) {
+            report("schedule", ValidationSeverity::Error, e);
+        }
+        if let Err(e) = validate_color(&registered_app.color) {
+            report("color", ValidationSeverity::Error, e);
+        }
+        if let Err(e) = validate_sha256(&registered_app.expected_sha256) {
+            report("expected_sha256", ValidationSeverity::Error, e);
+        }
+        if let Err(e) =
+            validate_run_as_user(&registered_app.run_as_user, registered_app.run_as_admin)
+        {
+            report("run_as_user", ValidationSeverity::Error, e);
+        }
+        if let Err(e) = validate_arg_presets(&registered_app.arg_presets) {
+            report("arg_presets", ValidationSeverity::Error, e);
+        }
+        if let Some(icon_path) = registered_app
+            .icon_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty() && !Path::new(&expand_env(p)).is_file())
+        {
+            report(
+                "icon_path",
+                ValidationSeverity::Warning,
+                format!("Icon file not found: {}", icon_path),
+            );
+        }
+        if let Some(Err(e)) = registered_app.health_check.as_ref().map(health::validate) {
+            report("health_check", ValidationSeverity::Error, e);
+        }
+        if let Some(Err(e)) = registered_app
+            .launch_condition
+            .as_ref()
+            .map(condition::validate)
+        {
+            report("launch_condition", ValidationSeverity::Error, e);
+        }
+    }
+
+    info!("Validated configuration: {} issue(s) found", issues.len());
+    Ok(issues)
+}
+
+// インポートした設定を検証（IDが空・重複している場合は振り直す）
+fn validate_imported_config(config: &mut AppConfig) -> Result<(), String> {
+    validate_imported_apps(&mut config.registered_apps, HashSet::new())
+}
+
+// インポートしたアプリを検証（IDが空・used_ids と重複している場合は振り直す）
+// 登録する場合と同じ確認を行い、通らないアプリがあればインポートしない
+fn validate_imported_apps(
+    registered_apps: &mut [RegisteredApp],
+    used_ids: HashSet<String>,
+) -> Result<(), String> {
+    let mut seen_ids = used_ids;
+    for (index, registered_app) in registered_apps.iter_mut().enumerate() {
+        if registered_app.name.trim().is_empty() {
+            return Err(format!("Entry #{} has an empty name", index + 1));
+        }
+        if registered_app.path.trim().is_empty() {
+            return Err(format!(
+                "Entry #{} ({}) has an empty path",
+                index + 1,
+                registered_app.name
+            ));
+        }
+        validate_registered_app(registered_app)
+            .map_err(|e| format!("Entry #{} ({}): {}", index + 1, registered_app.name, e))?;
+        if registered_app.id.trim().is_empty() || !seen_ids.insert(registered_app.id.clone()) {
+            registered_app.id = uuid::Uuid::new_v4().to_string();
+            seen_ids.insert(registered_app.id.clone());
+        }
+    }
+    Ok(())
+}
+
+// 設定をファイルへエクスポート
+#[tauri::command]
+fn export_config(app: AppHandle) -> Result<(), CommandError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("JSON", &["json"])
+        .set_file_name("ajiponzu-launcher-config.json")
+        .blocking_save_file()
+    else {
+        return Ok(());
+    };
+    let file_path = file_path.into_path().map_err(|e| e.to_string())?;
+
+    let state: tauri::State<AppState> = app.state();
+    let config = lock_config(&state).clone();
+    let config_str = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    write_file_atomically(&file_path, &config_str)?;
+
+    info!("Configuration exported to {}", file_path.display());
+    Ok(())
+}
+
+// ファイルから設定をインポート（現在の設定を置き換える）
+#[tauri::command]
+fn import_config(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let state: tauri::State<AppState> = app.state();
+
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("JSON", &["json"])
+        .blocking_pick_file()
+    else {
+        // キャンセルされた場合は現在の設定をそのまま返す
+        return Ok(lock_config(&state).registered_apps.clone());
+    };
+    let file_path = file_path.into_path().map_err(|e| e.to_string())?;
+
+    let config_str = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let mut imported = parse_config(&config_str).map_err(|e| {
+        CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid configuration file {}: {}", file_path.display(), e),
+        )
+    })?;
+    validate_imported_config(&mut imported)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+
+    let mut config = lock_config(&state);
+    *config = imported;
+    save_config(&app, &config)?;
+    let registered_apps = config.registered_apps.clone();
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!("Configuration imported from {}", file_path.display());
+    Ok(registered_apps)
+}
+
+// 古い形式の設定をインポートした結果
+#[derive(Debug, Serialize, Clone)]
+pub struct LegacyImport {
+    pub registered_apps: Vec<RegisteredApp>,
+    // 変換して追加・置き換えたアプリの数
+    pub migrated: usize,
+}
+
+// 古い形式の設定（バックアップなど）を from_version の形式として変換してインポートする
+// （merge の場合は現在のアプリにないものだけを末尾に追加し、それ以外は設定を置き換える）
+#[tauri::command]
+fn import_legacy_config(
+    app: AppHandle,
+    json: String,
+    from_version: u32,
+    merge: bool,
+) -> Result<LegacyImport, CommandError> {
+    let mut imported = migrate_legacy_config(&json, from_version)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    let migrated = if merge {
+        let before = config.registered_apps.len();
+        *config = merge_configs(config.clone(), imported)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+        config.registered_apps.len() - before
+    } else {
+        validate_imported_config(&mut imported)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+        *config = imported;
+        config.registered_apps.len()
+    };
+    save_config(&app, &config)?;
+    let mut registered_apps = config.registered_apps.clone();
+    drop(config);
+    registered_apps.sort_by_key(|a| a.sort_order);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!(
+        "Imported {} app(s) from a version {} config ({})",
+        migrated,
+        from_version,
+        if merge { "merged" } else { "replaced" }
+    );
+    Ok(LegacyImport {
+        registered_apps,
+        migrated,
+    })
+}
+
+// 指定された形式のバージョンとして設定を変換して読み込む（ファイルに書かれたバージョンは使わない）
+fn migrate_legacy_config(json: &str, from_version: u32) -> Result<AppConfig, String> {
+    if from_version > CONFIG_VERSION {
+        return Err(format!(
+            "Config version {} is newer than this launcher supports ({})",
+            from_version, CONFIG_VERSION
+        ));
+    }
+    let mut value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("Invalid config JSON: {}", e))?;
+    if !value.is_object() {
+        return Err("The config must be a JSON object".to_string());
+    }
+    migrate_config(&mut value, from_version);
+    serde_json::from_value(value)
+        .map_err(|e| format!("The config does not match version {}: {}", from_version, e))
+}
+
+// 設定のバックアップの情報
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigBackup {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    // 作成日時（RFC 3339。取得できない場合は None）
+    pub created: Option<String>,
+}
+
+// バックアップを保存するディレクトリ
+fn config_backups_dir(app: &AppHandle) -> PathBuf {
+    get_config_dir(app).join(BACKUPS_DIR_NAME)
+}
+
+// バックアップのファイルを古い順に取得（ファイル名に日時を含むため名前順が作成順）
+fn config_backup_files(app: &AppHandle) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(config_backups_dir(app)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .map(|n| n.to_string_lossy())
+                    .is_some_and(|n| n.starts_with("config-") && n.ends_with(".json"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+// 現在の設定を backups/config-<日時>.json に保存し、保存したパスを返す（大きな変更の前の手動のバックアップ）
+#[tauri::command]
+fn backup_config(app: AppHandle) -> Result<PathBuf, CommandError> {
+    let backups_dir = config_backups_dir(&app);
+    std::fs::create_dir_all(&backups_dir)
+        .map_err(|e| format!("Failed to create {}: {}", backups_dir.display(), e))?;
+
+    let state: tauri::State<AppState> = app.state();
+    let config_str =
+        serde_json::to_string_pretty(&*lock_config(&state)).map_err(|e| e.to_string())?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S-%3f");
+    let backup_path = backups_dir.join(format!("config-{}.json", timestamp));
+    write_file_atomically(&backup_path, &config_str)?;
+    info!("Configuration backed up to {}", backup_path.display());
+
+    // 上限を超えたバックアップは古いものから削除
+    let files = config_backup_files(&app);
+    let excess = files.len().saturating_sub(CONFIG_BACKUP_LIMIT);
+    for old in &files[..excess] {
+        match std::fs::remove_file(old) {
+            Ok(()) => info!("Removed old config backup {}", old.display()),
+            Err(e) => warn!(
+                "Failed to remove old config backup {}: {}",
+                old.display(),
+                e
+            ),
+        }
+    }
+    Ok(backup_path)
+}
+
+// 設定のバックアップの一覧を新しい順に取得
+#[tauri::command]
+fn list_config_backups(app: AppHandle) -> Result<Vec<ConfigBackup>, CommandError> {
+    Ok(config_backup_files(&app)
+        .into_iter()
+        .rev()
+        .map(|path| {
+            let metadata = std::fs::metadata(&path).ok();
+            ConfigBackup {
+                size_bytes: metadata.as_ref().map_or(0, |m| m.len()),
+                created: metadata
+                    .and_then(|m| m.modified().ok())
+                    .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339()),
+                path,
+            }
+        })
+        .collect())
+}
+
+// バックアップから設定を復元し、復元後のアプリの一覧を返す
+// （現在の設定は元に戻す履歴に積むため、「元に戻す」で復元前に戻せる）
+#[tauri::command]
+fn restore_config_backup(
+    app: AppHandle,
+    path: PathBuf,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    // バックアップのディレクトリにあるファイルだけを復元する
+    if !config_backup_files(&app).contains(&path) {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Not a config backup: {}", path.display()),
+        ));
+    }
+
+    let config_str = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut restored = parse_config(&config_str).map_err(|e| {
+        CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid configuration file {}: {}", path.display(), e),
+        )
+    })?;
+    validate_imported_config(&mut restored)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    *config = restored;
+    save_config(&app, &config)?;
+    let registered_apps = config.registered_apps.clone();
+    drop(config);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!("Configuration restored from {}", path.display());
+    Ok(registered_apps)
+}
+
+// 保存前の候補として新しいアプリの情報を作成（省略した項目は設定ファイルを読み込む場合と同じ既定値）
+fn new_app_candidate(name: &str, path: &str, arguments: &str) -> Result<RegisteredApp, String> {
+    serde_json::from_value(serde_json::json!({
+        "id": uuid::Uuid::new_v4().to_string(),
+        "name": name,
+        "path": path,
+        "arguments": arguments,
+        "description": "",
+        "delay": 0,
+    }))
+    .map_err(|e| e.to_string())
+}
+
+// ユーザー・全ユーザーのスタートメニューのショートカットを読み取り、(名前, リンク先, 引数) を返す
+#[cfg(target_os = "windows")]
+fn read_start_menu_shortcuts() -> Result<Vec<(String, String, String)>, String> {
+    let folders: Vec<String> = ["APPDATA", "ProgramData"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|dir| {
+            Path::new(&dir)
+                .join(r"Microsoft\Windows\Start Menu\Programs")
+                .to_string_lossy()
+                .into_owned()
+        })
+        .filter(|dir| Path::new(dir).is_dir())
+        .collect();
+    if folders.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // ショートカットごとにPowerShellを起動すると遅いため、まとめて読み取ってタブ区切りで出力する
+    let script = format!(
+        "[Console]::OutputEncoding = [System.Text.Encoding]::UTF8; \
+         $shell = New-Object -ComObject WScript.Shell; \
+         Get-ChildItem -LiteralPath {} -Recurse -Filter *.lnk -ErrorAction SilentlyContinue | \
+         ForEach-Object {{ $s = $shell.CreateShortcut($_.FullName); \
+         Write-Output ($_.BaseName + \"`t\" + $s.TargetPath + \"`t\" + $s.Arguments) }}",
+        folders
+            .iter()
+            .map(|dir| quote_powershell(dir))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let mut command = Command::new("powershell");
+    command
+        .args(["-WindowStyle", "Hidden", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW); // コンソールウィンドウを表示しない
+    let output = output_with_timeout(command, START_MENU_SCAN_TIMEOUT)
+        .map_err(|e| format!("Failed to read Start Menu shortcuts: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to read Start Menu shortcuts: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let name = fields.next()?.trim().to_string();
+            let target = fields.next()?.trim().to_string();
+            let arguments = fields.next().unwrap_or_default().trim().to_string();
+            Some((name, target, arguments))
+        })
+        .collect())
+}
+
+// スタートメニューのショートカットから登録候補のアプリを作成（保存はしない）
+// リンク先が実行ファイルでないもの・アンインストーラーなど・登録済みのもの・リンク先が重複するものは除く
+#[cfg(target_os = "windows")]
+fn scan_start_menu_candidates(
+    registered_paths: HashSet<String>,
+) -> Result<Vec<RegisteredApp>, String> {
+    let mut seen_targets = registered_paths;
+    let mut candidates = Vec::new();
+    for (name, target, arguments) in read_start_menu_shortcuts()? {
+        let lower_name = name.to_lowercase();
+        let lower_file_name = Path::new(&target)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.is_empty()
+            || !is_native_launchable(&target)
+            || START_MENU_SKIP_PATTERNS
+                .iter()
+                .any(|p| lower_name.contains(p) || lower_file_name.contains(p))
+        {
+            continue;
+        }
+        if !seen_targets.insert(normalize_executable_path(&target)) {
+            continue;
+        }
+        candidates.push(new_app_candidate(&name, &target, &arguments)?);
+    }
+    candidates.sort_by_key(|a| a.name.to_lowercase());
+    Ok(candidates)
+}
+
+// スタートメニューのショートカットから登録候補のアプリを取得（Windowsのみ。保存はしない）
+#[tauri::command]
+async fn scan_start_menu(app: AppHandle) -> Result<Vec<RegisteredApp>, CommandError> {
+    #[cfg(target_os = "windows")]
+    {
+        let state: tauri::State<AppState> = app.state();
+        let registered_paths: HashSet<String> = lock_config(&state)
+            .registered_apps
+            .iter()
+            .map(|a| normalize_executable_path(&expand_env(&a.path)))
+            .collect();
+        let candidates = tauri::async_runtime::spawn_blocking(move || {
+            scan_start_menu_candidates(registered_paths)
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        info!("Found {} Start Menu candidates", candidates.len());
+        Ok(candidates)
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = app;
+        Err(CommandError::new(
+            ErrorCode::Unsupported,
+            "Importing from the Start Menu is only supported on Windows",
+        ))
+    }
+}
+
+// アプリのアイコンをdata URLで取得（アイコンの画像が設定されていればその画像を使う。
+// URL・プロトコルは共通のアイコン、取り出せない場合は既定のアイコンを返す）
+#[tauri::command]
+async fn get_app_icon(app: AppHandle, app_id: String) -> Result<String, CommandError> {
+    let (path, icon_path) = {
+        let state: tauri::State<AppState> = app.state();
+        let config = lock_config(&state);
+        let registered_app = config
+            .registered_apps
+            .iter()
+            .find(|a| a.id == app_id)
+            .ok_or_else(|| {
+                CommandError::new(
+                    ErrorCode::AppNotFound,
+                    format!("Application not found: {}", app_id),
+                )
+            })?;
+        let icon_path = custom_icon_path(registered_app);
+        if icon_path.is_none() && registered_app.use_shell {
+            return Ok(icon::generic_app_icon());
+        }
+        (registered_executable_path(registered_app), icon_path)
+    };
+    if let Some(icon_path) = icon_path {
+        match icon::image_data_url(&icon_path) {
+            Ok(data_url) => return Ok(data_url),
+            Err(e) => warn!("Falling back to the executable icon for {}: {}", path, e),
+        }
+    }
+    if is_url_path(&path) {
+        return Ok(icon::generic_link_icon());
+    }
+    if !Path::new(&path).is_file() {
+        return Ok(icon::generic_app_icon());
+    }
+
+    let cache_dir = icon_cache_dir(&app)?;
+    let extracted = {
+        let path = path.clone();
+        tauri::async_runtime::spawn_blocking(move || executable_icon_png(&cache_dir, &path))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    match extracted {
+        Ok(png) => Ok(icon::png_data_url(&png)),
+        Err(e) => {
+            warn!("Using default icon for {}: {}", path, e);
+            Ok(icon::generic_app_icon())
+        }
+    }
+}
+
+// 実行ファイルのアイコンをPNGで取得（取り出したアイコンはパスと更新日時ごとにキャッシュする）
+fn executable_icon_png(cache_dir: &Path, path: &str) -> Result<Vec<u8>, String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let cache_path = cache_dir.join(icon::cache_file_name(path, modified));
+    if let Ok(png) = std::fs::read(&cache_path) {
+        return Ok(png);
+    }
+
+    let png = icon::extract_icon_png(path)?;
+    if let Err(e) = std::fs::write(&cache_path, &png) {
+        warn!("Failed to cache icon {}: {}", cache_path.display(), e);
+    }
+    Ok(png)
+}
+
+// 設定されたアイコンの画像のパス（設定されていない・ファイルがない場合は None。ない場合は警告を記録する）
+fn custom_icon_path(registered_app: &RegisteredApp) -> Option<String> {
+    let icon_path = registered_app
+        .icon_path
+        .as_deref()
+        .map(|p| expand_env(p.trim()))
+        .filter(|p| !p.is_empty())?;
+    if Path::new(&icon_path).is_file() {
+        Some(icon_path)
+    } else {
+        warn!(
+            "Icon file not found for {}: {}, using the default icon",
+            registered_app.name, icon_path
+        );
+        None
+    }
+}
+
+// トレイのメニューに表示するアイコン（設定されたアイコンの画像、なければ実行ファイルのアイコン）
+fn tray_menu_icon<R: Runtime>(
+    app: &AppHandle<R>,
+    registered_app: &RegisteredApp,
+) -> Option<tauri::image::Image<'static>> {
+    if let Some(icon_path) = custom_icon_path(registered_app) {
+        match tauri::image::Image::from_path(&icon_path) {
+            Ok(image) => return Some(image),
+            Err(e) => warn!("Failed to load icon {}: {}", icon_path, e),
+        }
+    }
+    if registered_app.use_shell || is_url_path(&registered_app.path) {
+        return None;
+    }
+    let path = registered_executable_path(registered_app);
+    let cache_dir = icon_cache_dir(app).ok()?;
+    let png = executable_icon_png(&cache_dir, &path).ok()?;
+    tauri::image::Image::from_bytes(&png).ok()
+}
+
+// 色の指定（#rgb・#rrggbb）が正しいか確認
+fn validate_color(color: &Option<String>) -> Result<(), String> {
+    let Some(color) = color.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+        return Ok(());
+    };
+    let valid = color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid color '{}' (expected #rgb or #rrggbb)",
+            color
+        ))
+    }
+}
+
+// 実行ファイルのSHA-256の確認（64文字の16進数。大文字小文字は区別しない）
+fn validate_sha256(expected_sha256: &Option<String>) -> Result<(), String> {
+    let Some(hash) = expected_sha256
+        .as_deref()
+        .map(str::trim)
+        .filter(|h| !h.is_empty())
+    else {
+        return Ok(());
+    };
+    if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid SHA-256 '{}' (expected 64 hexadecimal characters)",
+            hash
+        ))
+    }
+}
+
+// 別のユーザーとして実行する場合のユーザー名の確認（管理者として実行とは併用できない）
+fn validate_run_as_user(run_as_user: &Option<String>, run_as_admin: bool) -> Result<(), String> {
+    let Some(user) = run_as_user
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+    else {
+        return Ok(());
+    };
+    reject_control_characters("User name", user)?;
+    if run_as_admin {
+        return Err("run_as_admin cannot be combined with run_as_user".to_string());
+    }
+    Ok(())
+}
+
+// 起動する実行ファイルのSHA-256が登録した値と一致するか確認する（差し替え・改ざんの検出）
+fn verify_sha256(path: &str, expected_sha256: &str) -> Result<(), String> {
+    let actual = executable::sha256(path)?;
+    if actual.eq_ignore_ascii_case(expected_sha256.trim()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Checksum mismatch for {}: expected SHA-256 {}, got {}",
+            path,
+            expected_sha256.trim().to_lowercase(),
+            actual
+        ))
+    }
+}
+
+// 引数のプリセットの確認（名前が空・重複している場合や、引数に制御文字を含む場合はエラー）
+fn validate_arg_presets(presets: &[ArgPreset]) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for preset in presets {
+        let name = preset.name.trim();
+        if name.is_empty() {
+            return Err("Argument preset name must not be empty".to_string());
+        }
+        if !names.insert(name) {
+            return Err(format!("Duplicate argument preset name '{}'", name));
+        }
+        reject_control_characters("Preset arguments", &preset.arguments)?;
+    }
+    Ok(())
+}
+
+// 登録されたアプリの一覧をJSONで取得（貼り付けによる共有用）
+#[tauri::command]
+fn export_apps_json(app: AppHandle) -> Result<String, CommandError> {
+    let state: tauri::State<AppState> = app.state();
+    let mut registered_apps = lock_config(&state).registered_apps.clone();
+    registered_apps.sort_by_key(|a| a.sort_order);
+    serde_json::to_string_pretty(&registered_apps).map_err(|e| CommandError::from(e.to_string()))
+}
+
+// インポートしたアプリを末尾に追加する（既存と重複するIDは振り直す）
+fn append_imported_apps(
+    config: &mut AppConfig,
+    mut imported: Vec<RegisteredApp>,
+) -> Result<(), String> {
+    let used_ids = config
+        .registered_apps
+        .iter()
+        .map(|a| a.id.clone())
+        .collect();
+    validate_imported_apps(&mut imported, used_ids)?;
+    let next_order = config
+        .registered_apps
+        .iter()
+        .map(|a| a.sort_order + 1)
+        .max()
+        .unwrap_or(0);
+    for (offset, registered_app) in imported.iter_mut().enumerate() {
+        registered_app.sort_order = next_order + offset as i64;
+    }
+    config.registered_apps.extend(imported);
+    Ok(())
+}
+
+// JSONのアプリの一覧をインポート（merge の場合は末尾に追加し、既存と重複するIDは振り直す。
+// それ以外は置き換える）
+#[tauri::command]
+fn import_apps_json(
+    app: AppHandle,
+    json: String,
+    merge: bool,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    let mut imported: Vec<RegisteredApp> = serde_json::from_str(&json).map_err(|e| {
+        CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("Invalid app list JSON (expected an array of apps): {}", e),
+        )
+    })?;
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    if merge {
+        append_imported_apps(&mut config, imported)
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    } else {
+        validate_imported_apps(&mut imported, HashSet::new())
+            .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+        config.registered_apps = imported;
+    }
+    save_config(&app, &config)?;
+    let mut registered_apps = config.registered_apps.clone();
+    let count = registered_apps.len();
+    drop(config);
+    registered_apps.sort_by_key(|a| a.sort_order);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!(
+        "Imported app list from JSON ({}, {} apps)",
+        if merge { "merged" } else { "replaced" },
+        count
+    );
+    Ok(registered_apps)
+}
+
+// 他のランチャーの設定で変換できなかったエントリ（import_foreign_config の後に送る）
+#[derive(Debug, Serialize, Clone)]
+struct ForeignImportSkippedPayload {
+    format: String,
+    skipped: Vec<foreign::SkippedEntry>,
+}
+
+// インポートできる他のランチャーの設定の形式
+#[tauri::command]
+fn get_foreign_config_formats() -> Vec<foreign::ForeignFormat> {
+    foreign::FORMATS.to_vec()
+}
+
+// 他のランチャーの設定（JSON）を変換し、現在のアプリの末尾に追加する
+// 変換できなかったエントリは追加せず、「foreign-import-skipped」イベントで知らせる
+#[tauri::command]
+fn import_foreign_config(
+    app: AppHandle,
+    format: String,
+    json: String,
+) -> Result<Vec<RegisteredApp>, CommandError> {
+    let (foreign_apps, skipped) = foreign::convert(&format, &json)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    for entry in &skipped {
+        warn!(
+            "Skipped {} entry #{} ({}): {}",
+            format,
+            entry.index,
+            entry.name.as_deref().unwrap_or("unnamed"),
+            entry.reason
+        );
+    }
+    if foreign_apps.is_empty() && !skipped.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            format!("None of the {} entries could be imported", skipped.len()),
+        )
+        .with_detail(
+            skipped
+                .iter()
+                .map(|entry| format!("#{}: {}", entry.index, entry.reason))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ));
+    }
+
+    let imported = foreign_apps
+        .iter()
+        .map(|foreign_app| {
+            let mut registered_app =
+                new_app_candidate(&foreign_app.name, &foreign_app.path, &foreign_app.arguments)?;
+            registered_app.working_directory = foreign_app.working_directory.clone();
+            registered_app.description = foreign_app.description.clone();
+            Ok(registered_app)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let count = imported.len();
+
+    let state: tauri::State<AppState> = app.state();
+    let mut config = lock_config(&state);
+    append_imported_apps(&mut config, imported)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    save_config(&app, &config)?;
+    let mut registered_apps = config.registered_apps.clone();
+    drop(config);
+    registered_apps.sort_by_key(|a| a.sort_order);
+
+    sync_file_watchers(&app);
+    sync_hotkeys(&app);
+    rebuild_tray_menu(&app);
+
+    info!(
+        "Imported {} apps from {} config ({} skipped)",
+        count,
+        format,
+        skipped.len()
+    );
+    if !skipped.is_empty() {
+        if let Err(e) = app.emit(
+            "foreign-import-skipped",
+            ForeignImportSkippedPayload { format, skipped },
+        ) {
+            error!("Failed to emit foreign-import-skipped event: {}", e);
+        }
+    }
+    Ok(registered_apps)
+}
+
+// 引数の {secret:NAME} で使う秘密の値をOSの資格情報の保管場所に保存する（設定ファイルには保存しない）
+#[tauri::command]
+async fn set_secret(name: String, value: String) -> Result<(), CommandError> {
+    let name = name.trim().to_string();
+    secret::validate_name(&name).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    if value.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Secret value must not be empty",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || secret::store(&name, &value))
+        .await
+        .map_err(CommandError::background_task)??;
+    Ok(())
+}
+
+// 秘密の値を保管場所から削除する
+#[tauri::command]
+async fn delete_secret(name: String) -> Result<(), CommandError> {
+    let name = name.trim().to_string();
+    secret::validate_name(&name).map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    tauri::async_runtime::spawn_blocking(move || secret::delete(&name))
+        .await
+        .map_err(CommandError::background_task)??;
+    Ok(())
+}
+
+// 別のユーザーとして実行する場合のパスワードをOSの資格情報の保管場所に保存する（設定ファイルには保存しない）
+#[tauri::command]
+async fn set_run_as_password(user: String, password: String) -> Result<(), CommandError> {
+    let user = user.trim().to_string();
+    if user.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "User name must not be empty",
+        ));
+    }
+    reject_control_characters("User name", &user)
+        .map_err(|e| CommandError::new(ErrorCode::InvalidInput, e))?;
+    if password.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "Password must not be empty",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || secret::store_account_password(&user, &password))
+        .await
+        .map_err(CommandError::background_task)??;
+    Ok(())
+}
+
+// 別のユーザーとして実行する場合のパスワードを保管場所から削除する
+#[tauri::command]
+async fn delete_run_as_password(user: String) -> Result<(), CommandError> {
+    let user = user.trim().to_string();
+    if user.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::InvalidInput,
+            "User name must not be empty",
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || secret::delete_account_password(&user))
+        .await
+        .map_err(CommandError::background_task)??;
+    Ok(())
+}
+
+fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
+    let menu = Menu::new(app)?;
+
+    // 登録アプリのクイック起動項目（id は "launch:<app_id>"）
+    let mut registered_apps = lock_config(&app.state::<AppState>())
+        .registered_apps
+        .clone();
+    registered_apps.sort_by_key(|a| a.sort_order);
+    for registered_app in &registered_apps {
+        let id = format!("launch:{}", registered_app.id);
+        match tray_menu_icon(app, registered_app) {
+            Some(icon) => menu.append(&IconMenuItem::with_id(
+                app,
+                id,
+                &registered_app.name,
+                true,
+                Some(icon),
+                None::<&str>,
+            )?)?,
+            None => menu.append(&MenuItem::with_id(
+                app,
+                id,
+                &registered_app.name,
+                true,
+                None::<&str>,
+            )?)?,
+        }
+    }
+    if !registered_apps.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    let show_item = MenuItem::with_id(app, "show", tr(app, "tray.show", &[]), true, None::<&str>)?;
+    let hide_item = MenuItem::with_id(app, "hide", tr(app, "tray.hide", &[]), true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", tr(app, "tray.quit", &[]), true, None::<&str>)?;
+    menu.append_items(&[&show_item, &hide_item, &quit_item])?;
+
+    Ok(menu)
+}
+
+// 管理中のアプリの数（プロセス名で管理しているアプリも1つとして数える）
+fn running_app_count(state: &AppState) -> usize {
+    lock_processes(state)
+        .keys()
+        .map(|key| key.trim_end_matches(":name"))
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+// 実行中のアプリがある場合のトレイアイコン（通常のアイコンの右下に緑の丸を重ねたもの）
+const ACTIVE_TRAY_ICON: tauri::image::Image<'static> =
+    tauri::include_image!("icons/tray-active.png");
+
+// 実行中のアプリの数をトレイのツールチップとアイコンに反映
+fn update_tray_status(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    let state: tauri::State<AppState> = app.state();
+    let running_count = running_app_count(&state);
+
+    let tooltip = match running_count {
+        0 => tr(app, "tray.tooltip", &[]),
+        1 => tr(app, "tray.tooltip_running_one", &[]),
+        n => tr(app, "tray.tooltip_running", &[("count", &n.to_string())]),
+    };
+    if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+        error!("Failed to update tray tooltip: {}", e);
+    }
+
+    let icon = if running_count > 0 {
+        ACTIVE_TRAY_ICON
+    } else {
+        let Some(icon) = app.default_window_icon() else {
+            return;
+        };
+        icon.clone().to_owned()
+    };
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        error!("Failed to update tray icon: {}", e);
+    }
+}
+
+// 設定の変更に合わせてトレイメニューを作り直す
+fn rebuild_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id("main") else {
+        return;
+    };
+    let result = create_tray_menu(app).and_then(|menu| tray.set_menu(Some(menu)));
+    if let Err(e) = result {
+        error!("Failed to rebuild tray menu: {}", e);
+    }
+}
+
+// ウィンドウがモニターからはみ出している場合は、最も重なっているモニター（なければプライマリ）の内側へ移動
+fn clamp_window_to_monitor(window: &tauri::WebviewWindow) -> tauri::Result<()> {
+    // 最大化中はOSが配置するため調整しない
+    if window.is_maximized()? {
+        return Ok(());
+    }
+
+    let position = window.outer_position()?;
+    let size = window.outer_size()?;
+
+    let overlap = |monitor: &tauri::Monitor| -> i64 {
+        let (mx, my) = (monitor.position().x as i64, monitor.position().y as i64);
+        let (mw, mh) = (monitor.size().width as i64, monitor.size().height as i64);
+        let width =
+            (position.x as i64 + size.width as i64).min(mx + mw) - (position.x as i64).max(mx);
+        let height =
+            (position.y as i64 + size.height as i64).min(my + mh) - (position.y as i64).max(my);
+        width.max(0) * height.max(0)
+    };
+    let monitor = match window
+        .available_monitors()?
+        .into_iter()
+        .filter(|m| overlap(m) > 0)
+        .max_by_key(|m| overlap(m))
+    {
+        Some(monitor) => monitor,
+        None => match window.primary_monitor()? {
+            Some(monitor) => monitor,
+            None => return Ok(()),
+        },
+    };
+
+    let (mx, my) = (monitor.position().x, monitor.position().y);
+    let (mw, mh) = (monitor.size().width as i32, monitor.size().height as i32);
+    let x = position.x.min(mx + mw - size.width as i32).max(mx);
+    let y = position.y.min(my + mh - size.height as i32).max(my);
+    if (x, y) != (position.x, position.y) {
+        window.set_position(tauri::PhysicalPosition::new(x, y))?;
+    }
+    Ok(())
+}
+
+// コマンドライン引数で指定された起動の内容
+#[derive(Debug, Default)]
+struct CliRequest {
+    apps: Vec<String>,
+    groups: Vec<String>,
+    quit_after: bool,
+    safe_mode: bool,
+}
+
+impl CliRequest {
+    // 引数を解析（--config-dir など関係のない引数は無視する）
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let mut request = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == CLI_QUIT_AFTER_ARG {
+                request.quit_after = true;
+                continue;
+            }
+            if arg == CLI_SAFE_MODE_ARG {
+                request.safe_mode = true;
+                continue;
+            }
+            for (flag, values) in [
+                (CLI_LAUNCH_ARG, &mut request.apps),
+                (CLI_GROUP_ARG, &mut request.groups),
+            ] {
+                if arg == flag {
+                    values.extend(args.next());
+                } else if let Some(value) = arg.strip_prefix(&format!("{}=", flag)) {
+                    values.push(value.to_string());
+                }
+            }
+        }
+        request
+    }
+
+    fn is_empty(&self) -> bool {
+        self.apps.is_empty() && self.groups.is_empty()
+    }
+}
+
+// 環境変数でセーフモードが指定されているか（空・"0"・"false" 以外の値なら有効）
+fn safe_mode_env() -> bool {
+    std::env::var(SAFE_MODE_ENV).is_ok_and(|value| {
+        let value = value.trim();
+        !value.is_empty() && value != "0" && !value.eq_ignore_ascii_case("false")
+    })
+}
+
+// セーフモードで起動したかを取得（フロントエンドで自動起動しなかったことを表示する）
+#[tauri::command]
+fn get_safe_mode(app: AppHandle) -> bool {
+    app.state::<AppState>().safe_mode
+}
+
+// 名前またはIDに一致するものを探す（名前は前後の空白と大文字小文字を無視する）
+fn matches_name_or_id(query: &str, id: &str, name: &str) -> bool {
+    let query = query.trim();
+    id == query || name.trim().to_lowercase() == query.to_lowercase()
+}
+
+// コマンドライン引数で指定されたアプリ・グループを起動し、終了コードを返す
+async fn run_cli_request(app: &AppHandle, request: &CliRequest) -> i32 {
+    let (registered_apps, launch_groups) = {
+        let state: tauri::State<AppState> = app.state();
+        let config = lock_config(&state);
+        (config.registered_apps.clone(), config.launch_groups.clone())
+    };
+
+    let mut not_found = false;
+    let mut launch_failed = false;
+    for query in &request.apps {
+        let Some(registered_app) = registered_apps
+            .iter()
+            .find(|a| matches_name_or_id(query, &a.id, &a.name))
+        else {
+            warn!("Command line launch: application not found: {}", query);
+            notify(app, &tr(app, "notify.app_not_found", &[]), query);
+            not_found = true;
+            continue;
+        };
+        info!("Command line launch: {}", registered_app.name);
+        let result = launch_application(
+            app.clone(),
+            registered_app.id.clone(),
+            registered_app.path.clone(),
+            registered_app.arguments.clone(),
+            None,
+            None,
+        )
+        .await;
+        if let Err(e) = result {
+            error!("Failed to launch {}: {}", registered_app.name, e);
+            launch_failed = true;
+        }
+    }
+    for query in &request.groups {
+        let Some(group) = launch_groups
+            .iter()
+            .find(|g| matches_name_or_id(query, &g.id, &g.name))
+        else {
+            warn!("Command line launch: launch group not found: {}", query);
+            notify(app, &tr(app, "notify.group_not_found", &[]), query);
+            not_found = true;
+            continue;
+        };
+        info!("Command line launch: group {}", group.name);
+        if let Err(e) = launch_group(app.clone(), group.id.clone()).await {
+            error!("Failed to launch group {}: {}", group.name, e);
+            launch_failed = true;
+        }
+    }
+
+    if not_found {
+        EXIT_CODE_NOT_FOUND
+    } else if launch_failed {
+        EXIT_CODE_LAUNCH_FAILED
+    } else {
+        0
+    }
+}
+
+// 2つ目のランチャーが起動されたときに既存のウィンドウを前面に表示
+fn handle_second_instance(app: &AppHandle, argv: Vec<String>) {
+    info!("Another instance was started with args: {:?}", argv);
+
+    // ディープリンクのURLはディープリンクのプラグインから別途届くため、ここでは何もしない
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    if argv
+        .iter()
+        .any(|arg| arg.to_lowercase().starts_with(&prefix))
+    {
+        return;
+    }
+
+    // --launch・--group が渡された場合は既存のインスタンスで起動する（ランチャーは常駐したまま）
+    let request = CliRequest::parse(argv.into_iter().skip(1));
+    if !request.is_empty() {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            run_cli_request(&app, &request).await;
+        });
+        return;
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
 }
 
-fn create_tray_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
-    let show_item = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-    let hide_item = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
-    let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+// ディープリンクを処理（launch/<app_id> でアプリを起動し、show でウィンドウを表示する）
+fn handle_deep_link(app: &AppHandle, url: &str) {
+    info!("Received deep link: {}", url);
+    let prefix = format!("{}://", DEEP_LINK_SCHEME);
+    let target = url
+        .get(..prefix.len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case(&prefix))
+        .map(|_| &url[prefix.len()..])
+        .unwrap_or_default();
+    // クエリ・フラグメントは使わない
+    let target = target
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_matches('/');
 
-    Menu::with_items(app, &[&show_item, &hide_item, &quit_item])
+    match target.split_once('/') {
+        Some(("launch", app_id)) if !app_id.is_empty() => {
+            let state: tauri::State<AppState> = app.state();
+            let exists = lock_config(&state)
+                .registered_apps
+                .iter()
+                .any(|a| a.id == app_id);
+            if exists {
+                spawn_launch_by_id(app, app_id);
+            } else {
+                warn!("Deep link refers to an unknown app: {}", app_id);
+                notify(
+                    app,
+                    &tr(app, "notify.deep_link_failed", &[]),
+                    &tr(app, "notify.deep_link_unknown_app", &[("id", app_id)]),
+                );
+            }
+        }
+        None if target == "show" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        _ => {
+            warn!("Unsupported deep link: {}", url);
+            notify(
+                app,
+                &tr(app, "notify.deep_link_failed", &[]),
+                &tr(app, "notify.deep_link_unsupported", &[("url", url)]),
+            );
+        }
+    }
 }
 
 fn handle_tray_event(app: &AppHandle, event: TrayIconEvent) {
@@ -562,69 +10157,278 @@ fn handle_tray_event(app: &AppHandle, event: TrayIconEvent) {
         ..
     } = event
     {
-        let window = app.get_webview_window("main").unwrap();
-        let _ = window.show();
+        // 非表示で起動した場合も前面に出す
+        match app.get_webview_window("main") {
+            Some(window) => {
+                if let Err(e) = window.show().and_then(|()| window.set_focus()) {
+                    error!("Failed to show window: {}", e);
+                }
+            }
+            None => warn!("Main window not found"),
+        }
     }
 }
 
 fn handle_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
-    let window = app.get_webview_window("main").unwrap();
-
     match event.id.as_ref() {
-        "show" => {
-            let _ = window.show();
-        }
-        "hide" => {
-            let _ = window.hide();
+        "show" | "hide" => {
+            let Some(window) = app.get_webview_window("main") else {
+                warn!("Main window not found");
+                return;
+            };
+            let result = if event.id.as_ref() == "show" {
+                window.show()
+            } else {
+                window.hide()
+            };
+            if let Err(e) = result {
+                error!("Failed to {} window: {}", event.id.as_ref(), e);
+            }
         }
-        "quit" => {
-            app.exit(0);
+        "quit" => request_quit(app),
+        id => {
+            if let Some(app_id) = id.strip_prefix("launch:") {
+                spawn_launch_by_id(app, app_id);
+            }
         }
-        _ => {}
     }
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // ポータブルモードではログとウィンドウの状態も設定ディレクトリに保存する
+    let launcher_log_target = match portable_log_dir() {
+        Some(path) => TargetKind::Folder {
+            path,
+            file_name: Some(LAUNCHER_LOG_FILE_NAME.to_string()),
+        },
+        None => TargetKind::LogDir {
+            file_name: Some(LAUNCHER_LOG_FILE_NAME.to_string()),
+        },
+    };
+    let mut window_state_builder =
+        tauri_plugin_window_state::Builder::new().with_state_flags(WINDOW_STATE_FLAGS);
+    if let Some(dir) = config_dir_override() {
+        // 絶対パスを指定すると既定の設定ディレクトリではなくそのパスに保存される
+        window_state_builder = window_state_builder.with_filename(
+            dir.join(tauri_plugin_window_state::DEFAULT_FILENAME)
+                .to_string_lossy(),
+        );
+    }
+
     tauri::Builder::default()
+        // 二重起動時は新しいプロセスを起動せず、既存のウィンドウを表示する（最初に登録する必要がある）
+        // ディープリンクで起動された場合もURLが既存のインスタンスへ渡される
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            handle_second_instance(app, argv);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .targets([
+                    Target::new(TargetKind::Stdout),
+                    Target::new(launcher_log_target),
+                ])
+                .level(log::LevelFilter::Info)
+                .max_file_size(LAUNCHER_LOG_MAX_BYTES)
+                .rotation_strategy(RotationStrategy::KeepSome(LAUNCHER_LOG_KEEP_FILES))
+                .build(),
+        )
+        .plugin(window_state_builder.build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // アプリケーション状態を初期化
-            let config = load_config(app.handle());
-            app.manage(AppState {
-                config: Mutex::new(config),
-                running_processes: Mutex::new(HashMap::new()),
-            });
+            let mut startup_warnings = Vec::new();
+            if let Some(dir) = config_dir_override() {
+                info!("Using portable config dir {}", dir.display());
+                if let Err(e) = check_dir_writable(&dir) {
+                    error!("Config dir is not writable: {}", e);
+                    startup_warnings.push(format!(
+                        "Config dir is not writable; settings will not be saved: {}",
+                        e
+                    ));
+                }
+            }
+            // 設定ディレクトリに届かない場合（ネットワークドライブなど）は既定の設定で起動し、
+            // 届くようになったら読み込み直す
+            let (active_profile, (config, config_warning)) = match ensure_config_dir(app.handle())
+            {
+                Ok(_) => (load_active_profile(app.handle()), load_config(app.handle())),
+                Err(e) => {
+                    error!("{}", e);
+                    mark_config_unavailable(app.handle(), &e);
+                    (
+                        DEFAULT_PROFILE.to_string(),
+                        (
+                            AppConfig::default(),
+                            Some(format!(
+                                "{}\nStarting with the default settings in memory; the config will be read again when it becomes available",
+                                e
+                            )),
+                        ),
+                    )
+                }
+            };
+            startup_warnings.extend(config_warning);
+            let cli_request = CliRequest::parse(std::env::args().skip(1));
+            let safe_mode = cli_request.safe_mode || safe_mode_env();
+            if safe_mode {
+                warn!("Starting in safe mode; startup apps will not be launched");
+            }
+            app.manage(AppState::new(
+                config,
+                active_profile,
+                startup_warnings,
+                Arc::new(SystemLauncher),
+                safe_mode,
+            ));
+            sync_file_watchers(app.handle());
+            if CONFIG_UNAVAILABLE.load(Ordering::SeqCst) {
+                start_config_recovery(app.handle());
+            } else {
+                match create_config_watcher(app.handle()) {
+                    Ok(watcher) => {
+                        let state: tauri::State<AppState> = app.state();
+                        *state.config_watcher.lock_or_recover() = Some(watcher);
+                    }
+                    Err(e) => error!("Failed to watch config directory: {}", e),
+                }
+            }
+
+            app.handle().plugin(
+                tauri_plugin_global_shortcut::Builder::new()
+                    .with_handler(|app, shortcut, event| {
+                        if event.state() == ShortcutState::Pressed {
+                            handle_hotkey_pressed(app, shortcut);
+                        }
+                    })
+                    .build(),
+            )?;
+            sync_hotkeys(app.handle());
+
+            // 保存した位置が画面外になっていればモニター内に戻す
+            if let Some(window) = app.get_webview_window("main") {
+                if let Err(e) = clamp_window_to_monitor(&window) {
+                    error!("Failed to adjust window position: {}", e);
+                }
+            }
+            // 最前面の設定はウィンドウを最初に表示する前に反映する
+            apply_always_on_top(app.handle());
 
             let menu = create_tray_menu(app.handle())?;
 
-            let _tray = TrayIconBuilder::with_id("main")
+            let mut tray_builder = TrayIconBuilder::with_id("main");
+            if let Some(icon) = app.default_window_icon() {
+                tray_builder = tray_builder.icon(icon.clone());
+            } else {
+                warn!("Default window icon not found; tray icon has no image");
+            }
+            let _tray = tray_builder
                 .menu(&menu)
-                .icon(app.default_window_icon().unwrap().clone())
                 .tooltip("Ajiponzu Utility Launcher")
-                .on_menu_event(|app, event| handle_menu_event(app, event))
+                .on_menu_event(handle_menu_event)
                 .on_tray_icon_event(|tray, event| {
                     let app = tray.app_handle();
                     handle_tray_event(app, event);
                 })
                 .build(app)?;
 
-            // アプリケーション起動時に自動起動を実行
+            // 定時起動のスケジューラーを開始
+            tauri::async_runtime::spawn(run_scheduler(app.handle().clone()));
+
+            // ディープリンクを受け取る（Windows・Linuxはインストールせずに実行した場合に備えて実行時にも登録する）
+            #[cfg(any(target_os = "windows", target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                error!("Failed to register deep link scheme: {}", e);
+            }
+            let app_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    handle_deep_link(&app_handle, url.as_str());
+                }
+            });
+            match app.deep_link().get_current() {
+                Ok(Some(urls)) => {
+                    for url in urls {
+                        handle_deep_link(app.handle(), url.as_str());
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => error!("Failed to get deep link: {}", e),
+            }
+
+            let quit_after = cli_request.quit_after;
+
+            // ウィンドウは非表示で作成されるため、start_hidden でなければここで表示する
+            // （セーフモードでは設定を直せるよう常に表示する。--quit-after の場合はすぐに終了するため
+            // 表示しない。トレイからはいつでも表示できる）
+            let start_hidden = lock_config(&app.state::<AppState>()).start_hidden;
+            if (!start_hidden || safe_mode) && !quit_after {
+                if let Some(window) = app.get_webview_window("main") {
+                    if let Err(e) = window.show() {
+                        error!("Failed to show window: {}", e);
+                    }
+                }
+            }
+
+            // 起動済みの launch_once_per_boot のアプリは、自動起動より前に引き継ぐ
+            let runtime_state = read_runtime_state(app.handle());
+            restore_launched_this_boot(app.handle(), &runtime_state);
+
+            // 実行中のアプリを管理テーブルに反映してから自動起動を実行
+            // （--quit-after の場合はすぐに終了するため、セーフモードの場合は復旧のため自動起動は行わない）
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
+                // PowerShellが使えるかは最初に確認しておく（プロセスの生存確認の方法が変わるため）
+                #[cfg(target_os = "windows")]
+                powershell_available();
+                restore_runtime_processes(&app_handle, runtime_state.processes);
+                reconcile_running_processes(&app_handle);
+                if quit_after {
+                    return;
+                }
+                if safe_mode {
+                    info!("Safe mode: skipped launching startup apps");
+                    return;
+                }
                 if let Err(e) = launch_startup_apps(app_handle).await {
-                    eprintln!("Failed to launch startup apps: {}", e);
+                    error!("Failed to launch startup apps: {}", e);
                 }
             });
 
+            // コマンドライン引数で指定されたアプリ・グループを起動
+            if !cli_request.is_empty() || quit_after {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let exit_code = run_cli_request(&app_handle, &cli_request).await;
+                    if cli_request.quit_after {
+                        info!(
+                            "Quitting after command line launch (exit code {})",
+                            exit_code
+                        );
+                        let state: tauri::State<AppState> = app_handle.state();
+                        state.keep_apps_on_exit.store(true, Ordering::Relaxed);
+                        app_handle.exit(exit_code);
+                    }
+                });
+            }
+
             Ok(())
         })
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                // 隠す前に位置とサイズを保存（異常終了しても次回復元できるように）
+                if let Err(e) = window.app_handle().save_window_state(WINDOW_STATE_FLAGS) {
+                    error!("Failed to save window state: {}", e);
+                }
+
                 // アプリを終了させずにウィンドウを隠す
-                window.hide().unwrap();
+                if let Err(e) = window.hide() {
+                    error!("Failed to hide window: {}", e);
+                }
                 api.prevent_close();
             }
         })
@@ -632,16 +10436,1177 @@ pub fn run() {
             show_window,
             hide_window,
             get_registered_apps,
+            get_registered_app,
+            get_apps_by_category,
+            search_apps,
+            check_app_health,
+            get_process_stats,
+            validate_config,
+            reveal_app_location,
+            get_config_info,
+            run_diagnostics,
+            get_powershell_status,
+            reorder_apps,
             add_registered_app,
             update_registered_app,
+            patch_registered_app,
             remove_registered_app,
+            duplicate_registered_app,
+            set_apps_enabled,
             reset_config,
             launch_application,
+            launch_with_args,
             stop_application,
             is_application_running,
+            get_application_state,
+            suspend_application,
+            focus_application,
+            list_system_processes,
+            resume_application,
+            get_running_processes,
+            reconcile_running_state,
+            clean_name_markers,
+            kill_pid,
+            restart_application,
+            get_app_log_path,
+            open_app_log_file,
+            get_app_stats,
+            resolve_path_pattern,
+            validate_hotkey,
+            swap_assignments,
+            get_arg_presets,
+            get_launch_history,
+            clear_launch_history,
+            preview_launch_command,
+            export_launch_script,
+            test_launch,
+            stop_output_capture,
+            await_application_exit,
+            open_log_file,
             launch_startup_apps,
+            list_profiles,
+            get_active_profile,
+            create_profile,
+            switch_profile,
+            delete_profile,
+            move_app_to_profile,
+            get_launch_groups,
+            add_launch_group,
+            remove_launch_group,
+            launch_group,
+            pause_file_watchers,
+            resume_file_watchers,
+            export_config,
+            import_config,
+            backup_config,
+            list_config_backups,
+            restore_config_backup,
+            export_apps_json,
+            import_apps_json,
+            import_legacy_config,
+            get_foreign_config_formats,
+            import_foreign_config,
+            set_secret,
+            delete_secret,
+            set_run_as_password,
+            delete_run_as_password,
+            scan_start_menu,
+            get_app_icon,
+            set_stop_apps_on_quit,
+            set_skip_quit_confirmation,
+            set_stop_grace_period_ms,
+            set_launch_debounce_ms,
+            set_restart_notification_interval_ms,
+            set_maintenance_window,
+            set_suppress_next_startup,
+            cleanup_system_integration,
+            set_language,
+            set_always_on_top,
+            set_start_hidden,
+            confirm_quit,
+            set_notifications_enabled,
+            set_sequential_startup,
+            set_show_startup_summary,
+            set_base_directory,
+            get_autostart_status,
+            set_launcher_autostart,
+            get_startup_warnings,
+            get_safe_mode,
+            reload_config,
+            migrate_config_location,
+            undo_last_change,
+            redo_last_change,
+            check_process_name_conflicts,
+            find_apps_by_path,
+            inspect_executable,
+            compute_file_hash,
             open_file_dialog
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // トレイの「Quit」を含むすべての終了経路で実行される
+            if let tauri::RunEvent::Exit = event {
+                let state: tauri::State<AppState> = app.state();
+                let stop_apps_on_quit = lock_config(&state).stop_apps_on_quit
+                    && !state.keep_apps_on_exit.load(Ordering::Relaxed);
+                if stop_apps_on_quit {
+                    stop_all_applications(app);
+                }
+                // 書き込みを待っている設定を失わないよう、すぐに書き込む
+                // （設定ディレクトリに届かなかった場合は、最後にもう一度届くか確かめる）
+                if CONFIG_UNAVAILABLE.load(Ordering::SeqCst) {
+                    if let Err(e) = recover_config(app) {
+                        error!("Config dir is unavailable on exit; changes made while it was unavailable are not saved: {}", e);
+                    }
+                }
+                if let Err(e) = flush_config(app) {
+                    error!("Failed to save config on exit: {}", e);
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::launcher::{LauncherCall, RecordingLauncher};
+    use super::*;
+
+    // 記録用のランチャーで状態を作成（停止の猶予時間はテストが長くならないように短くする）
+    fn mock_state(registered_apps: Vec<RegisteredApp>) -> (AppState, Arc<RecordingLauncher>) {
+        let launcher = Arc::new(RecordingLauncher::new());
+        let config = AppConfig {
+            registered_apps,
+            stop_grace_period_ms: 200,
+            ..AppConfig::default()
+        };
+        let state = AppState::new(
+            config,
+            "default".to_string(),
+            Vec::new(),
+            launcher.clone(),
+            false,
+        );
+        (state, launcher)
+    }
+
+    fn mock_app(id: &str, path: &str, prevent_duplicate: bool) -> RegisteredApp {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "path": path,
+            "arguments": "",
+            "description": "",
+            "delay": 0,
+            "preventDuplicate": prevent_duplicate,
+        }))
+        .unwrap()
+    }
+
+    // Windowsのコマンドライン規則（CommandLineToArgvW）で1つの引数を読み戻す（quote_windows_arg の確認用）
+    fn unquote_windows_arg(quoted: &str) -> String {
+        let mut arg = String::new();
+        let mut in_quotes = false;
+        let mut backslashes = 0;
+        for c in quoted.chars() {
+            match c {
+                '\\' => backslashes += 1,
+                '"' => {
+                    arg.push_str(&"\\".repeat(backslashes / 2));
+                    if backslashes % 2 == 1 {
+                        arg.push('"');
+                    } else {
+                        in_quotes = !in_quotes;
+                    }
+                    backslashes = 0;
+                }
+                _ => {
+                    arg.push_str(&"\\".repeat(backslashes));
+                    backslashes = 0;
+                    assert!(
+                        in_quotes || !c.is_whitespace(),
+                        "unquoted space in {}",
+                        quoted
+                    );
+                    arg.push(c);
+                }
+            }
+        }
+        arg.push_str(&"\\".repeat(backslashes));
+        assert!(!in_quotes, "unterminated quote in {}", quoted);
+        arg
+    }
+
+    // PowerShellのコマンドの確認用の起動内容
+    fn powershell_spec(path: &str, arguments: &str) -> LaunchSpec {
+        LaunchSpec {
+            app_id: "app".to_string(),
+            path: path.to_string(),
+            arguments: arguments.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn powershell_command_escapes_apostrophes() {
+        let spec = powershell_spec(r"C:\Users\O'Brien\app.exe", r#"--name "a'b""#);
+        assert_eq!(
+            build_powershell_launch_command(&spec, false),
+            r"Start-Process -FilePath 'C:\Users\O''Brien\app.exe' -ArgumentList '--name','a''b'"
+        );
+    }
+
+    #[test]
+    fn powershell_command_escapes_typographic_quotes() {
+        let spec = powershell_spec("C:\\it\u{2019}s\\app.exe", "\u{2018}x\u{201B}");
+        assert_eq!(
+            build_powershell_launch_command(&spec, false),
+            "Start-Process -FilePath 'C:\\it\u{2019}\u{2019}s\\app.exe' -ArgumentList '\u{2018}\u{2018}x\u{201B}\u{201B}'"
+        );
+    }
+
+    #[test]
+    fn powershell_command_neutralizes_injection() {
+        let spec = powershell_spec(
+            r"C:\a'; Remove-Item C:\b",
+            r"$(Remove-Item C:\c); Stop-Computer",
+        );
+        assert_eq!(
+            build_powershell_launch_command(&spec, false),
+            r"Start-Process -FilePath 'C:\a''; Remove-Item C:\b' -ArgumentList '$(Remove-Item','C:\c);','Stop-Computer'"
+        );
+        // 登録時の補助的な確認でも拒否される
+        assert!(looks_like_command_injection(&spec.path));
+        assert!(looks_like_command_injection("C:\\a\u{2019}; calc"));
+        assert!(looks_like_command_injection(r"C:\$(calc)"));
+        assert!(!looks_like_command_injection(r"C:\Users\O'Brien\app.exe"));
+        // バッククォートは引用符・区切り文字をエスケープする場合だけ拒否する
+        assert!(looks_like_command_injection(r"C:\a`'; calc"));
+        assert!(looks_like_command_injection("C:\\a`; calc"));
+        assert!(!looks_like_command_injection(r"C:\Tools`2024\app.exe"));
+    }
+
+    #[test]
+    fn imported_app_with_injected_path_is_rejected() {
+        let mut imported = vec![mock_app("evil", r"C:\a'; Remove-Item C:\b", false)];
+        let error = validate_imported_apps(&mut imported, HashSet::new()).unwrap_err();
+        assert!(
+            error.contains("(evil): Path contains characters that could be interpreted"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn powershell_command_escapes_log_paths() {
+        let mut spec = powershell_spec(r"C:\app.exe", "");
+        spec.log_paths = Some((
+            PathBuf::from(r"C:\logs\a'; $(calc).log"),
+            PathBuf::from("C:\\logs\\b\u{2019}.err.log"),
+        ));
+        assert_eq!(
+            build_powershell_launch_command(&spec, true),
+            "$process = Start-Process -FilePath 'C:\\app.exe' -PassThru -RedirectStandardOutput 'C:\\logs\\a''; $(calc).log' -RedirectStandardError 'C:\\logs\\b\u{2019}\u{2019}.err.log'; Write-Output $process.Id"
+        );
+    }
+
+    #[test]
+    fn powershell_command_uses_verb_runas_only_when_elevated() {
+        let mut spec = powershell_spec(r"C:\app.exe", "--flag");
+        spec.log_paths = Some((PathBuf::from(r"C:\out.log"), PathBuf::from(r"C:\err.log")));
+        let command = build_powershell_launch_command(&spec, true);
+        assert!(!command.contains("-Verb RunAs"));
+        assert!(command.contains("-RedirectStandardOutput"));
+
+        // 管理者として実行する場合は標準出力をリダイレクトせず、PIDも出力しない
+        spec.run_as_admin = true;
+        assert_eq!(
+            build_powershell_launch_command(&spec, false),
+            r"Start-Process -FilePath 'C:\app.exe' -ArgumentList '--flag' -Verb RunAs"
+        );
+    }
+
+    // テストごとの一時ディレクトリ（前回の実行で残ったものは消す）
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("ajiponzu-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // ディレクトリに残っているファイルの名前
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn failed_temp_write_keeps_original_file() {
+        let dir = test_dir("temp-write-fails");
+        let path = dir.join("config.json");
+        std::fs::write(&path, "original").unwrap();
+        // 一時ファイルを作れない（同じ名前のディレクトリがある）場合
+        // （番号は他のテストの書き込みでも進むため、続く番号の分も作っておく）
+        let next = TMP_FILE_COUNTER.load(Ordering::Relaxed);
+        for n in next..next + 100 {
+            let mut tmp_name = path.as_os_str().to_owned();
+            tmp_name.push(format!(".{}-{}.tmp", std::process::id(), n));
+            std::fs::create_dir(&tmp_name).unwrap();
+        }
+        assert!(write_file_replacing(&path, "new", |_, _| unreachable!()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_rename_keeps_original_file() {
+        let dir = test_dir("rename-fails");
+        let path = dir.join("config.json");
+        std::fs::write(&path, "original").unwrap();
+        let error = write_file_replacing(&path, "new", |_, _| {
+            Err(std::io::Error::other("interrupted"))
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("Failed to replace"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+        assert_eq!(file_names(&dir), ["config.json"]);
+
+        // 置き換えられれば新しい内容になる
+        write_file_atomically(&path, "new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(file_names(&dir), ["config.json"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn stale_config_write_does_not_replace_newer_write() {
+        let dir = test_dir("stale-write");
+        let path = dir.join("config.json");
+        // 時間切れで残った古い試みが、新しい試みの後に終わった場合
+        let stale = config_write_attempt(&path, "old");
+        let newer = config_write_attempt(&path, "new");
+        newer().unwrap();
+        assert!(stale().is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(file_names(&dir), ["config.json"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn registered_app_input_accepts_frontend_keys() {
+        let input: RegisteredApp = serde_json::from_value(serde_json::json!({
+            "name": "Tool",
+            "path": "C:\\tool.exe",
+            "preventDuplicate": true,
+            "run_as_admin": true,
+            "delayMs": 250,
+            // ランチャーが管理する項目は入力に含まれていても使わない
+            "id": "other",
+            "launchCount": 3,
+        }))
+        .unwrap();
+        let registered_app = input.with_managed_fields_reset("id".to_string());
+        assert_eq!(registered_app.id, "id");
+        assert!(registered_app.prevent_duplicate);
+        assert!(registered_app.run_as_admin);
+        assert_eq!(registered_app.delay_ms, Some(250));
+        // 指定しなかった項目は設定ファイルを読み込む場合と同じ既定値になる
+        assert!(registered_app.enabled);
+        assert!(registered_app.hooks_required);
+        assert_eq!(registered_app.launch_count, 0);
+    }
+
+    #[test]
+    fn normalize_path_components_removes_dot_segments() {
+        let normalize = |path: &str| normalize_path_components(Path::new(path));
+        assert_eq!(
+            normalize("/opt/./app/../bin/tool"),
+            Path::new("/opt/bin/tool")
+        );
+        assert_eq!(normalize("/opt/app/"), Path::new("/opt/app"));
+        assert_eq!(normalize("/opt//app/."), Path::new("/opt/app"));
+        // ルートより上の「..」は無視し、相対パスの先頭の「..」は残す
+        assert_eq!(normalize("/../opt"), Path::new("/opt"));
+        assert_eq!(normalize("a/../../b"), Path::new("../b"));
+        assert_eq!(normalize("./a/./b/.."), Path::new("a"));
+    }
+
+    #[test]
+    fn normalize_executable_path_matches_equivalent_paths() {
+        let missing = std::env::temp_dir().join("ajiponzu-missing-dir");
+        let path = |rest: &str| format!("{}/{}", missing.display(), rest);
+        assert_eq!(
+            normalize_executable_path(&path("a/../bin/./tool")),
+            normalize_executable_path(&path("bin/tool/"))
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn path_compare_key_ignores_case_and_separators() {
+        assert_eq!(
+            path_compare_key(Path::new(r"C:/Program Files\App/TOOL.exe")),
+            path_compare_key(Path::new(r"c:\program files\app\tool.EXE"))
+        );
+        assert_eq!(
+            path_compare_key(Path::new(r"\\?\C:\Tools\a.exe")),
+            r"c:\tools\a.exe"
+        );
+        assert_eq!(
+            normalize_executable_path(r"C:\NoSuchDir\Sub\..\App.EXE"),
+            normalize_executable_path(r"c:/nosuchdir/./app.exe")
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn path_compare_key_is_case_sensitive() {
+        assert_ne!(
+            path_compare_key(Path::new("/opt/App")),
+            path_compare_key(Path::new("/opt/app"))
+        );
+    }
+
+    // 展開の結果と、見つからなかった変数の名前
+    fn expand_env_collecting(input: &str) -> (String, Vec<String>) {
+        let mut missing = Vec::new();
+        let expanded = expand_env_with(input, |name| missing.push(name.to_string()));
+        (expanded, missing)
+    }
+
+    #[test]
+    fn expand_env_leaves_text_without_variables() {
+        assert_eq!(expand_env_collecting(""), (String::new(), vec![]));
+        assert_eq!(
+            expand_env_collecting(r"C:\Tools\app.exe --flag"),
+            (r"C:\Tools\app.exe --flag".to_string(), vec![])
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn expand_env_expands_dollar_variables() {
+        std::env::set_var("AJIPONZU_TEST_DIR", "/opt/tools");
+        assert_eq!(
+            expand_env_collecting("$AJIPONZU_TEST_DIR/bin ${AJIPONZU_TEST_DIR}x"),
+            ("/opt/tools/bin /opt/toolsx".to_string(), vec![])
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn expand_env_keeps_unknown_and_malformed_variables() {
+        assert_eq!(
+            expand_env_collecting("$AJIPONZU_TEST_MISSING/a ${AJIPONZU_TEST_MISSING}"),
+            (
+                "$AJIPONZU_TEST_MISSING/a ${AJIPONZU_TEST_MISSING}".to_string(),
+                vec![
+                    "AJIPONZU_TEST_MISSING".to_string(),
+                    "AJIPONZU_TEST_MISSING".to_string()
+                ]
+            )
+        );
+        // 「$$」・「${}」・閉じていない「${」・数字で始まる名前は変数として扱わない
+        for input in ["$$", "cost: 5$", "${}", "${AJIPONZU_TEST_DIR", "$1"] {
+            assert_eq!(expand_env_collecting(input), (input.to_string(), vec![]));
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn expand_env_expands_percent_variables() {
+        std::env::set_var("AJIPONZU_TEST_DIR", r"C:\Tools");
+        assert_eq!(
+            expand_env_collecting(
+                r"%AJIPONZU_TEST_DIR%\app.exe %AJIPONZU_TEST_DIR%%AJIPONZU_TEST_DIR%"
+            ),
+            (r"C:\Tools\app.exe C:\ToolsC:\Tools".to_string(), vec![])
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn expand_env_keeps_unknown_and_malformed_variables() {
+        assert_eq!(
+            expand_env_collecting("%AJIPONZU_TEST_MISSING%"),
+            (
+                "%AJIPONZU_TEST_MISSING%".to_string(),
+                vec!["AJIPONZU_TEST_MISSING".to_string()]
+            )
+        );
+        // 「%%」・閉じていない「%VAR」は変数として扱わない
+        for input in ["%%", "100%", "%AJIPONZU_TEST_DIR", "50% off"] {
+            assert_eq!(expand_env_collecting(input), (input.to_string(), vec![]));
+        }
+    }
+
+    #[test]
+    fn process_name_from_path_strips_directory_and_extension() {
+        for (path, expected) in [
+            (r"C:\Program Files\App\Tool.EXE", "tool"),
+            ("C:/Tools/node.exe", "node"),
+            ("/usr/bin/node", "node"),
+            (r"\\server\share\bin\Sync.App.exe", "sync.app"),
+            ("  relative\\app.bat  ", "app"),
+            ("/home/user/.local/bin/.hidden", ".hidden"),
+        ] {
+            assert_eq!(
+                process_name_from_path(path).as_deref(),
+                Some(expected),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn process_name_from_path_rejects_paths_without_file_name() {
+        for path in ["", "   ", "C:\\Tools\\", "/usr/bin/", r"\\server\share\"] {
+            assert_eq!(process_name_from_path(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn priority_from_setting_ignores_case_and_whitespace() {
+        for (setting, expected) in [
+            ("low", ProcessPriority::Low),
+            ("Normal", ProcessPriority::Normal),
+            (" HIGH\t", ProcessPriority::High),
+        ] {
+            assert_eq!(ProcessPriority::from_setting(Some(setting)), Some(expected));
+        }
+    }
+
+    #[test]
+    fn priority_from_setting_keeps_os_default_for_missing_or_invalid_values() {
+        for setting in [None, Some(""), Some("  "), Some("urgent"), Some("lowest")] {
+            assert_eq!(
+                ProcessPriority::from_setting(setting),
+                None,
+                "{:?}",
+                setting
+            );
+        }
+    }
+
+    #[test]
+    fn priority_maps_to_platform_values() {
+        let priorities = [
+            ProcessPriority::Low,
+            ProcessPriority::Normal,
+            ProcessPriority::High,
+        ];
+        assert_eq!(
+            priorities.map(ProcessPriority::priority_class),
+            ["BelowNormal", "Normal", "High"]
+        );
+        assert_eq!(priorities.map(ProcessPriority::nice_value), [10, 0, -10]);
+        #[cfg(target_os = "windows")]
+        assert_eq!(
+            priorities.map(ProcessPriority::creation_flag),
+            [0x0000_4000, 0x0000_0020, 0x0000_0080]
+        );
+    }
+
+    #[test]
+    fn launched_process_is_tracked_by_app_id() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        let spec = LaunchSpec {
+            app_id: "tool".to_string(),
+            path: "/opt/tool".to_string(),
+            ..Default::default()
+        };
+        let spawned = launcher.spawn(&spec).unwrap();
+        let process_key = record_spawned_process(&state, "tool", false, spawned.pid);
+        assert_eq!(process_key, "tool");
+        assert_eq!(lock_processes(&state).get("tool"), Some(&1000));
+        assert_eq!(
+            launcher.calls(),
+            [LauncherCall::Spawn("/opt/tool".to_string())]
+        );
+        assert!(is_tracked_process_alive(&state, "tool", "tool", 1000));
+    }
+
+    #[test]
+    fn prevent_duplicate_and_unknown_pid_use_name_marker() {
+        let (state, launcher) = mock_state(Vec::new());
+        assert_eq!(
+            record_spawned_process(&state, "single", true, Some(42)),
+            "single:name"
+        );
+        // 管理者として実行した場合などはPIDが分からない
+        launcher.set_next_pid(None);
+        let spawned = launcher.spawn(&LaunchSpec::default()).unwrap();
+        assert_eq!(
+            record_spawned_process(&state, "admin", false, spawned.pid),
+            "admin:name"
+        );
+        let processes = lock_processes(&state);
+        assert_eq!(processes.get("single:name"), Some(&42));
+        assert_eq!(processes.get("admin:name"), Some(&0));
+    }
+
+    #[test]
+    fn stop_requests_exit_before_killing() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        launcher.add_process(7, "/opt/tool");
+        launcher.set_exit_on_terminate(true);
+        stop_tracked_process(&state, "tool", 7, false, Some("/opt/tool".to_string())).unwrap();
+        assert_eq!(launcher.calls(), [LauncherCall::Terminate(7)]);
+        assert!(!launcher.is_alive(7));
+    }
+
+    #[test]
+    fn duplicate_prevention_stops_only_same_executable() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", true)]);
+        launcher.add_process(7, "/opt/tool");
+        launcher.add_process(8, "/opt/tool");
+        launcher.add_process(9, "/opt/other");
+        stop_existing_instances(&state, "/opt/tool");
+        assert_eq!(
+            launcher.calls(),
+            [LauncherCall::Kill(KillTarget::ExecutablePath(
+                "/opt/tool".to_string()
+            ))]
+        );
+        assert!(launcher.find_by_path("/opt/tool").is_empty());
+        assert!(launcher.is_alive(9));
+    }
+
+    #[test]
+    fn name_tracked_app_is_alive_while_same_executable_runs() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", true)]);
+        // 起動したプロセスが既存のインスタンスに処理を渡して終了した場合
+        launcher.add_process(8, "/opt/tool");
+        assert!(is_tracked_process_alive(&state, "tool", "tool:name", 7));
+        assert!(!is_tracked_process_alive(&state, "tool", "tool", 7));
+    }
+
+    #[test]
+    fn stop_kills_when_exit_request_is_ignored() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        launcher.add_process(7, "/opt/tool");
+        stop_tracked_process(&state, "tool", 7, false, Some("/opt/tool".to_string())).unwrap();
+        assert_eq!(
+            launcher.calls(),
+            [
+                LauncherCall::Terminate(7),
+                LauncherCall::Kill(KillTarget::ProcessTree(7)),
+            ]
+        );
+        assert!(!launcher.is_alive(7));
+    }
+
+    #[test]
+    fn stop_kills_again_when_process_lingers() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        launcher.add_process(7, "/opt/tool");
+        launcher.set_kills_to_exit(2);
+        stop_tracked_process(&state, "tool", 7, false, Some("/opt/tool".to_string())).unwrap();
+        assert_eq!(
+            launcher.calls(),
+            [
+                LauncherCall::Terminate(7),
+                LauncherCall::Kill(KillTarget::ProcessTree(7)),
+                LauncherCall::Kill(KillTarget::ProcessTree(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop_fails_when_process_survives_kills() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        launcher.add_process(7, "/opt/tool");
+        launcher.set_kills_to_exit(0);
+        let error = stop_tracked_process(&state, "tool", 7, false, Some("/opt/tool".to_string()))
+            .unwrap_err();
+        assert!(error.contains("still running"), "{}", error);
+        assert!(launcher.is_alive(7));
+    }
+
+    #[test]
+    fn name_tracked_stop_escalates_by_executable_path() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", true)]);
+        // PIDが分からないマーカー（管理者実行）は実行ファイルのパスで探して停止する
+        launcher.add_process(7, "/opt/tool");
+        launcher.add_process(9, "/opt/other");
+        stop_tracked_process(&state, "tool", 0, true, Some("/opt/tool".to_string())).unwrap();
+        assert_eq!(
+            launcher.calls(),
+            [
+                LauncherCall::Terminate(7),
+                LauncherCall::Kill(KillTarget::ExecutablePath("/opt/tool".to_string())),
+            ]
+        );
+        assert!(launcher.is_alive(9));
+    }
+
+    #[test]
+    fn back_to_back_launch_is_debounced() {
+        let (state, _) = mock_state(vec![
+            mock_app("tool", "/opt/tool", false),
+            mock_app("other", "/opt/other", false),
+        ]);
+        assert!(debounce_launch(&state, "tool").is_ok());
+        assert!(matches!(
+            debounce_launch(&state, "tool"),
+            Err(LaunchError::Debounced(_))
+        ));
+        // 別のアプリは間引かない
+        assert!(debounce_launch(&state, "other").is_ok());
+    }
+
+    #[test]
+    fn launch_is_allowed_after_debounce_window() {
+        let (state, _) = mock_state(Vec::new());
+        lock_config(&state).launch_debounce_ms = 50;
+        assert!(debounce_launch(&state, "tool").is_ok());
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(debounce_launch(&state, "tool").is_ok());
+        // 0 の場合は間引かない
+        lock_config(&state).launch_debounce_ms = 0;
+        assert!(debounce_launch(&state, "tool").is_ok());
+        assert!(debounce_launch(&state, "tool").is_ok());
+    }
+
+    #[test]
+    fn dead_name_marker_is_cleaned_up() {
+        let (state, launcher) = mock_state(vec![
+            mock_app("gone", "/opt/gone", true),
+            mock_app("alive", "/opt/alive", true),
+        ]);
+        launcher.add_process(9, "/opt/alive");
+        {
+            let mut processes = lock_processes(&state);
+            processes.insert("gone:name".to_string(), 0);
+            processes.insert("alive:name".to_string(), 0);
+            processes.insert("plain".to_string(), 5);
+        }
+        let report = prune_process_entries(&state, |process_key| process_key.ends_with(":name"));
+        let keys = |entries: &[ProcessEntry]| {
+            entries
+                .iter()
+                .map(|e| e.process_key.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&report.removed), ["gone:name"]);
+        assert_eq!(keys(&report.remaining), ["alive:name"]);
+        // 外部から終了されたアプリは実行中として扱わない（マーカー以外のエントリは対象外）
+        assert_eq!(find_tracked_process(&state, "gone"), None);
+        assert!(find_tracked_process(&state, "alive").is_some());
+        assert_eq!(lock_processes(&state).get("plain"), Some(&5));
+    }
+
+    #[test]
+    fn stale_entry_is_removed_unless_relaunched() {
+        let (state, launcher) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        lock_processes(&state).insert("tool".to_string(), 7);
+        // 外部から終了された
+        assert!(!is_tracked_process_alive(&state, "tool", "tool", 7));
+        assert!(remove_process_entry_if_unchanged(&state, "tool", 7));
+        assert_eq!(find_tracked_process(&state, "tool"), None);
+
+        // 確認中に起動し直された場合は新しいエントリを残す
+        launcher.add_process(8, "/opt/tool");
+        lock_processes(&state).insert("tool".to_string(), 8);
+        assert!(!remove_process_entry_if_unchanged(&state, "tool", 7));
+        assert_eq!(lock_processes(&state).get("tool"), Some(&8));
+    }
+
+    #[test]
+    fn stale_entry_cleanup_works_with_poisoned_process_table() {
+        let (state, launcher) = mock_state(vec![
+            mock_app("gone", "/opt/gone", false),
+            mock_app("alive", "/opt/alive", false),
+        ]);
+        launcher.add_process(8, "/opt/alive");
+        {
+            let mut processes = lock_processes(&state);
+            processes.insert("gone".to_string(), 7);
+            processes.insert("alive".to_string(), 8);
+        }
+        std::thread::scope(|scope| {
+            let _ = scope
+                .spawn(|| {
+                    let _processes = state.running_processes.lock().unwrap();
+                    panic!("poison the process table");
+                })
+                .join();
+        });
+        assert!(state.running_processes.is_poisoned());
+
+        let report = prune_process_entries(&state, |_| true);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!(report.removed[0].app_id, "gone");
+        assert_eq!(report.remaining[0].app_id, "alive");
+        assert_eq!(
+            find_tracked_process(&state, "alive"),
+            Some(("alive".to_string(), 8))
+        );
+    }
+
+    #[test]
+    fn poisoned_locks_are_recovered() {
+        let (state, _) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        std::thread::scope(|scope| {
+            let _ = scope
+                .spawn(|| {
+                    let mut config = state.config.lock().unwrap();
+                    let mut processes = state.running_processes.lock().unwrap();
+                    config.launch_debounce_ms = 123;
+                    processes.insert("tool".to_string(), 7);
+                    panic!("poison the config and the process table");
+                })
+                .join();
+        });
+        assert!(state.config.is_poisoned());
+        assert!(state.running_processes.is_poisoned());
+
+        // パニックする前に書き込んだ内容のまま、読み書きを続けられる
+        assert_eq!(lock_config(&state).launch_debounce_ms, 123);
+        assert_eq!(lock_config(&state).registered_apps[0].id, "tool");
+        assert_eq!(lock_processes(&state).get("tool"), Some(&7));
+        lock_processes(&state).insert("other".to_string(), 8);
+        assert_eq!(lock_processes(&state).len(), 2);
+    }
+
+    #[test]
+    fn swap_assignments_exchanges_hotkeys() {
+        let mut config = AppConfig {
+            registered_apps: vec![
+                mock_app("a", "/opt/a", false),
+                mock_app("b", "/opt/b", false),
+            ],
+            ..AppConfig::default()
+        };
+        config.registered_apps[0].hotkey = Some("Ctrl+Alt+A".to_string());
+        swap_app_assignments(&mut config, "a", "b", "hotkey").unwrap();
+        assert_eq!(config.registered_apps[0].hotkey, None);
+        assert_eq!(
+            config.registered_apps[1].hotkey.as_deref(),
+            Some("Ctrl+Alt+A")
+        );
+
+        let code = |result: Result<(), CommandError>| result.unwrap_err().code;
+        assert_eq!(
+            code(swap_app_assignments(&mut config, "a", "b", "slot")),
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(
+            code(swap_app_assignments(&mut config, "a", "a", "hotkey")),
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(
+            code(swap_app_assignments(&mut config, "a", "missing", "hotkey")),
+            ErrorCode::AppNotFound
+        );
+        // 失敗した場合は変更しない
+        assert_eq!(
+            config.registered_apps[1].hotkey.as_deref(),
+            Some("Ctrl+Alt+A")
+        );
+    }
+
+    #[test]
+    fn run_as_user_app_can_be_stopped() {
+        let mut registered_app = mock_app("user", "/opt/tool", false);
+        registered_app.run_as_user = Some("other-user".to_string());
+        let (state, launcher) = mock_state(vec![registered_app]);
+        // 別のユーザーとして実行した場合はPIDが分からない
+        launcher.set_next_pid(None);
+        let spec = LaunchSpec {
+            app_id: "user".to_string(),
+            path: "/opt/tool".to_string(),
+            run_as_user: Some("other-user".to_string()),
+            ..Default::default()
+        };
+        let spawned = launcher.spawn(&spec).unwrap();
+        record_spawned_process(&state, "user", false, spawned.pid);
+        launcher.add_process(7, "/opt/tool");
+
+        let (process_key, pid) = take_tracked_process(&state, "user").unwrap();
+        assert_eq!((process_key.as_str(), pid), ("user:name", 0));
+        assert!(lock_processes(&state).is_empty());
+        stop_tracked_process(&state, "user", pid, true, Some("/opt/tool".to_string())).unwrap();
+        assert_eq!(
+            launcher.calls().last(),
+            Some(&LauncherCall::Kill(KillTarget::ExecutablePath(
+                "/opt/tool".to_string()
+            )))
+        );
+        assert!(!launcher.is_alive(7));
+    }
+
+    #[test]
+    fn tracking_entry_matches_launch_tracking() {
+        assert_eq!(tracking_entry("a", false, Some(7)), ("a".to_string(), 7));
+        assert_eq!(
+            tracking_entry("a", true, Some(7)),
+            ("a:name".to_string(), 7)
+        );
+        assert_eq!(tracking_entry("a", false, None), ("a:name".to_string(), 0));
+        let mut registered_app = mock_app("a", "/opt/tool", false);
+        assert!(!launches_without_pid(&registered_app));
+        registered_app.run_as_user = Some("other-user".to_string());
+        assert_eq!(
+            launches_without_pid(&registered_app),
+            cfg!(target_os = "windows")
+        );
+        // シェル経由の場合は別のユーザーとして実行しない
+        registered_app.use_shell = true;
+        assert!(!launches_without_pid(&registered_app));
+    }
+
+    #[test]
+    fn imported_apps_are_validated_like_registered_apps() {
+        let dir = test_dir("import-validation");
+        let executable = dir.join("tool.exe");
+        std::fs::write(&executable, "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        let executable = executable.to_string_lossy().to_string();
+
+        let mut valid = vec![mock_app("a", &executable, false)];
+        validate_imported_apps(&mut valid, HashSet::new()).unwrap();
+
+        let mut missing = vec![
+            mock_app("a", &executable, false),
+            mock_app("b", &dir.join("missing.exe").to_string_lossy(), false),
+        ];
+        let error = validate_imported_apps(&mut missing, HashSet::new()).unwrap_err();
+        assert!(
+            error.starts_with("Entry #2 (b): File not found"),
+            "{}",
+            error
+        );
+
+        let mut hook = vec![mock_app("hook", &executable, false)];
+        hook[0].pre_launch = Some("mount\ncalc".to_string());
+        let error = validate_imported_apps(&mut hook, HashSet::new()).unwrap_err();
+        assert!(error.contains("(hook): Pre-launch hook"), "{}", error);
+
+        let mut schedule = vec![mock_app("schedule", &executable, false)];
+        schedule[0].schedule = Some("25:99".to_string());
+        assert!(validate_imported_apps(&mut schedule, HashSet::new()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn string_errors_are_internal_without_message_matching() {
+        // メッセージの文字列からは分類しない
+        for message in [
+            "Application not found",
+            "Unknown option",
+            "The default profile cannot be deleted",
+            "Failed to write config.json",
+        ] {
+            assert_eq!(
+                CommandError::from(message).code,
+                ErrorCode::Internal,
+                "{}",
+                message
+            );
+            assert_eq!(
+                CommandError::from(message.to_string()).code,
+                ErrorCode::Internal
+            );
+        }
+
+        let error = CommandError::new(ErrorCode::DuplicateName, "Duplicate app name");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "code": "DUPLICATE_NAME",
+                "message": "Duplicate app name",
+                "detail": null,
+            })
+        );
+    }
+
+    #[test]
+    fn launch_errors_convert_to_command_errors() {
+        let cases = [
+            (LaunchError::NotFound("a".into()), ErrorCode::NotFound),
+            (
+                LaunchError::AccessDenied("a".into()),
+                ErrorCode::PermissionDenied,
+            ),
+            (LaunchError::Timeout("a".into()), ErrorCode::Timeout),
+            (
+                LaunchError::InvalidPath("a".into()),
+                ErrorCode::InvalidInput,
+            ),
+            (LaunchError::Debounced("a".into()), ErrorCode::Debounced),
+            (LaunchError::Other("a".into()), ErrorCode::LaunchFailed),
+        ];
+        for (error, code) in cases {
+            assert_eq!(CommandError::from(error), CommandError::new(code, "a"));
+        }
+
+        // 間引いた起動もほかのコマンドと同じ形で返す
+        let (state, _) = mock_state(vec![mock_app("tool", "/opt/tool", false)]);
+        debounce_launch(&state, "tool").unwrap();
+        let error = CommandError::from(debounce_launch(&state, "tool").unwrap_err());
+        assert_eq!(
+            serde_json::to_value(&error).unwrap()["code"],
+            serde_json::json!("DEBOUNCED")
+        );
+    }
+
+    #[test]
+    fn unknown_arg_preset_is_not_found() {
+        let mut config = AppConfig::default();
+        config
+            .registered_apps
+            .push(mock_app("tool", "/opt/tool", false));
+        let error = find_arg_preset(&config, "tool", "missing").unwrap_err();
+        assert_eq!(error.code, ErrorCode::NotFound);
+        let error = find_arg_preset(&config, "other", "missing").unwrap_err();
+        assert_eq!(error.code, ErrorCode::AppNotFound);
+    }
+
+    #[test]
+    fn autostart_is_synced_only_when_wanted_changes() {
+        let mut tool = mock_app("tool", "/opt/tool", false);
+        let (state, _) = mock_state(vec![tool.clone()]);
+        let mut config = AppConfig::default();
+        config.registered_apps.push(tool.clone());
+        // 起動後の最初の保存では同期する
+        assert!(autostart_sync_needed(&state, &config));
+        // 起動回数の記録など、自動起動に関係しない変更では同期しない
+        config.registered_apps[0].launch_count += 1;
+        assert!(!autostart_sync_needed(&state, &config));
+
+        tool.auto_start = true;
+        config.registered_apps[0] = tool.clone();
+        assert!(autostart_sync_needed(&state, &config));
+        assert!(!autostart_sync_needed(&state, &config));
+        // 無効にしたアプリは自動起動しない
+        config.registered_apps[0].enabled = false;
+        assert!(autostart_sync_needed(&state, &config));
+    }
+
+    #[test]
+    fn unversioned_config_is_migrated() {
+        // バージョンのない形式ではキャメルケースとスネークケースの項目名が混ざっていることがある
+        let config = parse_config(
+            r#"{
+                "sequentialStartup": true,
+                "registered_apps": [{
+                    "id": "a", "name": "A", "path": "/opt/a", "arguments": "",
+                    "description": "", "delay": 3,
+                    "preventDuplicate": true, "autoStart": true, "auto_start": false
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.config_version, CONFIG_VERSION);
+        assert!(config.sequential_startup);
+        let registered_app = &config.registered_apps[0];
+        assert!(registered_app.prevent_duplicate);
+        assert!(!registered_app.auto_start);
+        assert_eq!(registered_app.delay, 3);
+
+        // 保存する設定には現在のバージョンを書き込む
+        let saved = serde_json::to_value(&config).unwrap();
+        assert_eq!(saved["config_version"], serde_json::json!(CONFIG_VERSION));
+        assert_eq!(
+            AppConfig::default().config_version,
+            parse_config(&saved.to_string()).unwrap().config_version
+        );
+    }
+
+    #[test]
+    fn legacy_import_uses_the_given_version() {
+        let json = r#"{"registered_apps": [
+            {"id": "a", "name": "A", "path": "/opt/a", "autoStart": true, "auto_start": false}
+        ]}"#;
+        let config = migrate_legacy_config(json, 0).unwrap();
+        assert_eq!(config.registered_apps.len(), 1);
+        assert!(!config.registered_apps[0].auto_start);
+        // 現在の形式として読み込むと重複した項目名で失敗する
+        assert!(migrate_legacy_config(json, CONFIG_VERSION).is_err());
+
+        assert!(migrate_legacy_config(json, CONFIG_VERSION + 1)
+            .unwrap_err()
+            .contains("newer than this launcher supports"));
+        assert!(migrate_legacy_config("[]", 0).is_err());
+    }
+
+    #[test]
+    fn parse_arguments_empty_input() {
+        assert!(parse_arguments("").is_empty());
+        assert!(parse_arguments("   \t ").is_empty());
+    }
+
+    #[test]
+    fn parse_arguments_splits_on_whitespace_and_ignores_trailing_spaces() {
+        assert_eq!(parse_arguments("  -a  b\tc  "), ["-a", "b", "c"]);
+    }
+
+    #[test]
+    fn parse_arguments_keeps_quoted_spaces() {
+        assert_eq!(
+            parse_arguments(r#"--file "C:\My Documents\a.txt" 'b c'"#),
+            ["--file", r"C:\My Documents\a.txt", "b c"]
+        );
+    }
+
+    #[test]
+    fn parse_arguments_nested_and_escaped_quotes() {
+        assert_eq!(
+            parse_arguments(r#""it's" 'say "hi"' "a \"b\" c" \"d\' e\ f"#),
+            ["it's", r#"say "hi""#, r#"a "b" c"#, "\"d'", "e f"]
+        );
+        // クォートの途中から続く部分も同じ引数になる
+        assert_eq!(parse_arguments(r#"--name="a b"c"#), ["--name=a bc"]);
+    }
+
+    #[test]
+    fn parse_arguments_empty_quoted_argument() {
+        assert_eq!(parse_arguments(r#"a "" b ''"#), ["a", "", "b", ""]);
+    }
+
+    #[test]
+    fn parse_arguments_keeps_windows_path_backslashes() {
+        assert_eq!(
+            parse_arguments(r"C:\Tools\app.exe \\server\share"),
+            [r"C:\Tools\app.exe", r"\\server\share"]
+        );
+    }
+
+    #[test]
+    fn quote_windows_arg_leaves_plain_arguments() {
+        assert_eq!(quote_windows_arg("--flag"), "--flag");
+        assert_eq!(quote_windows_arg(r"C:\a\b"), r"C:\a\b");
+        assert_eq!(quote_windows_arg(""), r#""""#);
+        assert_eq!(quote_windows_arg("a b"), r#""a b""#);
+    }
+
+    #[test]
+    fn quote_windows_arg_round_trips() {
+        for arg in [
+            "",
+            "plain",
+            "a b",
+            r#"say "hi""#,
+            r"C:\My Documents\",
+            r#"trailing\"#,
+            r#"a\\"b"#,
+            r"\\server\share dir\\",
+            "tab\there",
+            "日本語 の 引数",
+        ] {
+            assert_eq!(unquote_windows_arg(&quote_windows_arg(arg)), arg);
+            assert_eq!(unquote_windows_arg(&quote_windows_arg_always(arg)), arg);
+        }
+    }
 }
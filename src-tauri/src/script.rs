@@ -0,0 +1,284 @@
+// 起動スクリプトの書き出し
+//
+// ランチャーの外でも同じように起動できるよう、登録したアプリの起動コマンドを PowerShell・cmd・bash の
+// スクリプトにする（引数の分割・クォートは実際の起動と同じ関数を使う）
+
+use std::collections::HashMap;
+
+use super::launcher::LaunchSpec;
+use super::{
+    build_powershell_launch_command, parse_arguments, quote_powershell, quote_windows_arg,
+    quote_windows_arg_always, ProcessPriority, RUN_AS_PASSWORD_ENV,
+};
+
+// 書き出すスクリプトの種類
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScriptShell {
+    PowerShell,
+    Cmd,
+    Bash,
+}
+
+impl ScriptShell {
+    pub fn parse(shell: &str) -> Result<Self, String> {
+        match shell.trim().to_lowercase().as_str() {
+            "powershell" | "pwsh" | "ps1" => Ok(Self::PowerShell),
+            "cmd" | "bat" | "batch" => Ok(Self::Cmd),
+            "bash" | "sh" => Ok(Self::Bash),
+            _ => Err(format!(
+                "Unknown script shell '{}' (expected powershell, cmd or bash)",
+                shell
+            )),
+        }
+    }
+}
+
+// スクリプトに書き出すアプリ（パス・引数は環境変数の展開・ショートカットの解決をした後のもの）
+pub struct ScriptEntry {
+    pub name: String,
+    pub path: String,
+    pub arguments: String,
+    pub env: HashMap<String, String>,
+    pub working_directory: String,
+    pub use_shell: bool,
+    pub is_url: bool,
+    pub run_as_admin: bool,
+    pub run_as_user: Option<String>,
+    pub priority: Option<ProcessPriority>,
+}
+
+// スクリプト全体を組み立てる（アプリごとに独立して起動し、環境変数・作業ディレクトリは次のアプリに引き継がない）
+pub fn build(shell: ScriptShell, entries: &[ScriptEntry]) -> String {
+    let mut lines = match shell {
+        ScriptShell::PowerShell => vec![
+            "# Launch script exported from Ajiponzu Utility Launcher".to_string(),
+            "# {secret:NAME} and placeholders are written as registered.".to_string(),
+        ],
+        ScriptShell::Cmd => vec![
+            "@echo off".to_string(),
+            "REM Launch script exported from Ajiponzu Utility Launcher".to_string(),
+            "REM {secret:NAME} and placeholders are written as registered.".to_string(),
+        ],
+        ScriptShell::Bash => vec![
+            "#!/usr/bin/env bash".to_string(),
+            "# Launch script exported from Ajiponzu Utility Launcher".to_string(),
+            "# {secret:NAME} and placeholders are written as registered.".to_string(),
+        ],
+    };
+    for entry in entries {
+        lines.push(String::new());
+        match shell {
+            ScriptShell::PowerShell => push_powershell(&mut lines, entry),
+            ScriptShell::Cmd => push_cmd(&mut lines, entry),
+            ScriptShell::Bash => push_bash(&mut lines, entry),
+        }
+    }
+    let newline = if shell == ScriptShell::Cmd {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut script = lines.join(newline);
+    script.push_str(newline);
+    script
+}
+
+// コメントに書く名前（改行を含む場合はスクリプトが壊れないよう空白にする）
+fn comment_name(name: &str) -> String {
+    name.replace(['\r', '\n'], " ")
+}
+
+// 起動する PowerShell のコマンドと同じものを、子の PowerShell で実行する
+// （環境変数はプロセス全体に設定されるため、アプリごとに別のプロセスにする）
+// 別のユーザーとして実行する場合は、パスワードを Get-Credential で尋ねる
+fn push_powershell(lines: &mut Vec<String>, entry: &ScriptEntry) {
+    lines.push(format!("# {}", comment_name(&entry.name)));
+    if let Some(user) = entry.run_as_user.as_deref() {
+        lines.push(format!(
+            "$env:{} = (Get-Credential -UserName {} -Message {}).GetNetworkCredential().Password",
+            RUN_AS_PASSWORD_ENV,
+            quote_powershell(user),
+            quote_powershell(&format!("Password for {}", comment_name(&entry.name)))
+        ));
+    }
+    let command = if entry.is_url {
+        format!(
+            "Start-Process -FilePath {}",
+            quote_powershell(entry.path.trim())
+        )
+    } else if entry.use_shell {
+        let mut command: String = entry
+            .env
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "[Environment]::SetEnvironmentVariable({}, {}, 'Process'); ",
+                    quote_powershell(key),
+                    quote_powershell(value)
+                )
+            })
+            .collect();
+        command.push_str(&format!(
+            "Start-Process -FilePath 'cmd' -ArgumentList {}",
+            quote_powershell(&format!("/S /C \"{}\"", shell_command_line(entry)))
+        ));
+        if !entry.working_directory.is_empty() {
+            command.push_str(&format!(
+                " -WorkingDirectory {}",
+                quote_powershell(&entry.working_directory)
+            ));
+        }
+        command
+    } else {
+        let spec = LaunchSpec {
+            path: entry.path.clone(),
+            arguments: entry.arguments.clone(),
+            env: entry.env.clone(),
+            working_directory: entry.working_directory.clone(),
+            priority: entry.priority,
+            run_as_admin: entry.run_as_admin,
+            run_as_user: entry.run_as_user.clone(),
+            ..Default::default()
+        };
+        build_powershell_launch_command(&spec, false)
+    };
+    lines.push(format!("powershell -NoProfile -Command {{ {} }}", command));
+    if entry.run_as_user.is_some() {
+        lines.push(format!(
+            "Remove-Item Env:{} -ErrorAction SilentlyContinue",
+            RUN_AS_PASSWORD_ENV
+        ));
+    }
+}
+
+// cmd では「%」を重ねてエスケープする
+fn escape_cmd(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+// CreateProcess と同じ規則でクォートし、cmd のメタ文字を含む場合はクォートの外に出ないよう必ずクォートする
+fn quote_cmd_arg(arg: &str) -> String {
+    let quoted = if arg.contains(['&', '|', '<', '>', '^', '(', ')']) {
+        quote_windows_arg_always(arg)
+    } else {
+        quote_windows_arg(arg)
+    };
+    escape_cmd(&quoted)
+}
+
+// setlocal・endlocal の間で環境変数を設定し、start で起動を待たずに次のアプリへ進む
+fn push_cmd(lines: &mut Vec<String>, entry: &ScriptEntry) {
+    lines.push(format!("REM {}", comment_name(&entry.name)));
+    if entry.is_url {
+        lines.push(format!("start \"\" {}", quote_cmd_arg(entry.path.trim())));
+        return;
+    }
+    if entry.run_as_admin {
+        lines.push("REM Run as administrator is not supported in cmd scripts".to_string());
+    }
+    if let Some(user) = &entry.run_as_user {
+        lines.push(format!(
+            "REM Run as user {} is not supported in cmd scripts",
+            comment_name(user)
+        ));
+    }
+    lines.push("setlocal".to_string());
+    let mut env: Vec<_> = entry.env.iter().collect();
+    env.sort();
+    for (key, value) in env {
+        lines.push(format!("set \"{}={}\"", escape_cmd(key), escape_cmd(value)));
+    }
+    let mut start = "start \"\"".to_string();
+    if !entry.working_directory.is_empty() {
+        start.push_str(&format!(" /D {}", quote_cmd_arg(&entry.working_directory)));
+    }
+    if let Some(priority) = entry.priority {
+        start.push_str(&format!(" /{}", priority.priority_class().to_uppercase()));
+    }
+    if entry.use_shell {
+        start.push_str(&format!(
+            " cmd /S /C \"{}\"",
+            escape_cmd(&shell_command_line(entry))
+        ));
+    } else {
+        start.push(' ');
+        start.push_str(
+            &std::iter::once(entry.path.clone())
+                .chain(parse_arguments(&entry.arguments))
+                .map(|arg| quote_cmd_arg(&arg))
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+    lines.push(start);
+    lines.push("endlocal".to_string());
+}
+
+// シングルクォートで囲む（中のシングルクォートは '\'' にする）
+fn quote_posix(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+// サブシェルで作業ディレクトリ・環境変数を設定し、バックグラウンドで起動する
+fn push_bash(lines: &mut Vec<String>, entry: &ScriptEntry) {
+    lines.push(format!("# {}", comment_name(&entry.name)));
+    if entry.is_url {
+        let opener = if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        lines.push(format!("{} {} &", opener, quote_posix(entry.path.trim())));
+        return;
+    }
+    if entry.run_as_admin {
+        lines.push("# Run as administrator is not supported in bash scripts".to_string());
+    }
+    if let Some(user) = &entry.run_as_user {
+        lines.push(format!(
+            "# Run as user {} is not supported in bash scripts",
+            comment_name(user)
+        ));
+    }
+    let mut command = vec!["exec".to_string()];
+    if !entry.env.is_empty() {
+        let mut env: Vec<_> = entry.env.iter().collect();
+        env.sort();
+        command.push("env".to_string());
+        command.extend(
+            env.into_iter()
+                .map(|(key, value)| quote_posix(&format!("{}={}", key, value))),
+        );
+    }
+    if let Some(priority) = entry.priority {
+        command.push(format!("nice -n {}", priority.nice_value()));
+    }
+    if entry.use_shell {
+        command.push("sh -c".to_string());
+        command.push(quote_posix(&shell_command_line(entry)));
+    } else {
+        command.push(quote_posix(&entry.path));
+        command.extend(
+            parse_arguments(&entry.arguments)
+                .iter()
+                .map(|a| quote_posix(a)),
+        );
+    }
+    let command = command.join(" ");
+    if entry.working_directory.is_empty() {
+        lines.push(format!("({}) &", command));
+    } else {
+        lines.push(format!(
+            "(cd -- {} && {}) &",
+            quote_posix(&entry.working_directory),
+            command
+        ));
+    }
+}
+
+// シェルで実行するアプリのコマンド文字列（build_shell_launch_command と同じくパスと引数をつなげる）
+fn shell_command_line(entry: &ScriptEntry) -> String {
+    format!("{} {}", entry.path.trim(), entry.arguments.trim())
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,372 @@
+// 引数の秘密の値（{secret:NAME}）
+//
+// APIキーなどを設定ファイルに平文で保存しないよう、値はOSの資格情報の保管場所に保存し、起動する時点で
+// 引数に入れる（保管場所にない場合は同じ名前の環境変数を使う）
+// - Windows: 資格情報マネージャー（汎用資格情報）
+// - macOS: キーチェーン（security コマンド。保存する値は標準入力で渡す）
+// - Linux: Secret Service（secret-tool コマンド。libsecret-tools）
+
+use std::sync::Mutex;
+
+use super::MutexExt;
+
+// 保管場所でランチャーの値を区別する名前
+const SERVICE: &str = "ajiponzu-utility-launcher";
+
+const PREFIX: &str = "{secret:";
+
+// 起動時に引数へ入れた値（ログに書き出す前に伏せるため）
+static RESOLVED_VALUES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// 秘密の名前を確認（英数字・「_」「-」「.」のみ）
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Err(format!(
+            "Invalid secret name '{}' (use letters, digits, '_', '-' and '.')",
+            name
+        ));
+    }
+    Ok(())
+}
+
+// 引数の {secret:NAME} を保管場所・環境変数の値で置き換える
+// 見つからない秘密がある場合は、起動しないようエラーにする
+pub fn resolve(arguments: &str) -> Result<String, String> {
+    if !arguments.contains(PREFIX) {
+        return Ok(arguments.to_string());
+    }
+
+    let mut result = String::with_capacity(arguments.len());
+    let mut missing = Vec::new();
+    let mut values = Vec::new();
+    let mut rest = arguments;
+    while let Some(start) = rest.find(PREFIX) {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + PREFIX.len()..];
+        let Some(end) = after.find('}') else {
+            result.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let name = &after[..end];
+        validate_name(name)?;
+        match lookup(name)? {
+            Some(value) => {
+                result.push_str(&value);
+                values.push(value);
+            }
+            None => missing.push(name.to_string()),
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+
+    if !missing.is_empty() {
+        return Err(format!(
+            "Secret(s) not found in the keyring or environment: {}",
+            missing.join(", ")
+        ));
+    }
+    let mut resolved = RESOLVED_VALUES.lock_or_recover();
+    for value in values {
+        if !value.is_empty() && !resolved.contains(&value) {
+            resolved.push(value);
+        }
+    }
+    Ok(result)
+}
+
+// ログに書き出す文字列から、起動時に引数へ入れた秘密の値を伏せる
+// （コマンドの Debug 表示ではエスケープされるため、エスケープした形も伏せる）
+pub fn redact(text: &str) -> String {
+    let resolved = RESOLVED_VALUES.lock_or_recover();
+    let mut redacted = text.to_string();
+    for value in resolved.iter() {
+        redacted = redacted.replace(value.as_str(), "***");
+        let escaped = format!("{:?}", value);
+        let escaped = &escaped[1..escaped.len() - 1];
+        if escaped != value {
+            redacted = redacted.replace(escaped, "***");
+        }
+    }
+    redacted
+}
+
+// 秘密の値を探す（保管場所を優先し、なければ同じ名前の環境変数）
+fn lookup(name: &str) -> Result<Option<String>, String> {
+    if let Some(value) = read(name)? {
+        return Ok(Some(value));
+    }
+    Ok(std::env::var(name).ok())
+}
+
+// 別のユーザーとして実行する場合のパスワードの保管場所での名前（Windowsのユーザー名は大文字小文字を区別しない）
+fn account_key(user: &str) -> String {
+    format!("user:{}", user.trim().to_lowercase())
+}
+
+// 別のユーザーとして実行する場合のパスワードを保存する
+pub fn store_account_password(user: &str, password: &str) -> Result<(), String> {
+    store(&account_key(user), password)
+}
+
+// 別のユーザーとして実行する場合のパスワードを削除する
+pub fn delete_account_password(user: &str) -> Result<(), String> {
+    delete(&account_key(user))
+}
+
+// 別のユーザーとして実行する場合のパスワードを取得する（保存していない場合は None）
+#[cfg(target_os = "windows")]
+pub fn account_password(user: &str) -> Result<Option<String>, String> {
+    read(&account_key(user))
+}
+
+// 保管場所に保存する（既にある場合は上書き）
+#[cfg(target_os = "windows")]
+pub fn store(name: &str, value: &str) -> Result<(), String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Security::Credentials::{
+        CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+    };
+
+    let mut target = wide(&target_name(name));
+    let mut user = wide(SERVICE);
+    let mut blob = value.as_bytes().to_vec();
+    let credential = CREDENTIALW {
+        Type: CRED_TYPE_GENERIC,
+        TargetName: PWSTR(target.as_mut_ptr()),
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        UserName: PWSTR(user.as_mut_ptr()),
+        ..Default::default()
+    };
+    unsafe { CredWriteW(&credential, 0) }
+        .map_err(|e| format!("Failed to store secret '{}': {}", name, e))
+}
+
+// 保管場所から削除する
+#[cfg(target_os = "windows")]
+pub fn delete(name: &str) -> Result<(), String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows::Win32::Security::Credentials::{CredDeleteW, CRED_TYPE_GENERIC};
+
+    let target = wide(&target_name(name));
+    match unsafe { CredDeleteW(PCWSTR(target.as_ptr()), CRED_TYPE_GENERIC, None) } {
+        Ok(()) => Ok(()),
+        Err(e) if e.code() == ERROR_NOT_FOUND.to_hresult() => {
+            Err(format!("Secret '{}' not found", name))
+        }
+        Err(e) => Err(format!("Failed to delete secret '{}': {}", name, e)),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read(name: &str) -> Result<Option<String>, String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows::Win32::Security::Credentials::{
+        CredFree, CredReadW, CREDENTIALW, CRED_TYPE_GENERIC,
+    };
+
+    let target = wide(&target_name(name));
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+    match unsafe {
+        CredReadW(
+            PCWSTR(target.as_ptr()),
+            CRED_TYPE_GENERIC,
+            None,
+            &mut credential,
+        )
+    } {
+        Ok(()) => {}
+        Err(e) if e.code() == ERROR_NOT_FOUND.to_hresult() => return Ok(None),
+        Err(e) => return Err(format!("Failed to read secret '{}': {}", name, e)),
+    }
+    let value = unsafe {
+        let blob = std::slice::from_raw_parts(
+            (*credential).CredentialBlob,
+            (*credential).CredentialBlobSize as usize,
+        );
+        let value = String::from_utf8_lossy(blob).into_owned();
+        CredFree(credential as *const _);
+        value
+    };
+    Ok(Some(value))
+}
+
+#[cfg(target_os = "windows")]
+fn target_name(name: &str) -> String {
+    format!("{}:{}", SERVICE, name)
+}
+
+#[cfg(target_os = "windows")]
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+// 保管場所に保存する（既にある場合は上書き）
+// 値が他のプロセスから引数として見えないよう、security の対話モードにコマンドを標準入力で渡す
+// （値は16進数で指定するため引用符のエスケープは不要。対話モードは失敗しても終了コードが変わらないため読み直して確認する）
+#[cfg(target_os = "macos")]
+pub fn store(name: &str, value: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let hex: String = value.bytes().map(|b| format!("{:02x}", b)).collect();
+    let mut child = Command::new("security")
+        .arg("-i")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run security: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(
+            stdin,
+            "add-generic-password -U -s {} -a {} -X {}",
+            SERVICE, name, hex
+        )
+        .map_err(|e| format!("Failed to store secret '{}': {}", name, e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to store secret '{}': {}", name, e))?;
+    if !output.status.success() || read(name)?.as_deref() != Some(value.trim_end_matches('\n')) {
+        return Err(format!(
+            "Failed to store secret '{}': {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// 保管場所から削除する
+#[cfg(target_os = "macos")]
+pub fn delete(name: &str) -> Result<(), String> {
+    match run_security(&["delete-generic-password", "-s", SERVICE, "-a", name]) {
+        Ok(_) => Ok(()),
+        Err(_) if read(name)?.is_none() => Err(format!("Secret '{}' not found", name)),
+        Err(e) => Err(format!("Failed to delete secret '{}': {}", name, e)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn read(name: &str) -> Result<Option<String>, String> {
+    // 見つからない場合は終了コード 44
+    let output = std::process::Command::new("security")
+        .args(["find-generic-password", "-s", SERVICE, "-a", name, "-w"])
+        .output()
+        .map_err(|e| format!("Failed to run security: {}", e))?;
+    match output.status.code() {
+        Some(0) => Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .trim_end_matches('\n')
+                .to_string(),
+        )),
+        Some(44) => Ok(None),
+        _ => Err(format!(
+            "Failed to read secret '{}': {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn run_security(args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = std::process::Command::new("security")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run security: {}", e))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+// 保管場所に保存する（既にある場合は上書き。値は標準入力で渡す）
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+pub fn store(name: &str, value: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("secret-tool")
+        .args(["store", "--label", &format!("{} {}", SERVICE, name)])
+        .args(["service", SERVICE, "name", name])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(secret_tool_error)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(value.as_bytes())
+            .map_err(|e| format!("Failed to store secret '{}': {}", name, e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to store secret '{}': {}", name, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to store secret '{}': {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// 保管場所から削除する
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+pub fn delete(name: &str) -> Result<(), String> {
+    if read(name)?.is_none() {
+        return Err(format!("Secret '{}' not found", name));
+    }
+    let output = std::process::Command::new("secret-tool")
+        .args(["clear", "service", SERVICE, "name", name])
+        .output()
+        .map_err(secret_tool_error)?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to delete secret '{}': {}",
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+// secret-tool がない場合は保管場所がないものとして環境変数だけを使う
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+fn read(name: &str) -> Result<Option<String>, String> {
+    let output = match std::process::Command::new("secret-tool")
+        .args(["lookup", "service", SERVICE, "name", name])
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(secret_tool_error(e)),
+    };
+    // 見つからない場合は何も出力せずに失敗する
+    if !output.status.success() || output.stdout.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+}
+
+#[cfg(all(not(target_os = "windows"), not(target_os = "macos")))]
+fn secret_tool_error(e: std::io::Error) -> String {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        "The keyring is not available: secret-tool (libsecret-tools) is not installed".to_string()
+    } else {
+        format!("Failed to run secret-tool: {}", e)
+    }
+}
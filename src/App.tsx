@@ -1,58 +1,430 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import Settings from "./components/Settings";
-import { RegisteredApp } from "./types";
+import {
+  AutoStoppedPayload,
+  FlappingDetectedPayload,
+  ProcessExitedPayload,
+  ProcessOutputPayload,
+  ProcessStats,
+  QuitRequestedPayload,
+  RegisteredApp,
+  StartupProgressPayload,
+  StartupSummaryEntry,
+} from "./types";
+import { errorCode, errorMessage } from "./errors";
 import "./App.css";
 import "./responsive.css";
 import "./app-theme.css";
 
+// アプリごとに表示する出力の最大行数
+const MAX_OUTPUT_LINES = 200;
+// 実行中のアプリのリソース使用量を取得し直す間隔（ミリ秒）
+const STATS_INTERVAL_MS = 3000;
+
+// 起動してからの時間を「時:分:秒」で表示
+const formatUptime = (secs: number) => {
+  const h = Math.floor(secs / 3600);
+  const m = Math.floor((secs % 3600) / 60);
+  const s = secs % 60;
+  return `${h}:${String(m).padStart(2, "0")}:${String(s).padStart(2, "0")}`;
+};
+
 function App() {
   const [showSettings, setShowSettings] = useState(false);
   const [registeredApps, setRegisteredApps] = useState<RegisteredApp[]>([]);
+  // イベントのリスナーから最新の一覧を参照する
+  const registeredAppsRef = useRef<RegisteredApp[]>([]);
+  registeredAppsRef.current = registeredApps;
   const [runningApps, setRunningApps] = useState<Set<string>>(new Set());
+  const [outputs, setOutputs] = useState<Record<string, string[]>>({});
+  const [icons, setIcons] = useState<Record<string, string>>({});
+  const [stats, setStats] = useState<Record<string, ProcessStats>>({});
+  // 自動起動の進み具合（app_id -> 最新の通知）
+  const [startupProgress, setStartupProgress] = useState<
+    Record<string, StartupProgressPayload>
+  >({});
+  // 一時停止中のアプリ（app_id）
+  const [suspendedApps, setSuspendedApps] = useState<Set<string>>(new Set());
+  // 自動起動の結果のまとめ（show_startup_summary が有効な場合のみ届く。閉じると null）
+  const [startupSummary, setStartupSummary] = useState<
+    StartupSummaryEntry[] | null
+  >(null);
+  // 起動に使う引数のプリセット（app_id -> プリセット名、空なら既定の引数）
+  const [selectedPresets, setSelectedPresets] = useState<
+    Record<string, string>
+  >({});
+  // セーフモードで起動した（自動起動していない）
+  const [safeMode, setSafeMode] = useState(false);
+  const [searchQuery, setSearchQuery] = useState("");
+  // 検索中の結果（検索していない場合は null）
+  const [searchResults, setSearchResults] = useState<RegisteredApp[] | null>(
+    null
+  );
 
   useEffect(() => {
     loadRegisteredApps();
     loadRunningApps();
+    showStartupWarnings();
+  }, []);
+
+  // 設定ファイルが読み込めなかった場合などの警告を表示
+  const showStartupWarnings = async () => {
+    try {
+      const warnings = await invoke<string[]>("get_startup_warnings");
+      if (warnings.length > 0) {
+        alert(`起動時に問題が見つかりました:\n${warnings.join("\n")}`);
+      }
+    } catch (error) {
+      console.error("Failed to get startup warnings:", error);
+    }
+  };
+
+  // 実行中のアプリのCPU・メモリの使用量を定期的に取得
+  useEffect(() => {
+    const loadStats = async () => {
+      const entries = await Promise.all(
+        [...runningApps].map(async (appId) => {
+          try {
+            const result = await invoke<ProcessStats>("get_process_stats", {
+              appId,
+            });
+            return [appId, result] as const;
+          } catch {
+            // URLやプロセスを追跡できないアプリは表示しない
+            return null;
+          }
+        })
+      );
+      setStats(
+        Object.fromEntries(
+          entries.filter(
+            (e): e is readonly [string, ProcessStats] => e !== null
+          )
+        )
+      );
+    };
+    loadStats();
+    const timer = setInterval(loadStats, STATS_INTERVAL_MS);
+    return () => clearInterval(timer);
+  }, [runningApps]);
+
+  // アプリが終了したら起動モードに戻す
+  useEffect(() => {
+    const unlisten = listen<ProcessExitedPayload>("process-exited", (event) => {
+      setRunningApps((prev) => {
+        const newSet = new Set(prev);
+        newSet.delete(event.payload.app_id);
+        return newSet;
+      });
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 起動直後の終了を繰り返して自動再起動を諦めたら知らせる
+  useEffect(() => {
+    const unlisten = listen<FlappingDetectedPayload>(
+      "flapping-detected",
+      (event) => {
+        const { app_id, runtime_ms, gave_up } = event.payload;
+        console.warn(`${app_id} exited after ${runtime_ms}ms`);
+        if (gave_up) {
+          const name =
+            registeredAppsRef.current.find((a) => a.id === app_id)?.name ??
+            app_id;
+          alert(
+            `${name} が起動直後の終了を繰り返したため、自動再起動を停止しました`
+          );
+        }
+      }
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 起動から一定時間が経って自動停止したアプリを停止済みにする
+  useEffect(() => {
+    const unlisten = listen<AutoStoppedPayload>(
+      "app-auto-stopped",
+      (event) => {
+        const { app_id, error } = event.payload;
+        const name =
+          registeredAppsRef.current.find((a) => a.id === app_id)?.name ??
+          app_id;
+        if (error) {
+          alert(`${name} の自動停止に失敗しました: ${errorMessage(error)}`);
+          return;
+        }
+        console.log(`Auto-stopped ${name}`);
+        setRunningApps((prev) => {
+          const next = new Set(prev);
+          next.delete(app_id);
+          return next;
+        });
+        setSuspendedApps((prev) => {
+          const next = new Set(prev);
+          next.delete(app_id);
+          return next;
+        });
+      }
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 自動起動の進み具合を受け取る
+  useEffect(() => {
+    const unlisten = listen<StartupProgressPayload>(
+      "startup-progress",
+      (event) => {
+        setStartupProgress((prev) => ({
+          ...prev,
+          [event.payload.app_id]: event.payload,
+        }));
+      }
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 自動起動がすべて終わった後の結果のまとめを受け取る
+  useEffect(() => {
+    const unlisten = listen<StartupSummaryEntry[]>(
+      "startup-summary",
+      (event) => {
+        setStartupSummary(event.payload);
+      }
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 出力の取得が有効なアプリの出力を受け取る
+  useEffect(() => {
+    const unlisten = listen<ProcessOutputPayload>("process-output", (event) => {
+      const { app_id, stream, line } = event.payload;
+      setOutputs((prev) => {
+        const lines = [
+          ...(prev[app_id] ?? []),
+          stream === "stderr" ? `[stderr] ${line}` : line,
+        ];
+        return { ...prev, [app_id]: lines.slice(-MAX_OUTPUT_LINES) };
+      });
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // トレイの「Quit」で実行中のアプリがある場合は終了してよいか確認する
+  useEffect(() => {
+    const unlisten = listen<QuitRequestedPayload>("quit-requested", (event) => {
+      const { running_count, stop_apps_on_quit } = event.payload;
+      const message = stop_apps_on_quit
+        ? `実行中のアプリが${running_count}個あります。ランチャーを終了するとアプリも停止します。終了しますか？`
+        : `実行中のアプリが${running_count}個あります（ランチャーを終了してもアプリは動作し続けます）。終了しますか？`;
+      if (confirm(message)) {
+        invoke("confirm_quit").catch((error) =>
+          console.error("Failed to quit:", error)
+        );
+      }
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 設定ディレクトリ（ネットワークドライブなど）に届かなくなったら知らせる
+  useEffect(() => {
+    const unlisten = listen<string>("config-unavailable", (event) => {
+      alert(
+        `設定ファイルを保存できません。届くようになるまで変更はメモリ上に保持し、後で保存します:\n${event.payload}`
+      );
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 設定ファイルが外部で変更されたら読み込み直す
+  useEffect(() => {
+    const unlisten = listen("config-reloaded", () => {
+      loadRegisteredApps();
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
   }, []);
 
   const loadRegisteredApps = async () => {
     try {
       const apps = await invoke<RegisteredApp[]>("get_registered_apps");
       setRegisteredApps(apps);
+      // 検索結果は古くなるため検索を解除する
+      setSearchQuery("");
+      setSearchResults(null);
+      loadIcons(apps);
     } catch (error) {
       console.error("Failed to load registered apps:", error);
     }
   };
 
+  // 名前・パス・説明で検索（一致度の高い順に並ぶ）
+  const searchApps = async (query: string) => {
+    setSearchQuery(query);
+    if (query.trim() === "") {
+      setSearchResults(null);
+      return;
+    }
+    try {
+      const apps = await invoke<RegisteredApp[]>("search_apps", { query });
+      setSearchResults(apps);
+    } catch (error) {
+      console.error("Failed to search apps:", error);
+    }
+  };
+
+  // アプリのアイコンを取得（パスが変わっている場合があるため毎回取得し直す。バックエンドでキャッシュされる）
+  const loadIcons = async (apps: RegisteredApp[]) => {
+    const entries = await Promise.all(
+      apps.map(async (app) => {
+        try {
+          const icon = await invoke<string>("get_app_icon", { appId: app.id });
+          return [app.id, icon] as const;
+        } catch (error) {
+          console.error(`Failed to load icon of ${app.name}:`, error);
+          return null;
+        }
+      })
+    );
+    setIcons(
+      Object.fromEntries(
+        entries.filter((e): e is readonly [string, string] => e !== null)
+      )
+    );
+  };
+
   const loadRunningApps = async () => {
     try {
       const apps = await invoke<RegisteredApp[]>("get_registered_apps");
-      const autoStartAppIds = apps
-        .filter((app) => app.auto_start)
-        .map((app) => app.id);
+      // セーフモードでは自動起動しないため、自動起動のアプリも実行中とみなさない
+      const isSafeMode = await invoke<boolean>("get_safe_mode");
+      setSafeMode(isSafeMode);
+      const autoStartAppIds = isSafeMode
+        ? []
+        : apps.filter((app) => app.auto_start).map((app) => app.id);
+
+      // ランチャーの起動前から実行中のアプリ
+      const runningProcesses = await invoke<[string, number][]>(
+        "get_running_processes"
+      );
+      const runningAppIds = runningProcesses.map(([appId]) => appId);
 
       // 実行中プロセスと自動起動アプリを合わせる
-      const allRunningIds = [...new Set([...autoStartAppIds])];
+      const allRunningIds = [
+        ...new Set([...runningAppIds, ...autoStartAppIds]),
+      ];
       setRunningApps(new Set(allRunningIds));
     } catch (error) {
       console.error("Failed to load running apps:", error);
     }
   };
 
+  // 引数のプレースホルダーに入れる値（{file} はバックエンドでファイルを選ぶ）
+  const buildLaunchContext = async (
+    app: RegisteredApp,
+    presetName: string | null
+  ) => {
+    const context: Record<string, string> = {};
+    const args =
+      app.arg_presets.find((preset) => preset.name === presetName)
+        ?.arguments ?? app.arguments;
+    if (app.placeholders && args.includes("{clipboard}")) {
+      context.clipboard = await navigator.clipboard.readText();
+    }
+    return context;
+  };
+
   const handleLaunchApp = async (app: RegisteredApp) => {
     try {
-      await invoke("launch_application", {
+      const presetName = selectedPresets[app.id] || null;
+      const pid = await invoke<number | null>("launch_application", {
         appId: app.id,
         path: app.path,
         arguments: app.arguments,
+        context: await buildLaunchContext(app, presetName),
+        presetName,
       });
+      console.log(`Launched ${app.name} (PID: ${pid ?? "untracked"})`);
 
-      // 起動後に実行状態を更新
+      // 起動後に実行状態と起動回数を更新
+      setRunningApps((prev) => new Set([...prev, app.id]));
+      loadRegisteredApps();
+    } catch (error) {
+      console.error("Failed to launch application:", error);
+      showLaunchError(error);
+    }
+  };
+
+  // 今回だけ引数を変えて起動する（登録した引数は変更しない）
+  const handleLaunchWithArgs = async (app: RegisteredApp) => {
+    const extraArguments = prompt(
+      `「${app.name}」を起動する引数を入力してください\n登録した引数: ${app.arguments || "（なし）"}`
+    );
+    if (extraArguments === null) return;
+    const replace = confirm(
+      "登録した引数の代わりに使いますか？\n（キャンセルすると登録した引数の後ろに追加します）"
+    );
+    try {
+      const pid = await invoke<number | null>("launch_with_args", {
+        appId: app.id,
+        extraArguments,
+        replace,
+      });
+      console.log(`Launched ${app.name} (PID: ${pid ?? "untracked"})`);
       setRunningApps((prev) => new Set([...prev, app.id]));
+      loadRegisteredApps();
     } catch (error) {
       console.error("Failed to launch application:", error);
-      alert(`アプリケーションの起動に失敗しました: ${error}`);
+      showLaunchError(error);
+    }
+  };
+
+  // 起動に失敗した理由に合わせてメッセージを表示
+  const showLaunchError = (error: unknown) => {
+    const message = errorMessage(error);
+    switch (errorCode(error)) {
+      case "NOT_FOUND":
+        if (
+          confirm(
+            `ファイルが見つかりません。設定で実行ファイルを選び直しますか？\n${message}`
+          )
+        ) {
+          setShowSettings(true);
+        }
+        break;
+      case "PERMISSION_DENIED":
+        alert(`アクセスが拒否されました: ${message}`);
+        break;
+      case "TIMEOUT":
+        alert(`起動がタイムアウトしました: ${message}`);
+        break;
+      case "INVALID_INPUT":
+        alert(`起動できないファイルです: ${message}`);
+        break;
+      case "DEBOUNCED":
+        // 続けて押された起動ボタンは無視する
+        console.info(message);
+        break;
+      default:
+        alert(`アプリケーションの起動に失敗しました: ${message}`);
     }
   };
 
@@ -67,6 +439,11 @@ function App() {
         newSet.delete(app.id);
         return newSet;
       });
+      setSuspendedApps((prev) => {
+        const next = new Set(prev);
+        next.delete(app.id);
+        return next;
+      });
 
       console.log(`Successfully stopped ${app.name}`);
     } catch (error) {
@@ -80,10 +457,62 @@ function App() {
       });
       
       console.log(`Switched ${app.name} to launch mode due to stop failure`);
-      alert(`アプリケーションの停止に失敗しました。起動モードに切り替えました: ${error}`);
+      alert(`アプリケーションの停止に失敗しました。起動モードに切り替えました: ${errorMessage(error)}`);
+    }
+  };
+
+  // 実行中のアプリを一時停止・再開する
+  const handleToggleSuspend = async (app: RegisteredApp) => {
+    const suspended = suspendedApps.has(app.id);
+    try {
+      await invoke(suspended ? "resume_application" : "suspend_application", {
+        appId: app.id,
+      });
+      setSuspendedApps((prev) => {
+        const next = new Set(prev);
+        if (suspended) {
+          next.delete(app.id);
+        } else {
+          next.add(app.id);
+        }
+        return next;
+      });
+    } catch (error) {
+      console.error("Failed to suspend/resume application:", error);
+      alert(
+        `アプリケーションの${suspended ? "再開" : "一時停止"}に失敗しました: ${errorMessage(error)}`
+      );
     }
   };
 
+  // 実行中のアプリのウィンドウを前面に表示する
+  const handleFocusApp = async (app: RegisteredApp) => {
+    try {
+      const focused = await invoke<boolean>("focus_application", {
+        appId: app.id,
+      });
+      if (!focused) {
+        alert(`${app.name} のウィンドウが見つかりませんでした`);
+      }
+    } catch (error) {
+      console.error("Failed to focus application:", error);
+      alert(`ウィンドウの表示に失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleStopOutput = async (app: RegisteredApp) => {
+    try {
+      await invoke("stop_output_capture", { appId: app.id });
+    } catch (error) {
+      console.error("Failed to stop output capture:", error);
+    }
+    setOutputs((prev) => {
+      const next = { ...prev };
+      delete next[app.id];
+      return next;
+    });
+  };
+
   async function hideWindow() {
     try {
       await invoke("hide_window");
@@ -98,6 +527,14 @@ function App() {
     loadRunningApps(); // 実行状態も更新
   };
 
+  // 自動起動が終わっていないアプリ（すべて終わったら表示しない）
+  const startupEntries = Object.values(startupProgress);
+  const startupTotal = startupEntries[0]?.total ?? 0;
+  const startupFinished = startupEntries.filter((p) =>
+    ["done", "failed", "skipped"].includes(p.phase)
+  ).length;
+  const startupWaiting = startupEntries.find((p) => p.phase === "waiting");
+
   return (
     <main className="app-container">
       <div className="header">
@@ -120,6 +557,66 @@ function App() {
         </div>
       </div>
 
+      {safeMode && (
+        <p className="startup-progress safe-mode">
+          ⚠️
+          セーフモードで起動しています。自動起動は行っていません（設定を直した後、ランチャーを通常どおり起動し直してください）
+        </p>
+      )}
+
+      {startupTotal > 0 && startupFinished < startupTotal && (
+        <p className="startup-progress">
+          自動起動中: {startupFinished} / {startupTotal}
+          {startupWaiting &&
+            startupWaiting.remaining_delay_ms > 0 &&
+            `（${
+              registeredApps.find((a) => a.id === startupWaiting.app_id)
+                ?.name ?? startupWaiting.app_id
+            } を ${Math.ceil(
+              startupWaiting.remaining_delay_ms / 1000
+            )} 秒後に起動）`}
+        </p>
+      )}
+
+      {startupSummary && startupSummary.length > 0 && (
+        <div className="startup-progress startup-summary">
+          <div>
+            自動起動の結果:{" "}
+            {
+              startupSummary.filter((entry) => entry.status === "launched")
+                .length
+            }{" "}
+            件起動・
+            {
+              startupSummary.filter((entry) => entry.status === "skipped")
+                .length
+            }{" "}
+            件スキップ・
+            {
+              startupSummary.filter((entry) => entry.status === "failed")
+                .length
+            }{" "}
+            件失敗{" "}
+            <button onClick={() => setStartupSummary(null)} title="閉じる">
+              ✕
+            </button>
+          </div>
+          <ul>
+            {startupSummary.map((entry) => (
+              <li key={entry.app_id}>
+                {entry.status === "launched"
+                  ? "✅"
+                  : entry.status === "skipped"
+                  ? "⏭️"
+                  : "❌"}{" "}
+                {entry.name}
+                {entry.detail && `: ${entry.detail}`}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
       <div className="content">
         {registeredApps.length === 0 ? (
           <div className="no-apps">
@@ -133,33 +630,134 @@ function App() {
           </div>
         ) : (
           <div className="apps-list">
-            {registeredApps.map((app) => {
+            <input
+              type="search"
+              className="app-search"
+              value={searchQuery}
+              onChange={(e) => searchApps(e.target.value)}
+              placeholder="アプリを検索"
+            />
+            {(searchResults ?? registeredApps).map((app) => {
               const isRunning = runningApps.has(app.id);
               console.log(runningApps);
               console.log(
                 `App: ${app.name}, ID: ${app.id}, isRunning: ${isRunning}`
               );
               return (
-                <div key={app.id} className="app-item">
+                <div
+                  key={app.id}
+                  className="app-item"
+                  style={
+                    app.color
+                      ? { borderLeft: `4px solid ${app.color}` }
+                      : undefined
+                  }
+                >
                   <div className="app-info">
-                    <h3>{app.name}</h3>
+                    <h3>
+                      {icons[app.id] && (
+                        <img className="app-icon" src={icons[app.id]} alt="" />
+                      )}
+                      {app.name}
+                    </h3>
                     <p>{app.description}</p>
+                    <p className="app-stats">
+                      起動回数: {app.launch_count ?? 0}
+                      {app.last_launched &&
+                        ` / 最終起動: ${new Date(
+                          app.last_launched
+                        ).toLocaleString()}`}
+                    </p>
+                    {isRunning && stats[app.id] && (
+                      <p className="app-stats">
+                        CPU: {stats[app.id].cpu_percent.toFixed(1)}% / メモリ:{" "}
+                        {(stats[app.id].memory_bytes / 1024 / 1024).toFixed(1)}{" "}
+                        MB / 稼働時間: {formatUptime(stats[app.id].uptime_secs)}
+                      </p>
+                    )}
+                    {outputs[app.id] && (
+                      <div className="app-output">
+                        <pre>{outputs[app.id].join("\n")}</pre>
+                        <button
+                          onClick={() => handleStopOutput(app)}
+                          className="output-stop-btn"
+                        >
+                          出力を閉じる
+                        </button>
+                      </div>
+                    )}
                   </div>
                   <div className="app-actions">
-                    {!isRunning ? (
-                      <button
-                        onClick={() => handleLaunchApp(app)}
-                        className="launch-btn"
+                    {!isRunning && app.arg_presets.length > 0 && (
+                      <select
+                        className="preset-select"
+                        value={selectedPresets[app.id] ?? ""}
+                        onChange={(e) =>
+                          setSelectedPresets((prev) => ({
+                            ...prev,
+                            [app.id]: e.target.value,
+                          }))
+                        }
+                        title="起動する引数のプリセット"
                       >
-                        起動
-                      </button>
+                        <option value="">既定の引数</option>
+                        {app.arg_presets.map((preset) => (
+                          <option key={preset.name} value={preset.name}>
+                            {preset.name}
+                          </option>
+                        ))}
+                      </select>
+                    )}
+                    {!isRunning ? (
+                      <>
+                        <button
+                          onClick={() => handleLaunchApp(app)}
+                          className="launch-btn"
+                        >
+                          起動
+                        </button>
+                        <button
+                          onClick={() => handleLaunchWithArgs(app)}
+                          className="launch-btn"
+                          title="今回だけ引数を変えて起動します（登録した引数は変更しません）"
+                        >
+                          引数…
+                        </button>
+                      </>
                     ) : (
-                      <button
-                        onClick={() => handleStopApp(app)}
-                        className="stop-btn"
-                      >
-                        停止
-                      </button>
+                      <>
+                        {app.on_already_running === "focus" && (
+                          <button
+                            onClick={() => handleFocusApp(app)}
+                            className="launch-btn"
+                            title="実行中のアプリのウィンドウを前面に表示します"
+                          >
+                            表示
+                          </button>
+                        )}
+                        {app.on_already_running === "relaunch" && (
+                          <button
+                            onClick={() => handleLaunchApp(app)}
+                            className="launch-btn"
+                            title="実行中のアプリを停止して起動し直します"
+                          >
+                            再起動
+                          </button>
+                        )}
+                        <button
+                          onClick={() => handleToggleSuspend(app)}
+                          className="suspend-btn"
+                          title="終了せずにプロセスを一時停止・再開します"
+                        >
+                          {suspendedApps.has(app.id) ? "再開" : "一時停止"}
+                        </button>
+                        <button
+                          onClick={() => handleStopApp(app)}
+                          className="stop-btn"
+                        >
+                          停止
+                        </button>
+                      </>
                     )}
                   </div>
                 </div>
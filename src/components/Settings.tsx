@@ -1,6 +1,27 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { RegisteredApp } from "../types";
+import { listen } from "@tauri-apps/api/event";
+import {
+  ArgPreset,
+  AutostartStatus,
+  ConfigBackup,
+  ConfigInfo,
+  Diagnostics,
+  ExeInfo,
+  ForeignFormat,
+  ForeignImportSkipped,
+  HealthCheck,
+  LaunchCondition,
+  LaunchEvent,
+  OnAlreadyRunning,
+  PathGlobPick,
+  PowerShellStatus,
+  RegisteredApp,
+  SystemProcess,
+  TestResult,
+  ValidationIssue,
+} from "../types";
+import { errorCode, errorMessage } from "../errors";
 import "./Settings.css";
 import "../responsive.css";
 import "../settings-theme.css";
@@ -9,6 +30,131 @@ interface SettingsProps {
   onClose: () => void;
 }
 
+// 環境変数を「KEY=VALUE」形式の行に変換
+const formatEnvText = (env: Record<string, string>) =>
+  Object.entries(env)
+    .map(([key, value]) => `${key}=${value}`)
+    .join("\n");
+
+// 「KEY=VALUE」形式の行を環境変数に変換（空行・キーのない行は無視）
+const parseEnvText = (text: string) => {
+  const env: Record<string, string> = {};
+  for (const line of text.split("\n")) {
+    const index = line.indexOf("=");
+    if (index <= 0) continue;
+    env[line.slice(0, index).trim()] = line.slice(index + 1);
+  }
+  return env;
+};
+
+// 引数のプリセットを「名前=引数」形式の行に変換
+const formatArgPresetsText = (presets: ArgPreset[]) =>
+  presets.map((preset) => `${preset.name}=${preset.arguments}`).join("\n");
+
+// 「名前=引数」形式の行を引数のプリセットに変換（空行・名前のない行は無視）
+const parseArgPresetsText = (text: string): ArgPreset[] =>
+  text
+    .split("\n")
+    .map((line) => [line.indexOf("="), line] as const)
+    .filter(([index]) => index > 0)
+    .map(([index, line]) => ({
+      name: line.slice(0, index).trim(),
+      arguments: line.slice(index + 1),
+    }));
+
+// パスにワイルドカード（* ? [）を含むか（URLは対象外）
+const isPathPattern = (path: string) =>
+  !/^[a-z][a-z0-9+.-]+:/i.test(path.trim()) && /[*?[]/.test(path);
+
+// 「0,1,2」形式のコア番号を配列に変換（空の場合は指定なし）
+const parseCpuAffinity = (text: string) => {
+  const cores = text
+    .split(",")
+    .map((core) => core.trim())
+    .filter((core) => /^\d+$/.test(core))
+    .map(Number);
+  return cores.length > 0 ? cores : null;
+};
+
+// ヘルスチェックの入力（TCPは "ポート" または "ホスト:ポート"、HTTPはURL）から設定を組み立てる
+// 編集中のアプリのタイムアウト・再試行の設定は引き継ぐ
+const buildHealthCheck = (
+  type: string,
+  target: string,
+  previous: HealthCheck | null | undefined
+): HealthCheck | null => {
+  const options = {
+    timeout_ms: previous?.timeout_ms ?? 2000,
+    retries: previous?.retries ?? 10,
+    retry_interval_ms: previous?.retry_interval_ms ?? 1000,
+  };
+  const value = target.trim();
+  if (type === "tcp" && value !== "") {
+    const separator = value.lastIndexOf(":");
+    const host = separator >= 0 ? value.slice(0, separator) : "127.0.0.1";
+    const port = Number(separator >= 0 ? value.slice(separator + 1) : value);
+    return { type: "tcp", host, port, ...options };
+  }
+  if (type === "http" && value !== "") {
+    const expected_status =
+      previous?.type === "http" ? previous.expected_status : 200;
+    return { type: "http", url: value, expected_status, ...options };
+  }
+  return null;
+};
+
+// 自動起動の条件の入力（時間帯は "09:00-17:00"、プロセスは名前、TCPは "ホスト:ポート"）から設定を組み立てる
+const buildLaunchCondition = (
+  type: string,
+  target: string
+): LaunchCondition | null => {
+  const value = target.trim();
+  if (value === "") return null;
+  if (type === "time_window") {
+    const [start, end] = value.split("-").map((time) => time.trim());
+    return { type, start, end: end ?? "" };
+  }
+  if (type === "process_running") {
+    return { type, name: value };
+  }
+  if (type === "tcp_reachable") {
+    const separator = value.lastIndexOf(":");
+    return {
+      type,
+      host: value.slice(0, separator),
+      port: Number(value.slice(separator + 1)),
+      timeout_ms: 1000,
+    };
+  }
+  return null;
+};
+
+// 設定済みの自動起動の条件を入力欄の文字列にする
+const launchConditionTarget = (
+  condition: LaunchCondition | null | undefined
+) => {
+  switch (condition?.type) {
+    case "time_window":
+      return `${condition.start}-${condition.end}`;
+    case "process_running":
+      return condition.name;
+    case "tcp_reachable":
+      return `${condition.host}:${condition.port}`;
+    default:
+      return "";
+  }
+};
+
+// 設定済みのヘルスチェックを入力欄の文字列にする
+const healthCheckTarget = (check: HealthCheck | null | undefined) => {
+  if (check?.type === "tcp") {
+    return check.host === "127.0.0.1"
+      ? String(check.port)
+      : `${check.host}:${check.port}`;
+  }
+  return check?.type === "http" ? check.url : "";
+};
+
 export default function Settings({ onClose }: SettingsProps) {
   const [registeredApps, setRegisteredApps] = useState<RegisteredApp[]>([]);
   const [isLoading, setIsLoading] = useState(true);
@@ -16,6 +162,39 @@ export default function Settings({ onClose }: SettingsProps) {
   // アプリ追加・編集用のフォーム
   const [showAddForm, setShowAddForm] = useState(false);
   const [editingApp, setEditingApp] = useState<RegisteredApp | null>(null);
+  // 入力中のホットキーを確かめた結果（問題がない場合は null）
+  const [hotkeyError, setHotkeyError] = useState<string | null>(null);
+  // スタートメニューから見つかった登録候補（未取得の場合は null）
+  const [startMenuCandidates, setStartMenuCandidates] = useState<
+    RegisteredApp[] | null
+  >(null);
+  const [isScanning, setIsScanning] = useState(false);
+  // 実行中のプロセスの一覧（未取得の場合は null）
+  const [systemProcesses, setSystemProcesses] = useState<
+    SystemProcess[] | null
+  >(null);
+  // 使用中の設定ファイルの情報
+  const [configInfo, setConfigInfo] = useState<ConfigInfo | null>(null);
+  // PowerShellが使えず機能が制限されているか（Windowsのみ）
+  // OSの自動起動へのランチャーの登録状態
+  const [autostartStatus, setAutostartStatus] =
+    useState<AutostartStatus | null>(null);
+  const [powerShellStatus, setPowerShellStatus] =
+    useState<PowerShellStatus | null>(null);
+  // 設定の確認で見つかった問題（未確認の場合は null）
+  const [validationIssues, setValidationIssues] = useState<
+    ValidationIssue[] | null
+  >(null);
+  // 設定のバックアップの一覧（表示していない場合は null）
+  const [configBackups, setConfigBackups] = useState<ConfigBackup[] | null>(
+    null
+  );
+  // 起動の履歴（表示していない場合は null）
+  const [launchHistory, setLaunchHistory] = useState<LaunchEvent[] | null>(
+    null
+  );
+  const [isTesting, setIsTesting] = useState(false);
+  const [isComputingHash, setIsComputingHash] = useState(false);
   const [formData, setFormData] = useState({
     name: "",
     path: "",
@@ -24,10 +203,86 @@ export default function Settings({ onClose }: SettingsProps) {
     delay: 0,
     preventDuplicate: false,
     autoStart: false,
+    logOutput: false,
+    watchPath: "",
+    watchRelaunchWhileRunning: false,
+    workingDirectory: "",
+    envText: "",
+    argPresetsText: "",
+    pathGlobPick: "highest_name" as PathGlobPick,
+    onAlreadyRunning: "do_nothing" as OnAlreadyRunning,
+    runAsAdmin: false,
+    autoRestart: false,
+    category: "",
+    dependsOn: [] as string[],
+    hotkey: "",
+    schedule: "",
+    delayMs: "",
+    delayJitterMs: 0,
+    skipIfRunning: false,
+    enabled: true,
+    captureOutput: false,
+    priority: "",
+    logTruncate: false,
+    useShell: false,
+    launchRetries: 0,
+    launchRetryDelayMs: 0,
+    stopCommand: "",
+    cpuAffinity: "",
+    healthCheckType: "",
+    healthCheckTarget: "",
+    launchConditionType: "",
+    launchConditionTarget: "",
+    preLaunch: "",
+    postLaunch: "",
+    hooksRequired: true,
+    detached: false,
+    placeholders: false,
+    launchOncePerBoot: false,
+    iconPath: "",
+    color: "",
+    minHealthyRuntimeMs: 0,
+    autoStopAfterMs: "",
+    autoElevate: false,
+    stopSettleMs: 0,
+    expectedSha256: "",
+    runAsUser: "",
   });
 
   useEffect(() => {
     loadRegisteredApps();
+    invoke<PowerShellStatus>("get_powershell_status")
+      .then(setPowerShellStatus)
+      .catch((error) =>
+        console.error("Failed to get PowerShell status:", error)
+      );
+  }, []);
+
+  // 設定ファイルが外部で変更されたら読み込み直す
+  useEffect(() => {
+    const unlisten = listen("config-reloaded", () => {
+      loadRegisteredApps();
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  // 他のランチャーの設定で変換できなかったエントリを知らせる
+  useEffect(() => {
+    const unlisten = listen<ForeignImportSkipped>(
+      "foreign-import-skipped",
+      (event) => {
+        const lines = event.payload.skipped.map(
+          (entry) =>
+            `#${entry.index}${entry.name ? ` (${entry.name})` : ""}: ${entry.reason}`
+        );
+        alert(`読み込めなかった項目があります:\n${lines.join("\n")}`);
+      }
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
   }, []);
 
   const loadRegisteredApps = async () => {
@@ -35,6 +290,10 @@ export default function Settings({ onClose }: SettingsProps) {
     try {
       const apps = await invoke<RegisteredApp[]>("get_registered_apps");
       setRegisteredApps(apps);
+      setConfigInfo(await invoke<ConfigInfo>("get_config_info"));
+      setAutostartStatus(
+        await invoke<AutostartStatus>("get_autostart_status").catch(() => null)
+      );
     } catch (error) {
       console.error("Failed to load registered apps:", error);
     } finally {
@@ -51,9 +310,54 @@ export default function Settings({ onClose }: SettingsProps) {
       delay: 0,
       preventDuplicate: false,
       autoStart: false,
+      logOutput: false,
+      watchPath: "",
+      watchRelaunchWhileRunning: false,
+      workingDirectory: "",
+      envText: "",
+      argPresetsText: "",
+      pathGlobPick: "highest_name",
+      onAlreadyRunning: "do_nothing",
+      runAsAdmin: false,
+      autoRestart: false,
+      category: "",
+      dependsOn: [],
+      hotkey: "",
+      schedule: "",
+      delayMs: "",
+      delayJitterMs: 0,
+      skipIfRunning: false,
+      enabled: true,
+      captureOutput: false,
+      priority: "",
+      logTruncate: false,
+      useShell: false,
+      launchRetries: 0,
+      launchRetryDelayMs: 0,
+      stopCommand: "",
+      cpuAffinity: "",
+    healthCheckType: "",
+    healthCheckTarget: "",
+    launchConditionType: "",
+    launchConditionTarget: "",
+      preLaunch: "",
+      postLaunch: "",
+      hooksRequired: true,
+      detached: false,
+      placeholders: false,
+      launchOncePerBoot: false,
+      iconPath: "",
+      color: "",
+      minHealthyRuntimeMs: 0,
+      autoStopAfterMs: "",
+      autoElevate: false,
+      stopSettleMs: 0,
+      expectedSha256: "",
+      runAsUser: "",
     });
     setShowAddForm(false);
     setEditingApp(null);
+    setHotkeyError(null);
   };
 
   const handleAdd = () => {
@@ -62,7 +366,17 @@ export default function Settings({ onClose }: SettingsProps) {
     setShowAddForm(true);
   };
 
-  const handleEdit = (app: RegisteredApp) => {
+  const handleEdit = async (listedApp: RegisteredApp) => {
+    // 一覧の取得後に変更されている場合があるため最新の設定を取得
+    let app = listedApp;
+    try {
+      app = await invoke<RegisteredApp>("get_registered_app", {
+        id: listedApp.id,
+      });
+    } catch (error) {
+      console.error("Failed to load registered app:", error);
+    }
+
     setFormData({
       name: app.name,
       path: app.path,
@@ -71,50 +385,265 @@ export default function Settings({ onClose }: SettingsProps) {
       delay: app.delay || 0,
       preventDuplicate: app.prevent_duplicate || false,
       autoStart: app.auto_start || false,
+      logOutput: app.log_output || false,
+      watchPath: app.watch_path || "",
+      watchRelaunchWhileRunning: app.watch_relaunch_while_running || false,
+      workingDirectory: app.working_directory || "",
+      envText: formatEnvText(app.env || {}),
+      argPresetsText: formatArgPresetsText(app.arg_presets || []),
+      pathGlobPick: app.path_glob_pick || "highest_name",
+      onAlreadyRunning: app.on_already_running || "do_nothing",
+      runAsAdmin: app.run_as_admin || false,
+      autoRestart: app.auto_restart || false,
+      category: app.category || "",
+      dependsOn: app.depends_on || [],
+      hotkey: app.hotkey || "",
+      schedule: app.schedule || "",
+      delayMs: app.delay_ms != null ? String(app.delay_ms) : "",
+      delayJitterMs: app.delay_jitter_ms || 0,
+      skipIfRunning: app.skip_if_running || false,
+      enabled: app.enabled ?? true,
+      captureOutput: app.capture_output || false,
+      priority: app.priority || "",
+      logTruncate: app.log_truncate || false,
+      useShell: app.use_shell || false,
+      launchRetries: app.launch_retries || 0,
+      launchRetryDelayMs: app.launch_retry_delay_ms || 0,
+      stopCommand: app.stop_command || "",
+      cpuAffinity: app.cpu_affinity?.join(",") ?? "",
+      healthCheckType: app.health_check?.type ?? "",
+      healthCheckTarget: healthCheckTarget(app.health_check),
+      launchConditionType: app.launch_condition?.type ?? "",
+      launchConditionTarget: launchConditionTarget(app.launch_condition),
+      preLaunch: app.pre_launch || "",
+      postLaunch: app.post_launch || "",
+      hooksRequired: app.hooks_required ?? true,
+      detached: app.detached || false,
+      placeholders: app.placeholders || false,
+      launchOncePerBoot: app.launch_once_per_boot || false,
+      iconPath: app.icon_path || "",
+      color: app.color || "",
+      minHealthyRuntimeMs: app.min_healthy_runtime_ms || 0,
+      autoStopAfterMs: app.auto_stop_after_ms != null ? String(app.auto_stop_after_ms) : "",
+      autoElevate: app.auto_elevate || false,
+      stopSettleMs: app.stop_settle_ms || 0,
+      expectedSha256: app.expected_sha256 || "",
+      runAsUser: app.run_as_user || "",
     });
     setEditingApp(app);
+    setHotkeyError(null);
     setShowAddForm(true);
   };
 
+  // ホットキーを保存せずに確かめる（書式・他のアプリとの重複・他のアプリケーションによる使用）
+  const checkHotkey = async (hotkey: string) => {
+    const error = await invoke("validate_hotkey", {
+      accelerator: hotkey,
+      forAppId: editingApp?.id ?? null,
+    }).then(
+      () => null,
+      (e) => errorMessage(e)
+    );
+    setHotkeyError(error);
+    return error;
+  };
+
+  // 登録せずに起動して結果を表示（起動し続けているプロセスはバックエンドで停止される）
+  const handleTestLaunch = async () => {
+    if (!formData.path) {
+      alert("パスを入力してください");
+      return;
+    }
+    setIsTesting(true);
+    try {
+      const result = await invoke<TestResult>("test_launch", {
+        path: formData.path,
+        arguments: formData.arguments,
+        workingDirectory: formData.workingDirectory,
+      });
+      const lines = !result.started
+        ? [`起動できませんでした: ${result.error}`]
+        : result.exited
+        ? [
+            `起動直後に終了しました（PID: ${result.pid ?? "不明"}、終了コード: ${
+              result.exit_code ?? "不明"
+            }）`,
+          ]
+        : [`起動できました（PID: ${result.pid ?? "不明"}）。確認のため停止しました`];
+      if (result.stderr) {
+        lines.push("", "標準エラー:", result.stderr);
+      }
+      alert(lines.join("\n"));
+    } catch (error) {
+      console.error("Failed to test launch:", error);
+      alert(`試験起動に失敗しました: ${errorMessage(error)}`);
+    } finally {
+      setIsTesting(false);
+    }
+  };
+
   const handleSave = async () => {
     if (!formData.name || !formData.path) {
       alert("名前とパスは必須です");
       return;
     }
 
-    try {
-      if (editingApp) {
-        // 既存アプリの更新
-        await invoke("update_registered_app", {
-          id: editingApp.id,
-          name: formData.name,
-          path: formData.path,
-          arguments: formData.arguments,
-          description: formData.description,
-          delay: formData.delay,
-          preventDuplicate: formData.preventDuplicate,
-          autoStart: formData.autoStart,
-        });
-      } else {
-        // 新規アプリの追加
-        await invoke("add_registered_app", {
-          name: formData.name,
-          path: formData.path,
-          arguments: formData.arguments,
-          description: formData.description,
-          delay: formData.delay,
-          preventDuplicate: formData.preventDuplicate,
-          autoStart: formData.autoStart,
-        });
+    // ワイルドカードのパスは、今一致するファイルがなければ確認する（起動時に解決する）
+    if (!formData.useShell && isPathPattern(formData.path)) {
+      const error = await invoke<string>("resolve_path_pattern", {
+        path: formData.path,
+        pick: formData.pathGlobPick,
+      }).then(
+        () => null,
+        (e) => errorMessage(e)
+      );
+      if (error && !confirm(`${errorMessage(error)}\n\n現在一致するファイルがありません。このまま保存しますか？`)) {
+        return;
       }
-      resetForm();
-      loadRegisteredApps();
+    }
+
+    // 同じ実行ファイルが登録済みの場合は確認する（追加する場合のみ）
+    if (!editingApp && !formData.useShell) {
+      const sameApps = await invoke<RegisteredApp[]>("find_apps_by_path", {
+        path: formData.path,
+      }).catch(() => []);
+      if (
+        sameApps.length > 0 &&
+        !confirm(
+          `この実行ファイルは「${sameApps.map((a) => a.name).join("」「")}」として登録済みです。\n\nこのまま追加しますか？`
+        )
+      ) {
+        return;
+      }
+    }
+
+    // このマシンで動作しない実行ファイル（アーキテクチャ・OSの不一致）の場合は確認する
+    if (!formData.useShell && !isPathPattern(formData.path)) {
+      const info = await invoke<ExeInfo>("inspect_executable", {
+        path: formData.path,
+      }).catch(() => null);
+      if (info?.warning && !confirm(`${info.warning}\n\nこのまま保存しますか？`)) {
+        return;
+      }
+    }
+
+    // ホットキーが使えない場合は確認する
+    if (formData.hotkey.trim()) {
+      const error = await checkHotkey(formData.hotkey);
+      if (error && !confirm(`${errorMessage(error)}\n\nこのまま保存しますか？`)) {
+        return;
+      }
+    }
+
+    // 重複起動禁止のプロセス名が多くのプロセスに一致する場合は確認する
+    if (formData.preventDuplicate) {
+      const warning = await invoke<string | null>(
+        "check_process_name_conflicts",
+        { path: formData.path }
+      ).catch(() => null);
+      if (warning && !confirm(`${warning}\n\nこのまま保存しますか？`)) {
+        return;
+      }
+    }
+
+    try {
+      await saveApp(false);
     } catch (error) {
+      // 同じ名前のアプリがある場合は確認してから保存する
+      if (
+        errorCode(error) === "DUPLICATE_NAME" &&
+        confirm(
+          `同じ名前のアプリが既に登録されています。\n${errorMessage(error)}\n\nこのまま保存しますか？`
+        )
+      ) {
+        try {
+          await saveApp(true);
+        } catch (retryError) {
+          console.error("Failed to save app:", retryError);
+          alert("保存に失敗しました");
+        }
+        return;
+      }
       console.error("Failed to save app:", error);
       alert("保存に失敗しました");
     }
   };
 
+  const saveApp = async (allowDuplicateName: boolean) => {
+    // 登録・更新するアプリの内容（Rust 側の RegisteredApp。ID・表示順・起動回数は送らない）
+    const input = {
+      name: formData.name,
+      path: formData.path,
+      arguments: formData.arguments,
+      description: formData.description,
+      delay: formData.delay,
+      preventDuplicate: formData.preventDuplicate,
+      autoStart: formData.autoStart,
+      logOutput: formData.logOutput,
+      watchPath: formData.watchPath || null,
+      watchRelaunchWhileRunning: formData.watchRelaunchWhileRunning,
+      workingDirectory: formData.workingDirectory,
+      env: parseEnvText(formData.envText),
+      argPresets: parseArgPresetsText(formData.argPresetsText),
+      pathGlobPick: formData.pathGlobPick,
+      onAlreadyRunning: formData.onAlreadyRunning,
+      runAsAdmin: formData.runAsAdmin,
+      autoRestart: formData.autoRestart,
+      category: formData.category,
+      dependsOn: formData.dependsOn,
+      hotkey: formData.hotkey || null,
+      schedule: formData.schedule || null,
+      delayMs: formData.delayMs === "" ? null : Number(formData.delayMs),
+      delayJitterMs: formData.delayJitterMs,
+      skipIfRunning: formData.skipIfRunning,
+      enabled: formData.enabled,
+      captureOutput: formData.captureOutput,
+      priority: formData.priority || null,
+      logTruncate: formData.logTruncate,
+      useShell: formData.useShell,
+      launchRetries: formData.launchRetries,
+      launchRetryDelayMs: formData.launchRetryDelayMs,
+      stopCommand: formData.stopCommand || null,
+      cpuAffinity: parseCpuAffinity(formData.cpuAffinity),
+      healthCheck: buildHealthCheck(
+        formData.healthCheckType,
+        formData.healthCheckTarget,
+        editingApp?.health_check
+      ),
+      launchCondition: buildLaunchCondition(
+        formData.launchConditionType,
+        formData.launchConditionTarget
+      ),
+      preLaunch: formData.preLaunch || null,
+      postLaunch: formData.postLaunch || null,
+      hooksRequired: formData.hooksRequired,
+      detached: formData.detached,
+      placeholders: formData.placeholders,
+      launchOncePerBoot: formData.launchOncePerBoot,
+      iconPath: formData.iconPath || null,
+      color: formData.color || null,
+      minHealthyRuntimeMs: formData.minHealthyRuntimeMs,
+      autoStopAfterMs: formData.autoStopAfterMs === "" ? null : Number(formData.autoStopAfterMs),
+      autoElevate: formData.autoElevate,
+      stopSettleMs: formData.stopSettleMs,
+      expectedSha256: formData.expectedSha256 || null,
+      runAsUser: formData.runAsUser || null,
+    };
+    if (editingApp) {
+      // 既存アプリの更新
+      await invoke("update_registered_app", {
+        id: editingApp.id,
+        input,
+        allowDuplicateName,
+      });
+    } else {
+      // 新規アプリの追加
+      await invoke("add_registered_app", { input, allowDuplicateName });
+    }
+    resetForm();
+    loadRegisteredApps();
+  };
+
   const handleDelete = async (app: RegisteredApp) => {
     if (confirm(`「${app.name}」を削除しますか？`)) {
       try {
@@ -127,6 +656,467 @@ export default function Settings({ onClose }: SettingsProps) {
     }
   };
 
+  const handleDuplicate = async (app: RegisteredApp) => {
+    try {
+      const newApp = await invoke<RegisteredApp>("duplicate_registered_app", {
+        id: app.id,
+      });
+      await loadRegisteredApps();
+      handleEdit(newApp);
+    } catch (error) {
+      console.error("Failed to duplicate app:", error);
+      alert("複製に失敗しました");
+    }
+  };
+
+  // 実行ファイルのある場所をファイルマネージャーで開く
+  const handleRevealLocation = async (app: RegisteredApp) => {
+    try {
+      await invoke("reveal_app_location", { appId: app.id });
+    } catch (error) {
+      console.error("Failed to reveal app location:", error);
+      alert(`場所を開けませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleOpenLog = async (app: RegisteredApp) => {
+    try {
+      await invoke("open_app_log_file", { id: app.id });
+    } catch (error) {
+      console.error("Failed to open log file:", error);
+      alert(`ログファイルを開けませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 設定ファイルから読み込み直す（外部で編集した場合など）
+  const handleScanStartMenu = async () => {
+    setIsScanning(true);
+    try {
+      const candidates = await invoke<RegisteredApp[]>("scan_start_menu");
+      setStartMenuCandidates(candidates);
+    } catch (error) {
+      console.error("Failed to scan Start Menu:", error);
+      alert(`スタートメニューを読み取れませんでした: ${errorMessage(error)}`);
+    } finally {
+      setIsScanning(false);
+    }
+  };
+
+  // 候補の名前・パス・引数を入力した状態で追加フォームを開く
+  const handleAddCandidate = (candidate: RegisteredApp) => {
+    resetForm();
+    setFormData((prev) => ({
+      ...prev,
+      name: candidate.name,
+      path: candidate.path,
+      arguments: candidate.arguments,
+    }));
+    setStartMenuCandidates(
+      (prev) => prev?.filter((c) => c.id !== candidate.id) ?? null
+    );
+    setShowAddForm(true);
+  };
+
+  // 実行中のプロセスの一覧を取得
+  const handleListProcesses = async () => {
+    try {
+      setSystemProcesses(
+        await invoke<SystemProcess[]>("list_system_processes")
+      );
+    } catch (error) {
+      console.error("Failed to list processes:", error);
+      alert(`プロセスの一覧を取得できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // プロセスの実行ファイルと引数を入力した状態で追加フォームを開く
+  const handleAddProcess = (process: SystemProcess) => {
+    resetForm();
+    const args = process.cmd
+      .slice(1)
+      .map((arg) => (/\s/.test(arg) || arg === "" ? `"${arg}"` : arg))
+      .join(" ");
+    setFormData((prev) => ({
+      ...prev,
+      name: process.name.replace(/\.exe$/i, ""),
+      path: process.exe_path,
+      arguments: args,
+    }));
+    setSystemProcesses(null);
+    setShowAddForm(true);
+  };
+
+  // アプリの一覧をJSONとしてクリップボードへコピー
+  const handleCopyJson = async () => {
+    try {
+      const json = await invoke<string>("export_apps_json");
+      await navigator.clipboard.writeText(json);
+      alert("アプリの一覧をクリップボードにコピーしました");
+    } catch (error) {
+      console.error("Failed to export apps:", error);
+      alert(`コピーに失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  // 貼り付けたJSONからアプリを追加・置き換え
+  const handlePasteJson = async () => {
+    const json = prompt("アプリの一覧のJSONを貼り付けてください");
+    if (!json) return;
+    const merge = confirm(
+      "現在のアプリに追加しますか？\n（キャンセルすると現在のアプリを置き換えます）"
+    );
+    if (!merge && !confirm("現在のアプリをすべて置き換えます。よろしいですか？")) {
+      return;
+    }
+    try {
+      const apps = await invoke<RegisteredApp[]>("import_apps_json", {
+        json,
+        merge,
+      });
+      setRegisteredApps(apps);
+    } catch (error) {
+      console.error("Failed to import apps:", error);
+      alert(`インポートに失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  // 他のランチャーからエクスポートしたJSONを読み込んで追加
+  const handleImportForeign = async () => {
+    try {
+      const formats = await invoke<ForeignFormat[]>("get_foreign_config_formats");
+      const format = prompt(
+        `形式を入力してください\n${formats
+          .map((f) => `${f.id}: ${f.description}`)
+          .join("\n")}`,
+        formats[0]?.id ?? ""
+      );
+      if (!format) return;
+      const json = prompt("他のランチャーの設定のJSONを貼り付けてください");
+      if (!json) return;
+      const apps = await invoke<RegisteredApp[]>("import_foreign_config", {
+        format,
+        json,
+      });
+      setRegisteredApps(apps);
+    } catch (error) {
+      console.error("Failed to import foreign config:", error);
+      alert(`インポートに失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  // 現在の実行ファイルのSHA-256を計算して入力する（大きなファイルは時間がかかる）
+  const handleComputeHash = async () => {
+    setIsComputingHash(true);
+    try {
+      const hash = await invoke<string>("compute_file_hash", {
+        path: formData.path,
+      });
+      setFormData((prev) => ({ ...prev, expectedSha256: hash }));
+    } catch (error) {
+      console.error("Failed to compute file hash:", error);
+      alert(`SHA-256を計算できませんでした: ${errorMessage(error)}`);
+    } finally {
+      setIsComputingHash(false);
+    }
+  };
+
+  // 引数の {secret:NAME} で使う値をOSの資格情報の保管場所に保存・削除する
+  const handleManageSecret = async () => {
+    const name = prompt(
+      "秘密の名前を入力してください（引数では {secret:名前} と書きます）"
+    )?.trim();
+    if (!name) return;
+    const value = prompt(
+      `「${name}」の値を入力してください\n（空のままOKすると保存した値を削除します）`
+    );
+    if (value === null) return;
+    try {
+      if (value === "") {
+        await invoke("delete_secret", { name });
+        alert(`「${name}」を削除しました`);
+      } else {
+        await invoke("set_secret", { name, value });
+        alert(`「${name}」を保存しました`);
+      }
+    } catch (error) {
+      console.error("Failed to update secret:", error);
+      alert(`秘密の値を保存・削除できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 別のユーザーとして実行する場合のパスワードをOSの資格情報の保管場所に保存・削除する
+  const handleRunAsPassword = async () => {
+    const user = formData.runAsUser.trim();
+    const password = prompt(
+      `「${user}」のパスワードを入力してください\n（空のままOKすると保存したパスワードを削除します）`
+    );
+    if (password === null) return;
+    try {
+      if (password === "") {
+        await invoke("delete_run_as_password", { user });
+        alert(`「${user}」のパスワードを削除しました`);
+      } else {
+        await invoke("set_run_as_password", { user, password });
+        alert(`「${user}」のパスワードを保存しました`);
+      }
+    } catch (error) {
+      console.error("Failed to update run-as password:", error);
+      alert(`パスワードを保存・削除できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // OSに登録した自動起動・ホットキーなどを解除する（ランチャーを使わなくなる場合向け）
+  const handleCleanup = async () => {
+    if (
+      !confirm(
+        "自動起動・ホットキー・ディープリンクなど、ランチャーがOSに登録したものを解除しますか？"
+      )
+    ) {
+      return;
+    }
+    const removeData = confirm(
+      "設定ファイルとログも削除しますか？\n（キャンセルすると残します）"
+    );
+    try {
+      const summary = await invoke<string[]>("cleanup_system_integration", {
+        removeData,
+      });
+      alert(`解除しました:\n${summary.join("\n")}`);
+      if (removeData && confirm("ランチャーを終了しますか？")) {
+        await invoke("confirm_quit");
+      }
+    } catch (error) {
+      console.error("Failed to clean up system integration:", error);
+      alert(`解除に失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  // 設定とログをポータブルモードのディレクトリ・ユーザーごとの設定ディレクトリへ移動
+  const handleMigrateConfig = async () => {
+    const answer = prompt(
+      "移動先を入力してください\nportable: 実行ファイルと同じ場所の portable ディレクトリ\nuser: ユーザーごとの設定ディレクトリ",
+      "portable"
+    );
+    const target = answer?.trim().toLowerCase();
+    if (!target) return;
+    if (target !== "portable" && target !== "user") {
+      alert("portable か user を入力してください");
+      return;
+    }
+
+    const migrate = (onConflict: string | null) =>
+      invoke<string>("migrate_config_location", { target, onConflict });
+    try {
+      let dir: string;
+      try {
+        dir = await migrate(null);
+      } catch (error) {
+        if (errorCode(error) !== "ALREADY_EXISTS") throw error;
+        let onConflict: string;
+        if (
+          confirm(
+            "移動先に設定ファイルがあります。移動先にだけあるアプリを加えてマージしますか？\n（キャンセルすると上書きするか確認します）"
+          )
+        ) {
+          onConflict = "merge";
+        } else if (confirm("移動先の設定ファイルを上書きしますか？")) {
+          onConflict = "overwrite";
+        } else {
+          return;
+        }
+        dir = await migrate(onConflict);
+      }
+      alert(`設定を移動しました: ${dir}`);
+      loadRegisteredApps();
+    } catch (error) {
+      console.error("Failed to migrate config:", error);
+      alert(`設定を移動できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 直前の変更を元に戻す（redo が true ならやり直す）
+  const handleUndo = async (redo: boolean) => {
+    try {
+      const apps = await invoke<RegisteredApp[]>(
+        redo ? "redo_last_change" : "undo_last_change"
+      );
+      setRegisteredApps(apps);
+    } catch (error) {
+      console.error("Failed to undo/redo config change:", error);
+      alert(
+        redo
+          ? `やり直せませんでした: ${errorMessage(error)}`
+          : `元に戻せませんでした: ${errorMessage(error)}`
+      );
+    }
+  };
+
+  // 現在の設定をバックアップとして保存する
+  const handleBackupConfig = async () => {
+    try {
+      const path = await invoke<string>("backup_config");
+      alert(`設定をバックアップしました:\n${path}`);
+      if (configBackups) {
+        setConfigBackups(await invoke<ConfigBackup[]>("list_config_backups"));
+      }
+    } catch (error) {
+      console.error("Failed to back up config:", error);
+      alert(`バックアップできませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleShowBackups = async () => {
+    try {
+      setConfigBackups(await invoke<ConfigBackup[]>("list_config_backups"));
+    } catch (error) {
+      console.error("Failed to list config backups:", error);
+      alert(`バックアップの一覧を読み込めませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // バックアップから復元する（「元に戻す」で復元前の設定に戻せる）
+  const handleRestoreBackup = async (backup: ConfigBackup) => {
+    if (
+      !confirm(
+        `このバックアップで現在の設定を置き換えますか？\n${backup.path}\n（「元に戻す」で復元前に戻せます）`
+      )
+    )
+      return;
+    try {
+      const apps = await invoke<RegisteredApp[]>("restore_config_backup", {
+        path: backup.path,
+      });
+      setRegisteredApps(apps);
+      setConfigBackups(null);
+    } catch (error) {
+      console.error("Failed to restore config backup:", error);
+      alert(`復元できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // すべてのアプリの設定を確認し、起動に失敗しそうな問題を一覧にする
+  const handleValidateConfig = async () => {
+    try {
+      setValidationIssues(await invoke<ValidationIssue[]>("validate_config"));
+    } catch (error) {
+      console.error("Failed to validate config:", error);
+      alert(`設定を確認できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 環境と状態をまとめた報告をクリップボードへコピー（不具合の報告用）
+  const handleCopyDiagnostics = async () => {
+    try {
+      const d = await invoke<Diagnostics>("run_diagnostics");
+      const report = [
+        `Launcher: ${d.launcher_version}`,
+        `OS: ${d.os_version ?? d.os} (${d.os}, ${d.arch})`,
+        `PowerShell: ${d.powershell.available ? "available" : "unavailable"}${
+          d.powershell.degraded ? " (degraded)" : ""
+        }`,
+        `Config: ${d.config.path} (${
+          d.config.exists ? `${d.config.size_bytes} bytes` : "missing"
+        }, ${d.config_writable ? "writable" : "read-only"})`,
+        `Profile: ${d.config.profile ?? "default"}`,
+        `Safe mode: ${d.safe_mode}`,
+        `Apps: ${d.registered_apps} registered, ${d.running_apps} running`,
+        `Autostart: registered=${d.autostart.registered}, up_to_date=${d.autostart.up_to_date}, wanted=${d.autostart.wanted}`,
+        `Validation issues: ${d.validation_issues.length}`,
+        ...d.validation_issues.map(
+          (issue) =>
+            `  - [${issue.severity}] ${issue.app_id} ${issue.field}: ${issue.message}`
+        ),
+      ].join("\n");
+      await navigator.clipboard.writeText(report);
+      alert(`診断情報をクリップボードにコピーしました:\n\n${report}`);
+    } catch (error) {
+      console.error("Failed to run diagnostics:", error);
+      alert(`診断情報を取得できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 有効なアプリの起動コマンドをスクリプトにしてコピーする（ランチャーの外での起動・バックアップ用）
+  const handleCopyLaunchScript = async () => {
+    const shell = prompt(
+      "スクリプトの種類を入力してください（powershell / cmd / bash）",
+      "powershell"
+    )?.trim();
+    if (!shell) return;
+    try {
+      const script = await invoke<string>("export_launch_script", { shell });
+      await navigator.clipboard.writeText(script);
+      alert("起動スクリプトをクリップボードにコピーしました");
+    } catch (error) {
+      console.error("Failed to export launch script:", error);
+      alert(`起動スクリプトを作成できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 最近の起動の履歴を新しい順に表示する
+  const handleShowHistory = async () => {
+    try {
+      setLaunchHistory(
+        await invoke<LaunchEvent[]>("get_launch_history", { limit: 100 })
+      );
+    } catch (error) {
+      console.error("Failed to load launch history:", error);
+      alert(`起動の履歴を読み込めませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleClearHistory = async () => {
+    if (!confirm("起動の履歴をすべて消去しますか？")) return;
+    try {
+      await invoke("clear_launch_history");
+      setLaunchHistory([]);
+    } catch (error) {
+      console.error("Failed to clear launch history:", error);
+      alert(`起動の履歴を消去できませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // ランチャーをOSの自動起動に直接登録・解除する
+  const handleToggleLauncherAutostart = async () => {
+    if (!autostartStatus) {
+      return;
+    }
+    try {
+      await invoke("set_launcher_autostart", {
+        enabled: !autostartStatus.registered,
+      });
+      setAutostartStatus(await invoke<AutostartStatus>("get_autostart_status"));
+    } catch (error) {
+      console.error("Failed to set launcher autostart:", error);
+      alert(`OSの自動起動の変更に失敗しました: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleReload = async () => {
+    try {
+      const apps = await invoke<RegisteredApp[]>("reload_config");
+      setRegisteredApps(apps);
+    } catch (error) {
+      console.error("Failed to reload config:", error);
+      alert(`設定ファイルを読み込めませんでした: ${errorMessage(error)}`);
+    }
+  };
+
+  // 表示順を1つ上下に移動
+  const handleMove = async (index: number, offset: number) => {
+    const target = index + offset;
+    if (target < 0 || target >= registeredApps.length) return;
+
+    const ids = registeredApps.map((app) => app.id);
+    [ids[index], ids[target]] = [ids[target], ids[index]];
+    try {
+      await invoke("reorder_apps", { orderedIds: ids });
+      loadRegisteredApps();
+    } catch (error) {
+      console.error("Failed to reorder apps:", error);
+      alert("並べ替えに失敗しました");
+    }
+  };
+
   const selectFile = async () => {
     try {
       const path = await invoke<string | null>("open_file_dialog");
@@ -148,6 +1138,52 @@ export default function Settings({ onClose }: SettingsProps) {
           </button>
         </div>
 
+        {configInfo && (
+          <p className="config-info" title={configInfo.path}>
+            設定ファイル: {configInfo.path}
+            {configInfo.exists
+              ? `（${configInfo.size_bytes.toLocaleString()} バイト${
+                  configInfo.modified
+                    ? `、${new Date(configInfo.modified).toLocaleString()} に更新`
+                    : ""
+                }）`
+              : "（未作成）"}
+            {configInfo.profile && ` プロファイル: ${configInfo.profile}`}
+          </p>
+        )}
+        {autostartStatus && (
+          <p
+            className={`config-info${
+              autostartStatus.registered !== autostartStatus.wanted ||
+              (autostartStatus.registered && !autostartStatus.up_to_date)
+                ? " degraded-warning"
+                : ""
+            }`}
+            title={autostartStatus.location}
+          >
+            OSの自動起動: {autostartStatus.registered ? "登録済み" : "未登録"}
+            {autostartStatus.registered &&
+              !autostartStatus.up_to_date &&
+              "（別の場所の実行ファイルを起動します）"}
+            {autostartStatus.registered !== autostartStatus.wanted &&
+              (autostartStatus.wanted
+                ? "（自動起動するアプリがありますが、登録されていません）"
+                : "（自動起動するアプリはありません）")}{" "}
+            <button type="button" onClick={handleToggleLauncherAutostart}>
+              {autostartStatus.registered ? "解除" : "登録"}
+            </button>
+          </p>
+        )}
+        {powerShellStatus?.degraded && (
+          <p
+            className="config-info degraded-warning"
+            title={powerShellStatus.limitations.join("\n")}
+          >
+            ⚠️
+            PowerShellを使用できないため、一部の機能が制限されています（管理者として実行、ショートカット・実行ファイル以外の起動、スタートメニューからの追加）
+          </p>
+        )}
+
         <div className="settings-content">
           {isLoading ? (
             <div className="loading">読み込み中...</div>
@@ -169,39 +1205,379 @@ export default function Settings({ onClose }: SettingsProps) {
                   >
                     ➕ 追加
                   </button>
-                </div>
-
-                {registeredApps.length === 0 ? (
-                  <p className="no-apps">
-                    登録されたアプリケーションがありません
-                  </p>
-                ) : (
-                  <div className="apps-list">
-                    {registeredApps.map((app) => (
-                      <div key={app.id} className="app-card">
-                        <div className="app-details">
-                          <h4>{app.name}</h4>
-                          <p className="app-path">{app.path}</p>
-                          {app.arguments && (
-                            <p className="app-args">引数: {app.arguments}</p>
-                          )}
-                          {app.description && (
-                            <p className="app-description">{app.description}</p>
-                          )}
-                          <div className="app-settings">
-                            <span
-                              className={`status ${
-                                app.auto_start ? "enabled" : "disabled"
-                              }`}
-                            >
-                              {app.auto_start ? "自動起動" : "手動起動"}
-                            </span>
-                            {app.delay && app.delay > 0 && (
-                              <span className="delay">遅延: {app.delay}秒</span>
-                            )}
-                            {app.prevent_duplicate && (
-                              <span className="prevent-duplicate">
-                                重複起動防止
+                  <button
+                    className="add-btn"
+                    onClick={handleReload}
+                    type="button"
+                    title="設定ファイルを読み込み直す"
+                  >
+                    🔄 再読み込み
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={() => handleUndo(false)}
+                    type="button"
+                    title="直前の設定の変更を元に戻す"
+                  >
+                    ↩️ 元に戻す
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={() => handleUndo(true)}
+                    type="button"
+                    title="元に戻した変更をやり直す"
+                  >
+                    ↪️ やり直す
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleBackupConfig}
+                    type="button"
+                    title="現在の設定をバックアップとして保存する（最新の20件まで残す）"
+                  >
+                    💾 バックアップ
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleShowBackups}
+                    type="button"
+                    title="保存したバックアップから設定を復元する"
+                  >
+                    🗂️ バックアップから復元
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleValidateConfig}
+                    type="button"
+                    title="すべてのアプリの設定に問題がないか確認する"
+                  >
+                    🩺 設定を確認
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleCopyDiagnostics}
+                    type="button"
+                    title="不具合の報告に貼り付ける環境・状態の情報をコピーする"
+                  >
+                    📋 診断情報をコピー
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleCopyLaunchScript}
+                    type="button"
+                    title="有効なアプリの起動コマンドをスクリプトにしてコピーする"
+                  >
+                    📜 起動スクリプトをコピー
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleShowHistory}
+                    type="button"
+                    title="最近起動したアプリと結果を新しい順に表示する"
+                  >
+                    📜 起動の履歴
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleCopyJson}
+                    type="button"
+                    title="アプリの一覧をJSONとしてコピー"
+                  >
+                    📤 コピー
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handlePasteJson}
+                    type="button"
+                    title="JSONを貼り付けてアプリを追加"
+                  >
+                    📥 貼り付け
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleImportForeign}
+                    type="button"
+                    title="他のランチャーの設定からアプリを追加"
+                  >
+                    📥 他のランチャーから
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleManageSecret}
+                    type="button"
+                    title="引数の {secret:名前} で使う値を保存・削除する"
+                  >
+                    🔑 秘密の値
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleCleanup}
+                    type="button"
+                    title="自動起動・ホットキーなど、OSへの登録を解除する"
+                  >
+                    🧹 登録を解除
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleMigrateConfig}
+                    type="button"
+                    title="設定とログをポータブルモード・ユーザーごとの設定ディレクトリへ移動する"
+                  >
+                    🚚 保存場所を移動
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleScanStartMenu}
+                    type="button"
+                    disabled={isScanning}
+                    title="スタートメニューのショートカットから追加するアプリを選ぶ（Windowsのみ）"
+                  >
+                    {isScanning ? "読み取り中..." : "📋 スタートメニューから"}
+                  </button>
+                  <button
+                    className="add-btn"
+                    onClick={handleListProcesses}
+                    type="button"
+                    title="実行中のプロセスから追加するアプリを選ぶ"
+                  >
+                    ⚙️ 実行中のプロセスから
+                  </button>
+                </div>
+
+                {validationIssues && (
+                  <div className="start-menu-candidates validation-report">
+                    <div className="section-header">
+                      <h4>設定の確認結果（{validationIssues.length}件）</h4>
+                      <button
+                        className="cancel-btn"
+                        onClick={() => setValidationIssues(null)}
+                        type="button"
+                      >
+                        閉じる
+                      </button>
+                    </div>
+                    {validationIssues.length === 0 ? (
+                      <p className="no-apps">問題は見つかりませんでした</p>
+                    ) : (
+                      <ul>
+                        {validationIssues.map((issue, index) => (
+                          <li
+                            key={`${issue.app_id}-${issue.field}-${index}`}
+                            className={`validation-${issue.severity}`}
+                          >
+                            {issue.severity === "error" ? "❌" : "⚠️"}{" "}
+                            <strong>
+                              {registeredApps.find((a) => a.id === issue.app_id)
+                                ?.name || issue.app_id}
+                            </strong>{" "}
+                            ({issue.field}): {issue.message}
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+
+                {configBackups && (
+                  <div className="start-menu-candidates validation-report">
+                    <div className="section-header">
+                      <h4>設定のバックアップ（{configBackups.length}件）</h4>
+                      <button
+                        className="cancel-btn"
+                        onClick={() => setConfigBackups(null)}
+                        type="button"
+                      >
+                        閉じる
+                      </button>
+                    </div>
+                    {configBackups.length === 0 ? (
+                      <p className="no-apps">バックアップはありません</p>
+                    ) : (
+                      <ul>
+                        {configBackups.map((backup) => (
+                          <li key={backup.path}>
+                            {backup.created
+                              ? new Date(backup.created).toLocaleString()
+                              : backup.path}{" "}
+                            （{backup.size_bytes.toLocaleString()} バイト）{" "}
+                            <button
+                              className="edit-btn"
+                              onClick={() => handleRestoreBackup(backup)}
+                              type="button"
+                              title={backup.path}
+                            >
+                              復元
+                            </button>
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+
+                {launchHistory && (
+                  <div className="start-menu-candidates validation-report">
+                    <div className="section-header">
+                      <h4>起動の履歴（{launchHistory.length}件）</h4>
+                      <div>
+                        <button
+                          className="cancel-btn"
+                          onClick={handleClearHistory}
+                          type="button"
+                          disabled={launchHistory.length === 0}
+                        >
+                          消去
+                        </button>
+                        <button
+                          className="cancel-btn"
+                          onClick={() => setLaunchHistory(null)}
+                          type="button"
+                        >
+                          閉じる
+                        </button>
+                      </div>
+                    </div>
+                    {launchHistory.length === 0 ? (
+                      <p className="no-apps">履歴はありません</p>
+                    ) : (
+                      <ul>
+                        {launchHistory.map((event, index) => (
+                          <li
+                            key={`${event.timestamp}-${event.app_id}-${index}`}
+                            className={
+                              event.success ? undefined : "validation-error"
+                            }
+                          >
+                            {event.success ? "✅" : "❌"}{" "}
+                            {new Date(event.timestamp).toLocaleString()}{" "}
+                            <strong>{event.name}</strong>
+                            {event.error && `: ${event.error}`}
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                  </div>
+                )}
+
+                {startMenuCandidates && (
+                  <div className="start-menu-candidates">
+                    <div className="section-header">
+                      <h4>
+                        スタートメニューのアプリ（{startMenuCandidates.length}件）
+                      </h4>
+                      <button
+                        className="cancel-btn"
+                        onClick={() => setStartMenuCandidates(null)}
+                        type="button"
+                      >
+                        閉じる
+                      </button>
+                    </div>
+                    {startMenuCandidates.length === 0 ? (
+                      <p className="no-apps">追加できるアプリが見つかりません</p>
+                    ) : (
+                      <div className="apps-list">
+                        {startMenuCandidates.map((candidate) => (
+                          <div key={candidate.id} className="app-card">
+                            <div className="app-details">
+                              <h4>{candidate.name}</h4>
+                              <p className="app-path">{candidate.path}</p>
+                              {candidate.arguments && (
+                                <p className="app-args">
+                                  引数: {candidate.arguments}
+                                </p>
+                              )}
+                            </div>
+                            <div className="app-actions">
+                              <button
+                                className="edit-btn"
+                                onClick={() => handleAddCandidate(candidate)}
+                              >
+                                追加
+                              </button>
+                            </div>
+                          </div>
+                        ))}
+                      </div>
+                    )}
+                  </div>
+                )}
+
+                {systemProcesses && (
+                  <div className="start-menu-candidates">
+                    <div className="section-header">
+                      <h4>実行中のプロセス（{systemProcesses.length}件）</h4>
+                      <button
+                        className="cancel-btn"
+                        onClick={() => setSystemProcesses(null)}
+                        type="button"
+                      >
+                        閉じる
+                      </button>
+                    </div>
+                    {systemProcesses.length === 0 ? (
+                      <p className="no-apps">追加できるプロセスが見つかりません</p>
+                    ) : (
+                      <div className="apps-list">
+                        {systemProcesses.map((process) => (
+                          <div key={process.pid} className="app-card">
+                            <div className="app-details">
+                              <h4>
+                                {process.name}（PID: {process.pid}）
+                              </h4>
+                              <p className="app-path">{process.exe_path}</p>
+                              {process.cmd.length > 1 && (
+                                <p className="app-args">
+                                  引数: {process.cmd.slice(1).join(" ")}
+                                </p>
+                              )}
+                            </div>
+                            <div className="app-actions">
+                              <button
+                                className="edit-btn"
+                                onClick={() => handleAddProcess(process)}
+                              >
+                                追加
+                              </button>
+                            </div>
+                          </div>
+                        ))}
+                      </div>
+                    )}
+                  </div>
+                )}
+
+                {registeredApps.length === 0 ? (
+                  <p className="no-apps">
+                    登録されたアプリケーションがありません
+                  </p>
+                ) : (
+                  <div className="apps-list">
+                    {registeredApps.map((app, index) => (
+                      <div key={app.id} className="app-card">
+                        <div className="app-details">
+                          <h4>{app.name}</h4>
+                          <p className="app-path">{app.path}</p>
+                          {app.arguments && (
+                            <p className="app-args">引数: {app.arguments}</p>
+                          )}
+                          {app.description && (
+                            <p className="app-description">{app.description}</p>
+                          )}
+                          <div className="app-settings">
+                            <span
+                              className={`status ${
+                                app.auto_start ? "enabled" : "disabled"
+                              }`}
+                            >
+                              {app.auto_start ? "自動起動" : "手動起動"}
+                            </span>
+                            {app.delay && app.delay > 0 && (
+                              <span className="delay">遅延: {app.delay}秒</span>
+                            )}
+                            {app.prevent_duplicate && (
+                              <span className="prevent-duplicate">
+                                重複起動防止
                               </span>
                             )}
                             {app.auto_start && (
@@ -210,12 +1586,49 @@ export default function Settings({ onClose }: SettingsProps) {
                           </div>
                         </div>
                         <div className="app-actions">
+                          <button
+                            className="move-btn"
+                            onClick={() => handleMove(index, -1)}
+                            disabled={index === 0}
+                            title="上へ移動"
+                          >
+                            ▲
+                          </button>
+                          <button
+                            className="move-btn"
+                            onClick={() => handleMove(index, 1)}
+                            disabled={index === registeredApps.length - 1}
+                            title="下へ移動"
+                          >
+                            ▼
+                          </button>
                           <button
                             className="edit-btn"
                             onClick={() => handleEdit(app)}
                           >
                             編集
                           </button>
+                          <button
+                            className="edit-btn"
+                            onClick={() => handleDuplicate(app)}
+                          >
+                            複製
+                          </button>
+                          <button
+                            className="edit-btn"
+                            onClick={() => handleRevealLocation(app)}
+                            title="実行ファイルのある場所を開く"
+                          >
+                            場所
+                          </button>
+                          {app.log_output && (
+                            <button
+                              className="edit-btn"
+                              onClick={() => handleOpenLog(app)}
+                            >
+                              ログ
+                            </button>
+                          )}
                           <button
                             className="delete-btn"
                             onClick={() => handleDelete(app)}
@@ -251,7 +1664,7 @@ export default function Settings({ onClose }: SettingsProps) {
                     </div>
 
                     <div className="form-group">
-                      <label>実行ファイルパス *</label>
+                      <label>実行ファイルパス / URL *</label>
                       <div className="path-input">
                         <input
                           type="text"
@@ -259,7 +1672,7 @@ export default function Settings({ onClose }: SettingsProps) {
                           onChange={(e) =>
                             setFormData({ ...formData, path: e.target.value })
                           }
-                          placeholder="C:\path\to\app.exe"
+                          placeholder="C:\path\to\app.exe または https://..."
                         />
                         <button type="button" onClick={selectFile}>
                           参照
@@ -267,6 +1680,24 @@ export default function Settings({ onClose }: SettingsProps) {
                       </div>
                     </div>
 
+                    {isPathPattern(formData.path) && (
+                      <div className="form-group">
+                        <label>ワイルドカードに複数一致した場合</label>
+                        <select
+                          value={formData.pathGlobPick}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              pathGlobPick: e.target.value as PathGlobPick,
+                            })
+                          }
+                        >
+                          <option value="highest_name">名前順で最後のもの</option>
+                          <option value="newest">更新日時が最も新しいもの</option>
+                        </select>
+                      </div>
+                    )}
+
                     <div className="form-group">
                       <label>引数</label>
                       <input
@@ -282,6 +1713,21 @@ export default function Settings({ onClose }: SettingsProps) {
                       />
                     </div>
 
+                    <div className="form-group">
+                      <label>引数のプリセット</label>
+                      <textarea
+                        value={formData.argPresetsText}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            argPresetsText: e.target.value,
+                          })
+                        }
+                        placeholder="名前=引数 (1行に1つ、起動時に既定の引数の代わりに選べる、オプション)"
+                        rows={2}
+                      />
+                    </div>
+
                     <div className="form-group">
                       <label>説明</label>
                       <input
@@ -343,11 +1789,720 @@ export default function Settings({ onClose }: SettingsProps) {
                         自動起動
                       </label>
                     </div>
-                  </div>
 
-                  <div className="form-actions">
-                    <button className="save-btn" onClick={handleSave}>
-                      {editingApp ? "更新" : "追加"}
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.logOutput}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              logOutput: e.target.checked,
+                            })
+                          }
+                        />
+                        出力をログファイルに保存
+                      </label>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.logTruncate}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              logTruncate: e.target.checked,
+                            })
+                          }
+                        />
+                        起動のたびにログを消去する
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>監視パス</label>
+                      <input
+                        type="text"
+                        value={formData.watchPath}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            watchPath: e.target.value,
+                          })
+                        }
+                        placeholder="変更を検知したら起動するファイル/フォルダ (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.watchRelaunchWhileRunning}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              watchRelaunchWhileRunning: e.target.checked,
+                            })
+                          }
+                        />
+                        実行中でも変更検知時に起動
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>作業ディレクトリ</label>
+                      <input
+                        type="text"
+                        value={formData.workingDirectory}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            workingDirectory: e.target.value,
+                          })
+                        }
+                        placeholder="起動時のカレントディレクトリ (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>環境変数</label>
+                      <textarea
+                        value={formData.envText}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            envText: e.target.value,
+                          })
+                        }
+                        placeholder="KEY=VALUE (1行に1つ、オプション)"
+                        rows={3}
+                      />
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.runAsAdmin}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              runAsAdmin: e.target.checked,
+                            })
+                          }
+                        />
+                        管理者として実行 (Windows)
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>別のユーザーとして実行（Windowsのみ）</label>
+                      <input
+                        type="text"
+                        value={formData.runAsUser}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            runAsUser: e.target.value,
+                          })
+                        }
+                        placeholder="ユーザー名（例: DOMAIN\user）。空欄の場合は現在のユーザー"
+                      />
+                      <button
+                        type="button"
+                        onClick={handleRunAsPassword}
+                        disabled={!formData.runAsUser.trim()}
+                        title="パスワードをOSの資格情報の保管場所に保存する"
+                      >
+                        パスワードを保存
+                      </button>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.autoRestart}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              autoRestart: e.target.checked,
+                            })
+                          }
+                        />
+                        異常終了時に自動で再起動
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>カテゴリ</label>
+                      <input
+                        type="text"
+                        value={formData.category}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            category: e.target.value,
+                          })
+                        }
+                        placeholder="例: 開発, ゲーム (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>先に起動するアプリ（自動起動時）</label>
+                      {registeredApps
+                        .filter((app) => app.id !== editingApp?.id)
+                        .map((app) => (
+                          <div key={app.id} className="checkbox-group">
+                            <label>
+                              <input
+                                type="checkbox"
+                                checked={formData.dependsOn.includes(app.id)}
+                                onChange={(e) =>
+                                  setFormData({
+                                    ...formData,
+                                    dependsOn: e.target.checked
+                                      ? [...formData.dependsOn, app.id]
+                                      : formData.dependsOn.filter(
+                                          (id) => id !== app.id
+                                        ),
+                                  })
+                                }
+                              />
+                              {app.name}
+                            </label>
+                          </div>
+                        ))}
+                    </div>
+
+                    <div className="form-group">
+                      <label>グローバルホットキー</label>
+                      <input
+                        type="text"
+                        value={formData.hotkey}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            hotkey: e.target.value,
+                          })
+                        }
+                        onBlur={(e) => checkHotkey(e.target.value)}
+                        placeholder="例: Ctrl+Alt+T (オプション)"
+                      />
+                      {hotkeyError && (
+                        <div className="config-info degraded-warning">
+                          ⚠️ {hotkeyError}
+                        </div>
+                      )}
+                    </div>
+
+                    <div className="form-group">
+                      <label>定時起動</label>
+                      <input
+                        type="text"
+                        value={formData.schedule}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            schedule: e.target.value,
+                          })
+                        }
+                        placeholder="例: 09:00 または 0 9 * * 1-5 (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>起動遅延（ミリ秒、指定時は秒より優先）</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.delayMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            delayMs: e.target.value,
+                          })
+                        }
+                        placeholder="例: 1500 (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>起動遅延のランダム幅（ミリ秒）</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.delayJitterMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            delayJitterMs: Number(e.target.value),
+                          })
+                        }
+                      />
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.skipIfRunning}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              skipIfRunning: e.target.checked,
+                            })
+                          }
+                        />
+                        既に実行中なら自動起動しない
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>既に実行中のアプリを起動した場合</label>
+                      <select
+                        value={formData.onAlreadyRunning}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            onAlreadyRunning: e.target.value as OnAlreadyRunning,
+                          })
+                        }
+                      >
+                        <option value="do_nothing">何もしない</option>
+                        <option value="focus">ウィンドウを前面に表示</option>
+                        <option value="relaunch">停止して起動し直す</option>
+                      </select>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.enabled}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              enabled: e.target.checked,
+                            })
+                          }
+                        />
+                        有効（自動起動・定時起動・グループ起動の対象にする）
+                      </label>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.captureOutput}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              captureOutput: e.target.checked,
+                            })
+                          }
+                        />
+                        出力をランチャーに表示する
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>優先度</label>
+                      <select
+                        value={formData.priority}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            priority: e.target.value,
+                          })
+                        }
+                      >
+                        <option value="">OSの既定</option>
+                        <option value="low">低</option>
+                        <option value="normal">通常</option>
+                        <option value="high">高</option>
+                      </select>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.useShell}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              useShell: e.target.checked,
+                            })
+                          }
+                        />
+                        シェル経由で実行（パイプ・&& などを使う場合。PIDの追跡はシェルのプロセスになります）
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>起動の再試行回数</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.launchRetries}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            launchRetries: Number(e.target.value),
+                          })
+                        }
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>再試行までの待ち時間（ミリ秒）</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.launchRetryDelayMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            launchRetryDelayMs: Number(e.target.value),
+                          })
+                        }
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>停止コマンド</label>
+                      <input
+                        type="text"
+                        value={formData.stopCommand}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            stopCommand: e.target.value,
+                          })
+                        }
+                        placeholder="例: mytool --shutdown"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>CPUアフィニティ</label>
+                      <input
+                        type="text"
+                        value={formData.cpuAffinity}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            cpuAffinity: e.target.value,
+                          })
+                        }
+                        placeholder="例: 0,1,2,3"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>ヘルスチェック</label>
+                      <select
+                        value={formData.healthCheckType}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            healthCheckType: e.target.value,
+                          })
+                        }
+                      >
+                        <option value="">なし</option>
+                        <option value="tcp">TCPポート</option>
+                        <option value="http">HTTP</option>
+                      </select>
+                      {formData.healthCheckType && (
+                        <input
+                          type="text"
+                          value={formData.healthCheckTarget}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              healthCheckTarget: e.target.value,
+                            })
+                          }
+                          placeholder={
+                            formData.healthCheckType === "tcp"
+                              ? "例: 8080 または localhost:8080"
+                              : "例: http://localhost:8080/health"
+                          }
+                        />
+                      )}
+                    </div>
+
+                    <div className="form-group">
+                      <label>自動起動の条件</label>
+                      <select
+                        value={formData.launchConditionType}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            launchConditionType: e.target.value,
+                          })
+                        }
+                      >
+                        <option value="">なし（常に起動する）</option>
+                        <option value="time_window">時間帯</option>
+                        <option value="process_running">
+                          プロセスが実行中
+                        </option>
+                        <option value="tcp_reachable">TCPで接続できる</option>
+                      </select>
+                      {formData.launchConditionType && (
+                        <input
+                          type="text"
+                          value={formData.launchConditionTarget}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              launchConditionTarget: e.target.value,
+                            })
+                          }
+                          placeholder={
+                            formData.launchConditionType === "time_window"
+                              ? "例: 09:00-17:00"
+                              : formData.launchConditionType ===
+                                  "process_running"
+                                ? "例: chrome.exe"
+                                : "例: vpn.example.com:443"
+                          }
+                        />
+                      )}
+                    </div>
+
+                    <div className="form-group">
+                      <label>起動前のコマンド</label>
+                      <input
+                        type="text"
+                        value={formData.preLaunch}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            preLaunch: e.target.value,
+                          })
+                        }
+                        placeholder="例: net use Z: \\server\share"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>起動後のコマンド</label>
+                      <input
+                        type="text"
+                        value={formData.postLaunch}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            postLaunch: e.target.value,
+                          })
+                        }
+                        placeholder="例: notify-send 起動しました"
+                      />
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.hooksRequired}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              hooksRequired: e.target.checked,
+                            })
+                          }
+                        />
+                        起動前・起動後のコマンドが失敗したらエラーにする
+                      </label>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.detached}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              detached: e.target.checked,
+                            })
+                          }
+                        />
+                        ランチャーから切り離して起動する（ランチャーを終了しても停止しない）
+                      </label>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.placeholders}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              placeholders: e.target.checked,
+                            })
+                          }
+                        />
+                        引数のプレースホルダー（{"{file}"}・{"{clipboard}"} など）を起動時に置き換える
+                      </label>
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.launchOncePerBoot}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              launchOncePerBoot: e.target.checked,
+                            })
+                          }
+                        />
+                        OSの起動ごとに1回だけ自動起動する（ランチャーを起動し直しても再び起動しない）
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>アイコンの画像（PNG・ICO、空なら実行ファイルのアイコン）</label>
+                      <input
+                        type="text"
+                        value={formData.iconPath}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            iconPath: e.target.value,
+                          })
+                        }
+                        placeholder="例: C:\icons\tool.png"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>色（一覧で見分けるため）</label>
+                      <input
+                        type="text"
+                        value={formData.color}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            color: e.target.value,
+                          })
+                        }
+                        placeholder="例: #4caf50"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>自動再起動する最短の動作時間(ms)</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.minHealthyRuntimeMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            minHealthyRuntimeMs: Number(e.target.value),
+                          })
+                        }
+                        placeholder="0で無効。これより早く終了した場合は起動直後の失敗とみなす"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>自動停止までの時間（ミリ秒）</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.autoStopAfterMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            autoStopAfterMs: e.target.value,
+                          })
+                        }
+                        placeholder="例: 3600000 (オプション)"
+                      />
+                    </div>
+
+                    <div className="form-group checkbox-group">
+                      <label>
+                        <input
+                          type="checkbox"
+                          checked={formData.autoElevate}
+                          onChange={(e) =>
+                            setFormData({
+                              ...formData,
+                              autoElevate: e.target.checked,
+                            })
+                          }
+                        />
+                        必要な場合だけ管理者として実行（Windows）
+                      </label>
+                    </div>
+
+                    <div className="form-group">
+                      <label>停止後の待機時間（ミリ秒）</label>
+                      <input
+                        type="number"
+                        min="0"
+                        value={formData.stopSettleMs}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            stopSettleMs: Number(e.target.value),
+                          })
+                        }
+                        placeholder="例: 1000（0で待たない）"
+                      />
+                    </div>
+
+                    <div className="form-group">
+                      <label>実行ファイルのSHA-256（一致しない場合は起動しない）</label>
+                      <input
+                        type="text"
+                        value={formData.expectedSha256}
+                        onChange={(e) =>
+                          setFormData({
+                            ...formData,
+                            expectedSha256: e.target.value,
+                          })
+                        }
+                        placeholder="空欄の場合は確認しない"
+                      />
+                      <button
+                        type="button"
+                        onClick={handleComputeHash}
+                        disabled={!formData.path.trim() || isComputingHash}
+                        title="現在の実行ファイルのSHA-256を入力する"
+                      >
+                        {isComputingHash ? "計算中..." : "現在のファイルから計算"}
+                      </button>
+                    </div>
+                  </div>
+
+                  <div className="form-actions">
+                    <button className="save-btn" onClick={handleSave}>
+                      {editingApp ? "更新" : "追加"}
+                    </button>
+                    <button
+                      className="cancel-btn"
+                      onClick={handleTestLaunch}
+                      disabled={isTesting || formData.useShell}
+                      title="登録せずに起動して、起動できるか確認する"
+                    >
+                      {isTesting ? "確認中..." : "試験起動"}
                     </button>
                     <button className="cancel-btn" onClick={resetForm}>
                       キャンセル
@@ -0,0 +1,21 @@
+import { CommandError } from "./types";
+
+// コマンドのエラーか（invoke の reject で受け取る値）
+export function isCommandError(error: unknown): error is CommandError {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    "message" in error
+  );
+}
+
+// 表示用のメッセージ（コマンドのエラー以外はそのまま文字列にする）
+export function errorMessage(error: unknown): string {
+  return isCommandError(error) ? error.message : String(error);
+}
+
+// エラーのコード（コマンドのエラー以外は null）
+export function errorCode(error: unknown): CommandError["code"] | null {
+  return isCommandError(error) ? error.code : null;
+}
@@ -9,6 +9,160 @@ export interface RegisteredApp {
   delay: number;
   prevent_duplicate: boolean;
   auto_start: boolean;
+  log_output: boolean;
+  watch_path: string | null;
+  watch_relaunch_while_running: boolean;
+  working_directory: string;
+  env: Record<string, string>;
+  run_as_admin: boolean;
+  auto_restart: boolean;
+  category: string;
+  sort_order: number;
+  depends_on: string[];
+  hotkey: string | null;
+  launch_count: number;
+  last_launched: string | null;
+  schedule: string | null;
+  delay_ms: number | null;
+  delay_jitter_ms: number;
+  skip_if_running: boolean;
+  enabled: boolean;
+  capture_output: boolean;
+  priority: string | null;
+  log_truncate: boolean;
+  use_shell: boolean;
+  launch_retries: number;
+  launch_retry_delay_ms: number;
+  stop_command: string | null;
+  cpu_affinity: number[] | null;
+  health_check: HealthCheck | null;
+  launch_condition: LaunchCondition | null;
+  pre_launch: string | null;
+  post_launch: string | null;
+  hooks_required: boolean;
+  detached: boolean;
+  placeholders: boolean;
+  launch_once_per_boot: boolean;
+  icon_path: string | null;
+  color: string | null;
+  arg_presets: ArgPreset[];
+  path_glob_pick: PathGlobPick;
+  min_healthy_runtime_ms: number;
+  auto_stop_after_ms: number | null;
+  auto_elevate: boolean;
+  on_already_running: OnAlreadyRunning;
+  stop_settle_ms: number;
+  expected_sha256: string | null;
+  run_as_user: string | null;
+}
+
+// パスのワイルドカードに複数のファイルが一致した場合に選ぶもの
+export type PathGlobPick = "highest_name" | "newest";
+
+// 既に実行中のアプリを起動しようとした場合の動作
+export type OnAlreadyRunning = "do_nothing" | "focus" | "relaunch";
+
+// 名前を付けた引数の組み合わせ
+export interface ArgPreset {
+  name: string;
+  arguments: string;
+}
+
+export interface LaunchGroup {
+  id: string;
+  name: string;
+  app_ids: string[];
+  max_concurrent: number | null;
+}
+
+// コマンドのエラー
+export interface CommandError {
+  code:
+    | "APP_NOT_FOUND"
+    | "GROUP_NOT_FOUND"
+    | "CONFIG_NOT_FOUND"
+    | "NOT_FOUND"
+    | "PROCESS_NOT_RUNNING"
+    | "ALREADY_EXISTS"
+    | "DUPLICATE_NAME"
+    | "INVALID_INPUT"
+    | "UNSUPPORTED"
+    | "PERMISSION_DENIED"
+    | "TIMEOUT"
+    | "LAUNCH_FAILED"
+    | "DEBOUNCED"
+    | "IO"
+    | "INTERNAL";
+  message: string;
+  detail: string | null;
+}
+
+export interface ProcessOutputPayload {
+  app_id: string;
+  stream: "stdout" | "stderr";
+  line: string;
+}
+
+export interface ExeInfo {
+  arch: string;
+  is_gui: boolean | null;
+  target_os: string;
+  warning: string | null;
+}
+
+export interface TestResult {
+  started: boolean;
+  pid: number | null;
+  exited: boolean;
+  exit_code: number | null;
+  stderr: string;
+  error: string | null;
+}
+
+export interface QuitRequestedPayload {
+  running_count: number;
+  stop_apps_on_quit: boolean;
+}
+
+// 自動起動の進み具合（startup-progress イベント）
+export interface StartupProgressPayload {
+  index: number;
+  total: number;
+  app_id: string;
+  phase: "waiting" | "launching" | "done" | "failed" | "skipped";
+  remaining_delay_ms: number;
+}
+
+// 自動起動したアプリ1つの結果（startup-summary イベント）
+export interface StartupSummaryEntry {
+  app_id: string;
+  name: string;
+  status: "launched" | "skipped" | "failed";
+  // スキップした理由・失敗したエラー
+  detail: string | null;
+}
+
+// 起動から一定時間が経って自動停止した（app-auto-stopped イベント）
+export interface AutoStoppedPayload {
+  app_id: string;
+  after_ms: number;
+  // 停止に失敗した場合のエラー
+  error: string | null;
+}
+
+// 起動直後の終了を繰り返している（flapping-detected イベント）
+export interface FlappingDetectedPayload {
+  app_id: string;
+  runtime_ms: number;
+  attempts: number;
+  gave_up: boolean;
+}
+
+export interface ProcessExitedPayload {
+  app_id: string;
+  pid: number;
+  exit_code: number | null;
+  timestamp: string;
 }
 
 export interface UtilityTool {
@@ -18,3 +172,115 @@ export interface UtilityTool {
   command: string;
   icon: string;
 }
+
+// アプリが使える状態かの確認方法（TCPのポート・HTTPのGET）
+export type HealthCheck = (
+  | { type: "tcp"; host: string; port: number }
+  | { type: "http"; url: string; expected_status: number }
+) & {
+  timeout_ms: number;
+  retries: number;
+  retry_interval_ms: number;
+};
+
+// 自動起動の条件（時間帯・プロセスの実行・TCPの接続）
+export type LaunchCondition =
+  | { type: "time_window"; start: string; end: string }
+  | { type: "process_running"; name: string }
+  | { type: "tcp_reachable"; host: string; port: number; timeout_ms: number };
+
+export type HealthStatus = "unknown" | "healthy" | "unhealthy";
+
+// 実行中のアプリのリソース使用量
+export interface ProcessStats {
+  pid: number;
+  cpu_percent: number;
+  memory_bytes: number;
+  uptime_secs: number;
+}
+
+// 設定の確認で見つかった問題（severity が error の場合は起動に失敗する）
+export interface ValidationIssue {
+  app_id: string;
+  field: string;
+  severity: "error" | "warning";
+  message: string;
+}
+
+// 起動の履歴
+export interface LaunchEvent {
+  app_id: string;
+  name: string;
+  timestamp: string;
+  success: boolean;
+  error?: string | null;
+}
+
+// OSの自動起動へのランチャーの登録状態（設定ではなくOSの実際の状態）
+export interface AutostartStatus {
+  registered: boolean;
+  // 登録されているエントリが今の実行ファイルを起動するか
+  up_to_date: boolean;
+  // 設定からは登録するべきか（自動起動が有効なアプリがある）
+  wanted: boolean;
+  location: string;
+}
+
+// PowerShellを使えるか（使えない場合に制限される機能）
+export interface PowerShellStatus {
+  available: boolean;
+  degraded: boolean;
+  limitations: string[];
+}
+
+// 設定のバックアップ
+export interface ConfigBackup {
+  path: string;
+  size_bytes: number;
+  created: string | null;
+}
+
+// 使用中の設定ファイルの情報
+export interface ConfigInfo {
+  path: string;
+  exists: boolean;
+  size_bytes: number;
+  modified: string | null;
+  profile: string | null;
+}
+
+// インポートできる他のランチャーの設定の形式
+export interface ForeignFormat {
+  id: string;
+  description: string;
+}
+
+// 他のランチャーの設定で変換できなかったエントリ（foreign-import-skipped イベント）
+export interface ForeignImportSkipped {
+  format: string;
+  skipped: { index: number; name: string | null; reason: string }[];
+}
+
+// 実行中のプロセス（list_system_processes）
+export interface SystemProcess {
+  pid: number;
+  name: string;
+  exe_path: string;
+  cmd: string[];
+}
+
+// 不具合の報告用の環境・状態のまとめ（run_diagnostics）
+export interface Diagnostics {
+  launcher_version: string;
+  os: string;
+  os_version: string | null;
+  arch: string;
+  powershell: PowerShellStatus;
+  config: ConfigInfo;
+  config_writable: boolean;
+  safe_mode: boolean;
+  registered_apps: number;
+  running_apps: number;
+  autostart: AutostartStatus;
+  validation_issues: ValidationIssue[];
+}